@@ -0,0 +1,1011 @@
+use std::{fmt, str::FromStr};
+
+use argmin::{
+    core::{ArgminError, CostFunction, Error, Gradient, Hessian, Jacobian, Operator},
+    solver::simulatedannealing::Anneal,
+};
+use ndarray::{Array1, Array2, Axis};
+
+use crate::{bounds::ParameterBounds, problems::Derivatives, results};
+
+/// Negates the cost, gradient and hessian of the inner problem, so argmin's minimizers maximize
+/// it. [`Negate::identity`] forwards everything untouched.
+///
+/// Least-squares methods minimize the residuals, which can't be negated: they are refused while
+/// maximizing. Anneal moves don't depend on the cost and are forwarded as is.
+#[derive(Debug, Clone)]
+pub struct Negate<P> {
+    inner: P,
+    sign: f64,
+}
+
+impl<P> Negate<P> {
+    pub fn new(inner: P) -> Self {
+        Self { inner, sign: -1.0 }
+    }
+
+    pub fn identity(inner: P) -> Self {
+        Self { inner, sign: 1.0 }
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn is_negated(&self) -> bool {
+        self.sign < 0.0
+    }
+
+    fn check_residuals(&self) -> Result<(), Error> {
+        if self.is_negated() {
+            return Err(ArgminError::NotImplemented {
+                text: "least squares can't maximize, the residuals can't be negated".to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Turns a row of a negated problem back into values of the original one, i.e. the best cost
+/// becomes the best (largest) value found.
+pub fn unnegate(mut result: results::Result) -> results::Result {
+    result.best_cost = -result.best_cost;
+    if let Some(history) = &mut result.best_cost_history {
+        history.iter_mut().for_each(|x| *x = -*x);
+    }
+    if let Some(trajectory) = &mut result.trajectory {
+        trajectory.costs.iter_mut().for_each(|x| *x = -*x);
+    }
+    result
+}
+
+impl<P: CostFunction<Output = f64>> CostFunction for Negate<P> {
+    type Param = P::Param;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.sign * self.inner.cost(param)?)
+    }
+}
+
+impl<P: Gradient<Gradient = Array1<f64>>> Gradient for Negate<P> {
+    type Param = P::Param;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self.sign * self.inner.gradient(param)?)
+    }
+}
+
+impl<P: Hessian<Hessian = Array2<f64>>> Hessian for Negate<P> {
+    type Param = P::Param;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(self.sign * self.inner.hessian(param)?)
+    }
+}
+
+impl<P: Operator> Operator for Negate<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.check_residuals()?;
+        self.inner.apply(param)
+    }
+}
+
+impl<P: Jacobian> Jacobian for Negate<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.check_residuals()?;
+        self.inner.jacobian(param)
+    }
+}
+
+impl<P: Anneal> Anneal for Negate<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        self.inner.anneal(param, temp)
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for Negate<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner.lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner.upper()
+    }
+}
+
+impl<P: Derivatives> Derivatives for Negate<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner.has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner.has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner.has_anneal()
+    }
+}
+
+/// Monotone transform of a non-negative cost applied by [`CostTransform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transform {
+    #[default]
+    None,
+    /// $\log(1 + f)$, compressing costs spanning many orders of magnitude.
+    Log,
+    /// $\sqrt{f}$, e.g. the residual norm of a sum of squares.
+    Sqrt,
+}
+
+impl Transform {
+    pub fn apply(&self, cost: f64) -> f64 {
+        match self {
+            Transform::None => cost,
+            Transform::Log => cost.ln_1p(),
+            Transform::Sqrt => cost.sqrt(),
+        }
+    }
+
+    /// Cost whose transform is `value`.
+    pub fn invert(&self, value: f64) -> f64 {
+        match self {
+            Transform::None => value,
+            Transform::Log => value.exp_m1(),
+            Transform::Sqrt => value.powi(2),
+        }
+    }
+
+    /// First and second derivatives of the transform at `cost`.
+    fn derivatives(&self, cost: f64) -> (f64, f64) {
+        match self {
+            Transform::None => (1.0, 0.0),
+            Transform::Log => (1.0 / (1.0 + cost), -1.0 / (1.0 + cost).powi(2)),
+            Transform::Sqrt => {
+                let root = cost.sqrt();
+                (0.5 / root, -0.25 / (root * cost))
+            }
+        }
+    }
+
+    /// Turns a row of a transformed problem back into costs of the original one.
+    pub fn restore(&self, mut result: results::Result) -> results::Result {
+        result.best_cost = self.invert(result.best_cost);
+        if let Some(history) = &mut result.best_cost_history {
+            history.iter_mut().for_each(|x| *x = self.invert(*x));
+        }
+        if let Some(trajectory) = &mut result.trajectory {
+            trajectory
+                .costs
+                .iter_mut()
+                .for_each(|x| *x = self.invert(*x));
+        }
+        result
+    }
+}
+
+impl FromStr for Transform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Transform::None),
+            "log" => Ok(Transform::Log),
+            "sqrt" => Ok(Transform::Sqrt),
+            _ => Err(format!(
+                "unknown cost transform `{s}`, expected log, sqrt or none"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Transform::None => "none",
+            Transform::Log => "log",
+            Transform::Sqrt => "sqrt",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Minimizes a [`Transform`] of the cost of the inner problem instead of the cost itself, with
+/// the gradient and hessian adjusted by the chain rule. The minimizers don't change, only how
+/// hard they are to reach.
+///
+/// The inner cost has to be non-negative, evaluations below zero fail. Where the cost is exactly
+/// zero the square root has no derivative, the gradient is set to zero there since it can only
+/// be a minimum. Residuals are forwarded untouched: least squares never sees the cost.
+#[derive(Debug, Clone)]
+pub struct CostTransform<P> {
+    inner: P,
+    transform: Transform,
+}
+
+impl<P> CostTransform<P> {
+    pub fn new(inner: P, transform: Transform) -> Self {
+        Self { inner, transform }
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+}
+
+impl<P: CostFunction<Output = f64>> CostTransform<P> {
+    /// Cost of the inner problem, checked to be in the domain of the transform.
+    fn inner_cost(&self, param: &P::Param) -> Result<f64, Error> {
+        let cost = self.inner.cost(param)?;
+        if self.transform != Transform::None && cost < 0.0 {
+            return Err(ArgminError::InvalidParameter {
+                text: format!(
+                    "`{}` cost transform needs a non-negative cost, got {cost}",
+                    self.transform
+                ),
+            }
+            .into());
+        }
+        Ok(cost)
+    }
+}
+
+impl<P: CostFunction<Output = f64>> CostFunction for CostTransform<P> {
+    type Param = P::Param;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.transform.apply(self.inner_cost(param)?))
+    }
+}
+
+impl<P> Gradient for CostTransform<P>
+where
+    P: CostFunction<Output = f64>
+        + Gradient<Param = <P as CostFunction>::Param, Gradient = Array1<f64>>,
+{
+    type Param = <P as CostFunction>::Param;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let gradient = self.inner.gradient(param)?;
+        if self.transform == Transform::None {
+            return Ok(gradient);
+        }
+        let cost = self.inner_cost(param)?;
+        if cost == 0.0 {
+            return Ok(Array1::zeros(gradient.len()));
+        }
+        Ok(self.transform.derivatives(cost).0 * gradient)
+    }
+}
+
+impl<P> Hessian for CostTransform<P>
+where
+    P: CostFunction<Output = f64>
+        + Gradient<Param = <P as CostFunction>::Param, Gradient = Array1<f64>>
+        + Hessian<Param = <P as CostFunction>::Param, Hessian = Array2<f64>>,
+{
+    type Param = <P as CostFunction>::Param;
+    type Hessian = Array2<f64>;
+
+    /// $ g'(f) \nabla^2 f + g''(f) \nabla f \nabla f^T $
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let hessian = self.inner.hessian(param)?;
+        if self.transform == Transform::None {
+            return Ok(hessian);
+        }
+        let cost = self.inner_cost(param)?;
+        if cost == 0.0 {
+            return Ok(hessian);
+        }
+        let gradient = self.inner.gradient(param)?;
+        let (first, second) = self.transform.derivatives(cost);
+        let outer = gradient
+            .view()
+            .insert_axis(Axis(1))
+            .dot(&gradient.view().insert_axis(Axis(0)));
+        Ok(first * hessian + second * outer)
+    }
+}
+
+impl<P: Operator> Operator for CostTransform<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.apply(param)
+    }
+}
+
+impl<P: Jacobian> Jacobian for CostTransform<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.inner.jacobian(param)
+    }
+}
+
+impl<P: Anneal> Anneal for CostTransform<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        self.inner.anneal(param, temp)
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for CostTransform<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner.lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner.upper()
+    }
+}
+
+impl<P: Derivatives> Derivatives for CostTransform<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner.has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner.has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner.has_anneal()
+    }
+}
+
+/// Diagonal change of variables $x = D u$: the solvers minimize $f(D u)$ over `u`, with the
+/// gradient $D \nabla f$ and hessian $D \nabla^2 f D$ of the chain rule. Scaling every axis
+/// to its natural size makes a badly scaled problem well conditioned for gradient methods.
+///
+/// Params, bounds and anneal moves are all in `u`, [`Rescale::restore`] maps a result back to
+/// the original variables. The entries of `D` have to be positive.
+#[derive(Debug, Clone)]
+pub struct Rescale<P> {
+    inner: P,
+    scale: Array1<f64>,
+}
+
+impl<P> Rescale<P> {
+    pub fn new(inner: P, scale: Array1<f64>) -> Self {
+        Self { inner, scale }
+    }
+
+    /// Leaves the `dim` variables untouched.
+    pub fn identity(inner: P, dim: usize) -> Self {
+        Self::new(inner, Array1::ones(dim))
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn scale(&self) -> &Array1<f64> {
+        &self.scale
+    }
+
+    /// $ x = D u $
+    pub fn to_original(&self, param: &Array1<f64>) -> Array1<f64> {
+        param * &self.scale
+    }
+
+    /// $ u = D^{-1} x $
+    pub fn to_scaled(&self, param: &Array1<f64>) -> Array1<f64> {
+        param / &self.scale
+    }
+
+    /// Turns a row of the rescaled problem back into params of the original one, the costs
+    /// are the same.
+    pub fn restore(&self, mut result: results::Result) -> results::Result {
+        let to_original = |param: &mut Vec<f64>| {
+            param.iter_mut().zip(&self.scale).for_each(|(x, d)| *x *= d);
+        };
+        if let Some(param) = &mut result.best_param {
+            to_original(param);
+        }
+        if let Some(trajectory) = &mut result.trajectory {
+            trajectory.params.iter_mut().for_each(to_original);
+        }
+        result
+    }
+}
+
+impl<P: CostFunction<Param = Array1<f64>, Output = f64>> CostFunction for Rescale<P> {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.cost(&self.to_original(param))
+    }
+}
+
+impl<P: Gradient<Param = Array1<f64>, Gradient = Array1<f64>>> Gradient for Rescale<P> {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self.inner.gradient(&self.to_original(param))? * &self.scale)
+    }
+}
+
+impl<P: Hessian<Param = Array1<f64>, Hessian = Array2<f64>>> Hessian for Rescale<P> {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let hessian = self.inner.hessian(&self.to_original(param))?;
+        // Columns, then rows.
+        Ok(hessian * &self.scale * self.scale.view().insert_axis(Axis(1)))
+    }
+}
+
+impl<P: Operator<Param = Array1<f64>>> Operator for Rescale<P> {
+    type Param = Array1<f64>;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.apply(&self.to_original(param))
+    }
+}
+
+impl<P: Jacobian<Param = Array1<f64>, Jacobian = Array2<f64>>> Jacobian for Rescale<P> {
+    type Param = Array1<f64>;
+    type Jacobian = Array2<f64>;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        Ok(self.inner.jacobian(&self.to_original(param))? * &self.scale)
+    }
+}
+
+impl<P: Anneal<Param = Array1<f64>, Output = Array1<f64>>> Anneal for Rescale<P> {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        Ok(self.to_scaled(&self.inner.anneal(&self.to_original(param), temp)?))
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for Rescale<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.to_scaled(&self.inner.lower())
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.to_scaled(&self.inner.upper())
+    }
+}
+
+impl<P: Derivatives> Derivatives for Rescale<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner.has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner.has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner.has_anneal()
+    }
+}
+
+/// Gives a cost-only problem a gradient by central finite differences,
+/// $\partial_k f \approx (f(x + h e_k) - f(x - h e_k)) / 2h$ with an absolute step $h$, so the
+/// gradient methods can run on it. Replaces the gradient of the inner problem when it has one,
+/// see [`MaybeApprox`] to keep it.
+///
+/// Every gradient costs `2 * dim` evaluations of the inner cost, which don't show up in the
+/// evaluation counts of the solver. The hessian is forwarded as is.
+#[derive(Debug, Clone)]
+pub struct ApproxGradient<P> {
+    inner: P,
+    step: f64,
+}
+
+impl<P> ApproxGradient<P> {
+    /// Finite differences with a step of `1e-6`, see [`Self::with_step`].
+    pub fn new(inner: P) -> Self {
+        Self { inner, step: 1e-6 }
+    }
+
+    /// Step of the finite differences, must be positive.
+    pub fn with_step(mut self, step: f64) -> Result<Self, Error> {
+        if !(step > 0.0 && step.is_finite()) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`step` must be positive, got {step}"),
+            }
+            .into());
+        }
+        self.step = step;
+        Ok(self)
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+}
+
+impl<P: CostFunction<Param = Array1<f64>, Output = f64>> CostFunction for ApproxGradient<P> {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.cost(param)
+    }
+}
+
+impl<P: CostFunction<Param = Array1<f64>, Output = f64>> Gradient for ApproxGradient<P> {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let step = self.step;
+        let mut moved = param.clone();
+        let mut gradient = Array1::zeros(param.len());
+        for k in 0..param.len() {
+            moved[k] = param[k] + step;
+            let forward = self.inner.cost(&moved)?;
+            moved[k] = param[k] - step;
+            let backward = self.inner.cost(&moved)?;
+            moved[k] = param[k];
+            gradient[k] = (forward - backward) / (2.0 * step);
+        }
+        Ok(gradient)
+    }
+}
+
+impl<P: Hessian> Hessian for ApproxGradient<P> {
+    type Param = P::Param;
+    type Hessian = P::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.inner.hessian(param)
+    }
+}
+
+impl<P: Operator> Operator for ApproxGradient<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.apply(param)
+    }
+}
+
+impl<P: Jacobian> Jacobian for ApproxGradient<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.inner.jacobian(param)
+    }
+}
+
+impl<P: Anneal> Anneal for ApproxGradient<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        self.inner.anneal(param, temp)
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for ApproxGradient<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner.lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner.upper()
+    }
+}
+
+impl<P: Derivatives> Derivatives for ApproxGradient<P> {
+    fn has_gradient(&self) -> bool {
+        true
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner.has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner.has_anneal()
+    }
+}
+
+/// The gradient of the inner problem, or an [`ApproxGradient`] of it, as a single type so the
+/// choice can be made at runtime. Everything else is forwarded to the inner problem.
+#[derive(Debug, Clone)]
+pub enum MaybeApprox<P> {
+    Exact(P),
+    Approx(ApproxGradient<P>),
+}
+
+impl<P> MaybeApprox<P> {
+    pub fn inner(&self) -> &P {
+        match self {
+            MaybeApprox::Exact(inner) => inner,
+            MaybeApprox::Approx(approx) => approx.inner(),
+        }
+    }
+}
+
+impl<P: CostFunction> CostFunction for MaybeApprox<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner().cost(param)
+    }
+}
+
+impl<P> Gradient for MaybeApprox<P>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+{
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        match self {
+            MaybeApprox::Exact(inner) => inner.gradient(param),
+            MaybeApprox::Approx(approx) => approx.gradient(param),
+        }
+    }
+}
+
+impl<P: Hessian> Hessian for MaybeApprox<P> {
+    type Param = P::Param;
+    type Hessian = P::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.inner().hessian(param)
+    }
+}
+
+impl<P: Operator> Operator for MaybeApprox<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner().apply(param)
+    }
+}
+
+impl<P: Jacobian> Jacobian for MaybeApprox<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.inner().jacobian(param)
+    }
+}
+
+impl<P: Anneal> Anneal for MaybeApprox<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        self.inner().anneal(param, temp)
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for MaybeApprox<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner().lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner().upper()
+    }
+}
+
+impl<P: Derivatives> Derivatives for MaybeApprox<P> {
+    fn has_gradient(&self) -> bool {
+        match self {
+            MaybeApprox::Exact(inner) => inner.has_gradient(),
+            MaybeApprox::Approx(approx) => approx.has_gradient(),
+        }
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner().has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner().has_anneal()
+    }
+}
+
+/// Runs a problem over `Vec<f64>` (e.g. [`RosenbrockVec`](crate::RosenbrockVec)) with the
+/// solvers set up for `Array1<f64>`: params are converted to `Vec<f64>` for the inner problem,
+/// its gradient and hessian back to `Array1<f64>` and `Array2<f64>`.
+#[derive(Debug, Clone)]
+pub struct ArrayAdapter<P> {
+    inner: P,
+}
+
+impl<P> ArrayAdapter<P> {
+    pub fn new(inner: P) -> Self {
+        Self { inner }
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P: CostFunction<Param = Vec<f64>, Output = f64>> CostFunction for ArrayAdapter<P> {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.cost(&param.to_vec())
+    }
+}
+
+impl<P: Gradient<Param = Vec<f64>, Gradient = Vec<f64>>> Gradient for ArrayAdapter<P> {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(Array1::from(self.inner.gradient(&param.to_vec())?))
+    }
+}
+
+impl<P: Hessian<Param = Vec<f64>, Hessian = Vec<Vec<f64>>>> Hessian for ArrayAdapter<P> {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let rows = self.inner.hessian(&param.to_vec())?;
+        let dim = rows.len();
+        Ok(Array2::from_shape_fn((dim, dim), |(i, j)| rows[i][j]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::solver::{
+        gradientdescent::SteepestDescent,
+        linesearch::MoreThuenteLineSearch,
+        quasinewton::{BFGS, LBFGS},
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::{
+        ellipsoid::{Ellipsoid, EllipsoidKind},
+        run_solver, RosenbrockND, RosenbrockVec, RunOptions, TestFnProblem,
+    };
+
+    #[test]
+    fn test_maximize() {
+        let sphere = Ellipsoid::new(EllipsoidKind::Ellipsoid, 2).with_condition(1.0);
+        let negative_sphere = Negate::new(sphere);
+        let param = array![1.0, -2.0];
+        assert_eq!(negative_sphere.cost(&param).unwrap(), -5.0);
+        assert_eq!(negative_sphere.gradient(&param).unwrap(), array![-2.0, 4.0]);
+        assert_eq!(Negate::identity(sphere).cost(&param).unwrap(), 5.0);
+
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+        let result = run_solver(
+            "",
+            "L-BFGS",
+            Negate::new(negative_sphere),
+            solver,
+            |state| state.param(param).max_iters(50),
+            &RunOptions::default(),
+        );
+        let result = unnegate(result);
+        assert_eq!(result.best_cost, 0.0);
+        assert!(result.best_param.unwrap().iter().all(|x| x.abs() < 1e-8));
+    }
+
+    #[test]
+    fn test_log_transform() {
+        let inner = RosenbrockND::default();
+        let problem = CostTransform::new(inner.clone(), Transform::Log);
+        let param = array![-1.2, 1.0];
+        let cost = inner.cost(&param).unwrap();
+        assert_eq!(problem.cost(&param).unwrap(), cost.ln_1p());
+        let gradient = problem.gradient(&param).unwrap();
+        assert_eq!(gradient, inner.gradient(&param).unwrap() / (1.0 + cost));
+
+        // Chain rule of the hessian against finite differences of the gradient.
+        let hessian = problem.hessian(&param).unwrap();
+        let h = 1e-6;
+        for i in 0..2 {
+            let mut shifted = param.clone();
+            shifted[i] += h;
+            let column = (problem.gradient(&shifted).unwrap() - &gradient) / h;
+            for j in 0..2 {
+                assert!((hessian[[j, i]] - column[j]).abs() < 1e-3, "{hessian}");
+            }
+        }
+
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+        let result = run_solver(
+            "",
+            "L-BFGS",
+            problem,
+            solver,
+            |state| state.param(param).max_iters(100),
+            &RunOptions::default(),
+        );
+        let minimizer = result.best_param.clone().unwrap();
+        assert!(
+            minimizer.iter().all(|x| (x - 1.0).abs() < 1e-4),
+            "{minimizer:?}"
+        );
+        let result = Transform::Log.restore(result);
+        assert!(result.best_cost < 1e-8);
+        assert_eq!("log".parse(), Ok(Transform::Log));
+    }
+
+    #[test]
+    fn test_rescale_restores_sphere() {
+        // Weights 1 and 100: a sphere stretched along the second axis.
+        let stretched = Ellipsoid::new(EllipsoidKind::Ellipsoid, 2).with_condition(100.0);
+        let problem = Rescale::new(stretched, array![1.0, 0.1]);
+        let init = array![3.0, 2.0];
+        let u = problem.to_scaled(&init);
+        assert_eq!(problem.cost(&u).unwrap(), stretched.cost(&init).unwrap());
+        // Isotropic in `u`: the gradient points at the minimum, as a Newton step would.
+        assert_eq!(problem.hessian(&u).unwrap(), 2.0 * Array2::eye(2));
+        let gradient = problem.gradient(&u).unwrap();
+        assert!((gradient - 2.0 * &u).iter().all(|x| x.abs() < 1e-12));
+
+        let run = |problem, init: &Array1<f64>| {
+            let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+            run_solver(
+                "",
+                "Steepest Descent",
+                problem,
+                solver,
+                |state| state.param(init.clone()).max_iters(1),
+                &RunOptions::default(),
+            )
+        };
+        // A single step of steepest descent with an exact line search reaches the minimum.
+        let result = problem.restore(run(problem.clone(), &u));
+        assert!(result.best_cost < 1e-12, "{}", result.best_cost);
+        assert!(result.best_param.unwrap().iter().all(|x| x.abs() < 1e-6));
+        let unscaled = run(Rescale::identity(stretched, 2), &init);
+        assert!(unscaled.best_cost > 1e-3, "{}", unscaled.best_cost);
+    }
+
+    #[test]
+    fn test_array_adapter_matches_vec() {
+        let inner = RosenbrockVec::default();
+        let adapted = ArrayAdapter::new(inner);
+        for param in [vec![-1.2, 1.0], vec![1.0, 1.0], vec![0.5, -2.0, 3.0]] {
+            let array = Array1::from(param.clone());
+            assert_eq!(adapted.cost(&array).unwrap(), inner.cost(&param).unwrap());
+            assert_eq!(
+                adapted.gradient(&array).unwrap().to_vec(),
+                inner.gradient(&param).unwrap()
+            );
+            let hessian = adapted.hessian(&array).unwrap();
+            assert_eq!(
+                hessian.outer_iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+                inner.hessian(&param).unwrap()
+            );
+        }
+
+        // Runs with the ndarray solvers.
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let res = run_solver(
+            "",
+            "L-BFGS",
+            adapted,
+            LBFGS::new(MoreThuenteLineSearch::new(), 7),
+            |state| state.param(array![-1.2, 1.0]).max_iters(100),
+            &options,
+        );
+        assert!(res.best_cost < 1e-10, "{}", res.best_cost);
+    }
+
+    #[test]
+    fn test_approx_gradient_sphere() {
+        let approx = ApproxGradient::new(TestFnProblem::sphere());
+        for param in [array![1.0, -2.0, 3.0], array![0.1, 0.0, -10.0]] {
+            let gradient = approx.gradient(&param).unwrap();
+            let exact = 2.0 * &param;
+            let close = gradient
+                .iter()
+                .zip(&exact)
+                .all(|(x, y)| (x - y).abs() < 1e-6 * y.abs().max(1.0));
+            assert!(close, "{gradient} vs {exact}");
+        }
+        assert_eq!(
+            MaybeApprox::Exact(TestFnProblem::sphere())
+                .gradient(&array![1.0, 2.0])
+                .unwrap(),
+            array![2.0, 4.0]
+        );
+        assert!(ApproxGradient::new(TestFnProblem::sphere())
+            .with_step(0.0)
+            .is_err());
+
+        // The inner problem doesn't need a `Gradient` implementation.
+        struct Sum;
+        impl CostFunction for Sum {
+            type Param = Array1<f64>;
+            type Output = f64;
+
+            fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(param.sum())
+            }
+        }
+        let gradient = ApproxGradient::new(Sum)
+            .gradient(&array![1.0, 2.0])
+            .unwrap();
+        assert!(
+            gradient.iter().all(|x| (x - 1.0).abs() < 1e-6),
+            "{gradient}"
+        );
+
+        // Sphere without its gradient.
+        let cost_only = TestFnProblem::new("sphere", |x| x.iter().map(|x| x * x).sum());
+        assert!(!cost_only.has_gradient());
+        let approx = ApproxGradient::new(cost_only).with_step(1e-5).unwrap();
+        assert!(approx.has_gradient());
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let res = run_solver(
+            "",
+            "BFGS",
+            approx,
+            BFGS::new(MoreThuenteLineSearch::new()),
+            |state| {
+                state
+                    .param(array![1.0, -2.0, 3.0])
+                    .inv_hessian(Array2::eye(3))
+                    .max_iters(100)
+            },
+            &options,
+        );
+        assert!(res.best_cost < 1e-12, "{}", res.best_cost);
+    }
+}
@@ -0,0 +1,289 @@
+use std::{fmt, str::FromStr};
+
+use argmin::{
+    core::Error,
+    solver::simulatedannealing::{SATempFunc, SimulatedAnnealing},
+};
+use ndarray::Array1;
+use rand::{distributions::Uniform, Rng};
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+use crate::Method;
+
+/// Cooling factor used by the exponential schedule.
+const EXP_FACTOR: f64 = 0.95;
+
+/// Temperature schedule of the simulated annealing solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Schedule {
+    Exponential,
+    #[default]
+    Boltzmann,
+    Fast,
+}
+
+impl Schedule {
+    fn temp_func(&self) -> SATempFunc<f64> {
+        match self {
+            Schedule::Exponential => SATempFunc::Exponential(EXP_FACTOR),
+            Schedule::Boltzmann => SATempFunc::Boltzmann,
+            Schedule::Fast => SATempFunc::TemperatureFast,
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exp" => Ok(Schedule::Exponential),
+            "boltzmann" => Ok(Schedule::Boltzmann),
+            "fast" => Ok(Schedule::Fast),
+            _ => Err(format!(
+                "unknown schedule `{s}`, expected exp, boltzmann or fast"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Schedule::Exponential => "exp",
+            Schedule::Boltzmann => "boltzmann",
+            Schedule::Fast => "fast",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Simulated annealing settings exposed on the command line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnnealingConfig {
+    pub init_temp: f64,
+    pub schedule: Schedule,
+    /// Reanneal (reset the temperature) every given number of iterations.
+    pub reanneal: Option<u64>,
+}
+
+impl Default for AnnealingConfig {
+    fn default() -> Self {
+        Self {
+            init_temp: 15.0,
+            schedule: Schedule::default(),
+            reanneal: None,
+        }
+    }
+}
+
+impl AnnealingConfig {
+    /// Method label including the settings, so tables stay reproducible.
+    pub fn label(&self) -> String {
+        let details = match self.reanneal {
+            Some(iters) => format!("{}, reanneal {iters}", self.schedule),
+            None => self.schedule.to_string(),
+        };
+        Method::SimulatedAnnealing.label_with(&details)
+    }
+
+    /// Builds the solver, using a seeded random number generator when `seed` is given.
+    pub fn build(
+        &self,
+        seed: Option<u64>,
+    ) -> Result<SimulatedAnnealing<f64, Xoshiro256PlusPlus>, Error> {
+        let solver = match seed {
+            Some(seed) => SimulatedAnnealing::new_with_rng(
+                self.init_temp,
+                Xoshiro256PlusPlus::seed_from_u64(seed),
+            )?,
+            None => SimulatedAnnealing::new(self.init_temp)?,
+        };
+        let solver = solver.with_temp_func(self.schedule.temp_func());
+        Ok(match self.reanneal {
+            Some(iters) => solver.with_reannealing_fixed(iters),
+            None => solver,
+        })
+    }
+}
+
+/// Largest anneal move along an axis as a fraction of the width of its bounds, `0.1` on the
+/// `[-5, 5]` bounds of the rosenbrock.
+pub const DEFAULT_ANNEAL_STEP: f64 = 0.01;
+
+/// Source of the moves of [`bounded_anneal`], implemented by every random number generator.
+/// Tests can use a fixed sequence of moves instead.
+pub trait AnnealMove {
+    /// Coordinate to perturb, below `dim`.
+    fn axis(&mut self, dim: usize) -> usize;
+
+    /// Size of the perturbation in `[-1, 1]`, as a multiple of the largest move.
+    fn offset(&mut self) -> f64;
+}
+
+impl<R: Rng> AnnealMove for R {
+    fn axis(&mut self, dim: usize) -> usize {
+        self.sample(Uniform::from(0..dim))
+    }
+
+    fn offset(&mut self) -> f64 {
+        self.sample(Uniform::new_inclusive(-1.0, 1.0))
+    }
+}
+
+/// Random move shared by the problems' `Anneal` implementations: perturbs random coordinates by
+/// up to `step` times the width of their bounds, a number of times proportional to the
+/// temperature, staying within the bounds.
+pub(crate) fn bounded_anneal(
+    param: &Array1<f64>,
+    temp: f64,
+    moves: &mut impl AnnealMove,
+    (lower_bound, upper_bound): (&Array1<f64>, &Array1<f64>),
+    step: f64,
+) -> Array1<f64> {
+    let mut param_n = param.clone();
+    // Perform modifications to a degree proportional to the current temperature `temp`.
+    for _ in 0..(temp.floor() as u64 + 1) {
+        // Pick the coordinate to modify.
+        let idx = moves.axis(param.len());
+
+        // Offset in [-step, step] times the width of the bounds.
+        let width = upper_bound[idx] - lower_bound[idx];
+        let val = moves.offset() * step * width;
+
+        // modify previous parameter value at random position `idx` by `val`
+        param_n[idx] += val;
+
+        // check if bounds are violated. If yes, project onto bound.
+        param_n[idx] = param_n[idx].clamp(lower_bound[idx], upper_bound[idx]);
+    }
+    param_n
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use argmin::core::{
+        observers::{Observe, ObserverMode},
+        Executor, IterState, KV,
+    };
+    use ndarray::{array, Array1};
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    type SAState = IterState<Array1<f64>, (), (), (), f64>;
+
+    /// Cycles through fixed `(axis, offset)` moves.
+    struct FixedMoves {
+        moves: Vec<(usize, f64)>,
+        next: usize,
+    }
+
+    impl FixedMoves {
+        fn new(moves: Vec<(usize, f64)>) -> Self {
+            Self { moves, next: 0 }
+        }
+    }
+
+    impl AnnealMove for FixedMoves {
+        fn axis(&mut self, _dim: usize) -> usize {
+            self.moves[self.next % self.moves.len()].0
+        }
+
+        fn offset(&mut self) -> f64 {
+            let offset = self.moves[self.next % self.moves.len()].1;
+            self.next += 1;
+            offset
+        }
+    }
+
+    struct CostTrajectory(Arc<Mutex<Vec<f64>>>);
+
+    impl Observe<SAState> for CostTrajectory {
+        fn observe_iter(&mut self, state: &SAState, _kv: &KV) -> Result<(), Error> {
+            self.0.lock().unwrap().push(state.get_cost());
+            Ok(())
+        }
+    }
+
+    fn trajectory(schedule: Schedule) -> Vec<f64> {
+        let costs = Arc::new(Mutex::new(Vec::new()));
+        let config = AnnealingConfig {
+            schedule,
+            ..Default::default()
+        };
+        let solver = config.build(Some(42)).unwrap();
+        Executor::new(RosenbrockND::default().with_seed(42), solver)
+            .configure(|state| state.param(array![10.2, -20.0]).max_iters(100))
+            .add_observer(CostTrajectory(costs.clone()), ObserverMode::Always)
+            .run()
+            .unwrap();
+        let costs = costs.lock().unwrap().clone();
+        costs
+    }
+
+    #[test]
+    fn test_schedules() {
+        for schedule in [Schedule::Exponential, Schedule::Boltzmann, Schedule::Fast] {
+            assert_eq!(schedule.to_string().parse::<Schedule>(), Ok(schedule));
+            // Same seed, same schedule: same trajectory.
+            assert_eq!(trajectory(schedule), trajectory(schedule));
+        }
+        assert_ne!(
+            trajectory(Schedule::Exponential),
+            trajectory(Schedule::Fast)
+        );
+        assert_ne!(trajectory(Schedule::Boltzmann), trajectory(Schedule::Fast));
+    }
+
+    #[test]
+    fn test_move_scales_with_bounds() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+        let param = array![0.0, 0.0];
+        let bounds = (&array![-5.0, -500.0], &array![5.0, 500.0]);
+        let mut moves = [Vec::new(), Vec::new()];
+        for _ in 0..2000 {
+            let moved = bounded_anneal(&param, 0.0, &mut rng, bounds, DEFAULT_ANNEAL_STEP);
+            for (i, x) in moved.iter().enumerate().filter(|(_, x)| **x != 0.0) {
+                moves[i].push(x.abs());
+            }
+        }
+        let [narrow, wide] = moves.map(|x| x.iter().sum::<f64>() / x.len() as f64);
+        // Uniform moves of up to 0.1 on the narrow axis, 10 on the wide one.
+        assert!((narrow - 0.05).abs() < 0.005, "{narrow}");
+        assert!((wide / narrow - 100.0).abs() < 10.0, "{wide} / {narrow}");
+    }
+
+    #[test]
+    fn test_clamps_at_bounds() {
+        let bounds = (&array![-5.0, -5.0], &array![5.0, 5.0]);
+        let step = 0.1;
+        // Moves of a full step, 1 along each axis.
+        let mut moves = FixedMoves::new(vec![(0, 1.0), (1, -1.0)]);
+        let moved = bounded_anneal(&array![4.5, -4.5], 0.0, &mut moves, bounds, step);
+        assert_eq!(moved, array![5.0, -4.5]);
+        let moved = bounded_anneal(&array![4.5, -4.5], 0.0, &mut moves, bounds, step);
+        assert_eq!(moved, array![4.5, -5.0]);
+        // Four moves at temperature 3, cycling the single one: 3 -> 4 -> 5, then staying on
+        // the bound.
+        let mut moves = FixedMoves::new(vec![(0, 1.0)]);
+        let moved = bounded_anneal(&array![3.0, 0.0], 3.0, &mut moves, bounds, step);
+        assert_eq!(moved, array![5.0, 0.0]);
+        // Inside the box nothing is clamped.
+        let mut moves = FixedMoves::new(vec![(1, 0.5)]);
+        let moved = bounded_anneal(&array![0.0, 0.0], 0.0, &mut moves, bounds, step);
+        assert_eq!(moved, array![0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_label() {
+        let config = AnnealingConfig {
+            schedule: Schedule::Fast,
+            reanneal: Some(50),
+            ..Default::default()
+        };
+        assert_eq!(config.label(), "Simulated Annealing (fast, reanneal 50)");
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Timings of the repeated runs of one method, for `--bench-json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchEntry {
+    pub name: String,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub samples: usize,
+}
+
+impl BenchEntry {
+    /// `None` without any sample, e.g. for a run that failed or was skipped.
+    pub fn new(name: impl ToString, samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut ns = samples
+            .iter()
+            .map(|x| x.as_nanos() as u64)
+            .collect::<Vec<_>>();
+        ns.sort_unstable();
+        let mid = ns.len() / 2;
+        let median_ns = match ns.len() % 2 {
+            0 => (ns[mid - 1] + ns[mid]) / 2,
+            _ => ns[mid],
+        };
+        Some(Self {
+            name: name.to_string(),
+            median_ns,
+            min_ns: ns[0],
+            samples: ns.len(),
+        })
+    }
+}
+
+/// Machine-readable timings of a comparison, meant to be diffed by regression tooling rather
+/// than read. Unlike [`Report`](crate::results::Report) it only keeps what identifies the
+/// benchmark, so its layout never changes with the result rows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub problem: String,
+    pub dim: usize,
+    pub benchmarks: Vec<BenchEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_json() {
+        let ns = |x: &[u64]| {
+            x.iter()
+                .map(|&x| Duration::from_nanos(x))
+                .collect::<Vec<_>>()
+        };
+        let report = BenchReport {
+            problem: "rosenbrock".to_string(),
+            dim: 2,
+            benchmarks: [
+                BenchEntry::new("BFGS", &ns(&[30, 10, 20])),
+                BenchEntry::new("Newton", &ns(&[40, 10, 20, 30])),
+                BenchEntry::new("Failed", &[]),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed = serde_json::from_str::<BenchReport>(&json).unwrap();
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.benchmarks.len(), 2);
+        let bfgs = &parsed.benchmarks[0];
+        assert_eq!((bfgs.median_ns, bfgs.min_ns, bfgs.samples), (20, 10, 3));
+        assert_eq!(parsed.benchmarks[1].median_ns, 25);
+
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(value["dim"], 2);
+        assert_eq!(value["benchmarks"][0]["name"], "BFGS");
+    }
+}
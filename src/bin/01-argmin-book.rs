@@ -1,29 +1,52 @@
+use std::path::Path;
+
 use argmin::{
     core::{
         checkpointing::{CheckpointingFrequency, FileCheckpoint},
         observers::{ObserverMode, SlogLogger},
-        Executor,
+        Executor, IterState,
     },
     solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch},
 };
-use argmin_exploring::RosenbrockND;
-use ndarray::array;
+use argmin_exploring::{
+    checkpoints,
+    cli::{exit_usage, Args},
+    RosenbrockND,
+};
+use ndarray::{array, Array1};
+
+const CHECKPOINT_DIR: &str = "checkpoints";
+
+type Solver = SteepestDescent<MoreThuenteLineSearch<Array1<f64>, Array1<f64>, f64>>;
+type Iter = IterState<Array1<f64>, Array1<f64>, (), (), f64>;
+
+/// Flags that never take a value.
+const SWITCHES: &[&str] = &["cleanup-checkpoints", "list-checkpoints"];
 
 fn main() {
-    let max_iters = std::env::args()
-        .nth(1)
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|x| panic!("Invalid number for `max_iters`: {x}"))
-        })
+    let args = Args::from_env(SWITCHES);
+    let max_iters = args
+        .positional(0, "max_iters")
+        .unwrap_or_else(exit_usage)
         .unwrap_or(10);
+    if args.flag("list-checkpoints") {
+        let files = checkpoints::list::<Solver, Iter>(Path::new(CHECKPOINT_DIR))
+            .unwrap_or_else(|e| panic!("Could not read `{CHECKPOINT_DIR}`: {e}"));
+        if files.is_empty() {
+            println!("No checkpoints in `{CHECKPOINT_DIR}`");
+        }
+        for file in files {
+            println!("{file}");
+        }
+        return;
+    }
 
     let problem = RosenbrockND::default();
     let init_param = array![10.2, -20.0];
     let linesearch = MoreThuenteLineSearch::new();
     let solver = SteepestDescent::new(linesearch);
     let checkpoint = FileCheckpoint::new(
-        "checkpoints",
+        CHECKPOINT_DIR,
         "01-argmin-book",
         CheckpointingFrequency::Every(5),
     );
@@ -31,9 +54,18 @@ fn main() {
     let res = Executor::new(problem, solver)
         .configure(|state| state.param(init_param).max_iters(max_iters))
         .add_observer(SlogLogger::term(), ObserverMode::Always)
-        .checkpointing(checkpoint)
-        .run()
-        .unwrap();
+        .checkpointing(checkpoint.clone())
+        .run();
+    // Kept by default, a failed run resumes from it.
+    if args.flag("cleanup-checkpoints") {
+        let path = checkpoints::checkpoint_path(&checkpoint);
+        if checkpoints::cleanup(&checkpoint, &res)
+            .unwrap_or_else(|e| panic!("Could not remove `{}`: {e}", path.display()))
+        {
+            println!("Removed checkpoint `{}`", path.display());
+        }
+    }
+    let res = res.unwrap();
     println!("{}", res);
     let state = res.state();
     println!("{:?}", state);
@@ -1,408 +1,2636 @@
-use argmin::{
-    core::{
-        observers::{ObserverMode, SlogLogger},
-        Executor, State, TerminationReason,
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Debug,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use argmin::core::{CostFunction, IterState, Solver};
+use argmin::solver::{
+    gradientdescent::SteepestDescent,
+    linesearch::{HagerZhangLineSearch, MoreThuenteLineSearch},
+    newton::{Newton, NewtonCG},
+    quasinewton::{BFGS, DFP, LBFGS},
+    trustregion::{CauchyPoint, Dogleg, Steihaug, TrustRegion},
+};
+#[cfg(feature = "linalg")]
+use argmin_exploring::linalg_newton::NewtonLinalg;
+use argmin_exploring::{
+    adapters::{unnegate, Rescale, Transform},
+    annealing::{AnnealingConfig, Schedule},
+    bench::{BenchEntry, BenchReport},
+    bounds::BoundsMode,
+    bounds_report::BoundsRow,
+    caching::{CostCache, EXACT_BITS},
+    cli::{exit_usage, parse_list, Args},
+    comparison::{
+        backtracking, bounds_mode_for, iteration_cap, lbfgs_label, objective, run_seeds,
+        solver_runs, CgRestart, DeSettings, MomentumSettings, Objective, RestartSettings,
+        DE_POPULATION, LBFGS_M,
     },
-    solver::{
-        conjugategradient::{beta::PolakRibiere, NonlinearConjugateGradient},
-        gradientdescent::SteepestDescent,
-        landweber::Landweber,
-        linesearch::{
-            condition::ArmijoCondition, BacktrackingLineSearch, HagerZhangLineSearch,
-            MoreThuenteLineSearch,
-        },
-        neldermead::NelderMead,
-        newton::{Newton, NewtonCG},
-        particleswarm::ParticleSwarm,
-        quasinewton::{SR1TrustRegion, BFGS, DFP, LBFGS},
-        simulatedannealing::SimulatedAnnealing,
-        trustregion::{CauchyPoint, Dogleg, Steihaug, TrustRegion},
+    convergence::ConvergenceRate,
+    init::{jitter, InitStrategy},
+    methods::parse_method_list,
+    metrics::{MetricsRow, MetricsWeights},
+    noisy::NoiseDist,
+    observers::{CsvHistoryObserver, Logger, NdjsonObserver},
+    optimum::{KnownOptimum, OptimumCheck, ParamTarget},
+    pareto::ParetoPlot,
+    problems::Derivatives,
+    profile::{Profile, ProfileRow},
+    repro::{config_hash, result_hash, DEFAULT_REPRO_DIGITS},
+    results::{
+        self, relative_to, sort_by_cost, Columns, Format, LineSearchRow, Report, RunParams,
+        RunStatus, Summary, SweepRow, ThresholdRow, DEFAULT_PRECISION,
+    },
+    run_config::{run_comparison, RunConfig},
+    run_solver,
+    runner::{
+        attempt_seed, child_seed, map_runs, run_best_of, run_repeated, run_seed,
+        run_with_fresh_retries, FailurePolicy, SolverRun, TimeBudget,
     },
+    safeguarded_newton::SafeguardedNewton,
+    snapshot::{self, SNAPSHOT_COLUMNS},
+    surface::{cost_surface, grid_cell, render_heatmap},
+    termination::Stall,
+    Method, Problem, RosenbrockND, RosenbrockVec, RunOptions, Shekel,
 };
-use argmin_exploring::{RosenbrockND, RosenbrockVec};
-use ndarray::{array, Array2};
-use std::time::Duration;
-use tabled::{Style, Table, Tabled};
+use ndarray::{Array1, Array2};
+use serde::Serialize;
+use tabled::Tabled;
+
+/// Memories of L-BFGS compared by `--compare-quasinewton` unless given with `--lbfgs-m`.
+const COMPARE_LBFGS_M: [usize; 4] = [3, 5, 10, 20];
+
+/// Dimension of the rosenbrock of `--compare-quasinewton` unless given with `--dim`.
+const COMPARE_QUASINEWTON_DIM: usize = 100;
+
+/// Initial trust region radii compared by `--compare-trustregion` unless given with
+/// `--tr-radius`, around argmin's default of 1.
+const COMPARE_TR_RADII: [f64; 3] = [0.1, 1.0, 10.0];
+
+/// Start of `--compare-newton-variants` unless given with `--init` or `--init-strategy`,
+/// repeated along every pair of axes: far up the side of the rosenbrock valley, where its
+/// hessian is indefinite.
+const NEWTON_VARIANTS_INIT: [f64; 2] = [-2.0, 6.0];
+
+/// Rate at which `result` approaches the `optimum` cost, from the costs of `problem` along its
+/// trajectory: Newton never evaluates them itself.
+fn convergence_rate(
+    problem: &Problem,
+    init: &Array1<f64>,
+    optimum: f64,
+    result: &results::Result,
+) -> Option<ConvergenceRate> {
+    let trajectory = result.trajectory.as_ref()?;
+    let costs = std::iter::once(init.clone())
+        .chain(trajectory.params.iter().map(|x| Array1::from(x.clone())))
+        .map(|x| problem.cost(&x).unwrap_or(f64::NAN))
+        .collect::<Vec<_>>();
+    ConvergenceRate::estimate(&costs, optimum)
+}
 
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 #[tabled(rename_all = "Pascal")]
-struct Result {
-    family: String,
-    method: String,
-    best_cost: f64,
-    time: String,
+struct ContainerResult {
+    container: &'static str,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+/// Runs steepest descent with the given line search on the same rosenbrock using `Array1` and
+/// `Vec` params. Line searches are generic over the param type, hence one instance per container.
+fn compare_container<L1, L2>(
+    method: Method,
+    (linesearch_ndarray, linesearch_vec): (L1, L2),
+    init: &Array1<f64>,
     iterations: u64,
-    termination_reason: String,
-}
-
-impl Result {
-    fn new(
-        family: impl ToString,
-        method: impl ToString,
-        best_cost: f64,
-        time: Option<Duration>,
-        iterations: u64,
-        termination_reason: Option<&TerminationReason>,
-    ) -> Self {
-        let time = time
-            .map(|d| format!("{d:?}"))
-            .unwrap_or_else(|| "-".to_string());
-
-        let termination_reason = match termination_reason {
-            Some(x) => format!("{x}"),
-            None => "-".to_string(),
-        };
-        Self {
-            family: family.to_string(),
-            method: method.to_string(),
-            best_cost,
-            time,
+    (warmup, repeat): (usize, usize),
+    options: &RunOptions,
+) -> [ContainerResult; 2]
+where
+    L1: Clone,
+    L2: Clone,
+    SteepestDescent<L1>: Solver<RosenbrockND, IterState<Array1<f64>, Array1<f64>, (), (), f64>>,
+    SteepestDescent<L2>: Solver<RosenbrockVec, IterState<Vec<f64>, Vec<f64>, (), (), f64>>,
+{
+    let ndarray = |options: &RunOptions| {
+        run_solver(
+            method.family(),
+            &method.to_string(),
+            RosenbrockND::with_dim(init.len()),
+            SteepestDescent::new(linesearch_ndarray.clone()),
+            |state| state.param(init.clone()).max_iters(iterations),
+            options,
+        )
+    };
+    let vec = |options: &RunOptions| {
+        run_solver(
+            method.family(),
+            &method.to_string(),
+            RosenbrockVec::default(),
+            SteepestDescent::new(linesearch_vec.clone()),
+            |state| state.param(init.to_vec()).max_iters(iterations),
+            options,
+        )
+    };
+    let warmup_options = options.for_warmup();
+    for _ in 0..warmup {
+        ndarray(&warmup_options);
+        vec(&warmup_options);
+    }
+    let ndarray = run_repeated(repeat, || ndarray(options));
+    let vec = run_repeated(repeat, || vec(options));
+    [
+        ContainerResult {
+            container: "ndarray",
+            result: ndarray,
+        },
+        ContainerResult {
+            container: "Vec",
+            result: vec,
+        },
+    ]
+}
+
+/// End-to-end `Array1` vs `Vec` comparison, restricted to the solvers accepting both param
+/// types: steepest descent with each line search.
+fn compare_containers(
+    init: &Array1<f64>,
+    iterations: u64,
+    (warmup, repeat): (usize, usize),
+    options: &RunOptions,
+) -> Vec<ContainerResult> {
+    [
+        compare_container(
+            Method::Backtracking,
+            (backtracking(), backtracking()),
+            init,
             iterations,
-            termination_reason,
+            (warmup, repeat),
+            options,
+        ),
+        compare_container(
+            Method::MoreThuente,
+            (MoreThuenteLineSearch::new(), MoreThuenteLineSearch::new()),
+            init,
+            iterations,
+            (warmup, repeat),
+            options,
+        ),
+        compare_container(
+            Method::HagerZhang,
+            (HagerZhangLineSearch::new(), HagerZhangLineSearch::new()),
+            init,
+            iterations,
+            (warmup, repeat),
+            options,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Runs steepest descent with `linesearch` on the plain problem under each of `caps`.
+fn compare_linesearch<L>(
+    method: Method,
+    linesearch: L,
+    problem: &Problem,
+    init: &Array1<f64>,
+    caps: &[u64],
+    options: &RunOptions,
+) -> Vec<LineSearchRow>
+where
+    L: Clone,
+    SteepestDescent<L>: Solver<Problem, IterState<Array1<f64>, Array1<f64>, (), (), f64>>,
+{
+    caps.iter()
+        .map(|&cap| {
+            let result = run_solver(
+                method.family(),
+                &method.to_string(),
+                problem.clone(),
+                SteepestDescent::new(linesearch.clone()),
+                |state| state.param(init.clone()).max_iters(cap),
+                options,
+            );
+            LineSearchRow::new(cap, &result)
+        })
+        .collect()
+}
+
+/// Steepest descent with each line search, stopped once the best param gets within the
+/// tolerance of `target`, so only the line search differs between the rows. Every line search
+/// runs with `iterations` times 1, 10 and 100 as its cap.
+fn compare_linesearches(
+    problem: &Problem,
+    init: &Array1<f64>,
+    iterations: u64,
+    target: ParamTarget,
+    options: &RunOptions,
+) -> Vec<LineSearchRow> {
+    let caps = [iterations, iterations * 10, iterations * 100];
+    let options = RunOptions {
+        param_target: Some(target),
+        ..options.clone()
+    };
+    [
+        compare_linesearch(
+            Method::Backtracking,
+            backtracking(),
+            problem,
+            init,
+            &caps,
+            &options,
+        ),
+        compare_linesearch(
+            Method::MoreThuente,
+            MoreThuenteLineSearch::new(),
+            problem,
+            init,
+            &caps,
+            &options,
+        ),
+        compare_linesearch(
+            Method::HagerZhang,
+            HagerZhangLineSearch::new(),
+            problem,
+            init,
+            &caps,
+            &options,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[derive(Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+struct QuasiNewtonResult {
+    /// Doubles kept to approximate the hessian: the `n x n` inverse hessian of BFGS and DFP, the
+    /// last `m` steps and gradient changes of L-BFGS.
+    memory_doubles: usize,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+/// BFGS and DFP against L-BFGS with each memory in `lbfgs_m`, on the plain problem.
+fn compare_quasinewtons(
+    problem: &Problem,
+    init: &Array1<f64>,
+    iterations: u64,
+    lbfgs_m: &[usize],
+    options: &RunOptions,
+) -> Vec<QuasiNewtonResult> {
+    let n = init.len();
+    let inv_hessian = |result| QuasiNewtonResult {
+        memory_doubles: n * n,
+        result,
+    };
+    let mut results = vec![
+        inv_hessian(run_solver(
+            Method::Bfgs.family(),
+            &Method::Bfgs.to_string(),
+            problem.clone(),
+            BFGS::new(MoreThuenteLineSearch::new()),
+            |state| {
+                state
+                    .param(init.clone())
+                    .inv_hessian(Array2::eye(n))
+                    .max_iters(iterations)
+            },
+            options,
+        )),
+        inv_hessian(run_solver(
+            Method::Dfp.family(),
+            &Method::Dfp.to_string(),
+            problem.clone(),
+            DFP::new(MoreThuenteLineSearch::new()),
+            |state| {
+                state
+                    .param(init.clone())
+                    .inv_hessian(Array2::eye(n))
+                    .max_iters(iterations)
+            },
+            options,
+        )),
+    ];
+    results.extend(lbfgs_m.iter().map(|&m| QuasiNewtonResult {
+        memory_doubles: 2 * m * n,
+        result: run_solver(
+            Method::Lbfgs.family(),
+            &lbfgs_label(m),
+            problem.clone(),
+            LBFGS::new(MoreThuenteLineSearch::new(), m),
+            |state| state.param(init.clone()).max_iters(iterations),
+            options,
+        ),
+    }));
+    results
+}
+
+#[derive(Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+struct TrustRegionResult {
+    initial_radius: f64,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+/// Runs `TrustRegion` with `subproblem` from the initial `radius`, on the plain problem.
+fn trust_region<S>(
+    method: Method,
+    subproblem: S,
+    radius: f64,
+    problem: &Problem,
+    init: &Array1<f64>,
+    iterations: u64,
+    options: &RunOptions,
+) -> Result<TrustRegionResult, String>
+where
+    TrustRegion<S, f64>: Solver<Problem, IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, f64>>,
+{
+    let solver = TrustRegion::new(subproblem)
+        .with_radius(radius)
+        .map_err(|e| format!("Invalid `--tr-radius`: {e}"))?;
+    let result = run_solver(
+        method.family(),
+        &method.to_string(),
+        problem.clone(),
+        solver,
+        |state| state.param(init.clone()).max_iters(iterations),
+        options,
+    );
+    Ok(TrustRegionResult {
+        initial_radius: radius,
+        result,
+    })
+}
+
+/// Cauchy point, dogleg and Steihaug subproblems of the trust region method, from every initial
+/// radius in `radii`.
+fn compare_trustregions(
+    problem: &Problem,
+    init: &Array1<f64>,
+    iterations: u64,
+    radii: &[f64],
+    options: &RunOptions,
+) -> Result<Vec<TrustRegionResult>, String> {
+    let mut results = Vec::new();
+    for method in [Method::CauchyPoint, Method::Dogleg, Method::Steihaug] {
+        for &radius in radii {
+            let result = match method {
+                Method::CauchyPoint => trust_region(
+                    method,
+                    CauchyPoint::new(),
+                    radius,
+                    problem,
+                    init,
+                    iterations,
+                    options,
+                ),
+                Method::Dogleg => trust_region(
+                    method,
+                    Dogleg::new(),
+                    radius,
+                    problem,
+                    init,
+                    iterations,
+                    options,
+                ),
+                _ => trust_region(
+                    method,
+                    Steihaug::new(),
+                    radius,
+                    problem,
+                    init,
+                    iterations,
+                    options,
+                ),
+            };
+            results.push(result?);
         }
     }
+    Ok(results)
 }
 
-fn main() {
-    let mut args = std::env::args().skip(1);
-    let iterations = args
-        .next()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|x| panic!("Invalid number for `max_iters`: {x}"))
+#[derive(Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+struct NewtonVariantResult {
+    /// Whether the variant is part of this build, LAPACK Newton needs the `linalg` feature.
+    #[tabled(display_with = "display_compiled")]
+    compiled: bool,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+fn display_compiled(compiled: &bool) -> String {
+    match compiled {
+        true => "yes".to_string(),
+        false => "no".to_string(),
+    }
+}
+
+/// Plain Newton, LAPACK Newton, Newton-CG and safeguarded Newton on the plain problem. LAPACK
+/// Newton is a skipped row when built without the `linalg` feature.
+fn compare_newton_variants(
+    problem: &Problem,
+    init: &Array1<f64>,
+    iterations: u64,
+    options: &RunOptions,
+) -> Vec<NewtonVariantResult> {
+    let compiled = |result| NewtonVariantResult {
+        compiled: true,
+        result,
+    };
+    let configure =
+        |state: IterState<_, _, _, _, _>| state.param(init.clone()).max_iters(iterations);
+    let lapack = Method::Newton.label_with("LAPACK");
+    #[cfg(feature = "linalg")]
+    let lapack = compiled(run_solver(
+        Method::Newton.family(),
+        &lapack,
+        problem.clone(),
+        NewtonLinalg::new(),
+        configure,
+        options,
+    ));
+    #[cfg(not(feature = "linalg"))]
+    let lapack = NewtonVariantResult {
+        compiled: false,
+        result: results::Result::skipped(
+            Method::Newton.family(),
+            &lapack,
+            "built without the `linalg` feature",
+        ),
+    };
+    vec![
+        compiled(run_solver(
+            Method::Newton.family(),
+            &Method::Newton.to_string(),
+            problem.clone(),
+            Newton::new(),
+            configure,
+            options,
+        )),
+        lapack,
+        compiled(run_solver(
+            Method::NewtonCg.family(),
+            &Method::NewtonCg.to_string(),
+            problem.clone(),
+            NewtonCG::new(MoreThuenteLineSearch::new()),
+            configure,
+            options,
+        )),
+        compiled(run_solver(
+            Method::SafeguardedNewton.family(),
+            &Method::SafeguardedNewton.to_string(),
+            problem.clone(),
+            SafeguardedNewton::new(),
+            configure,
+            options,
+        )),
+    ]
+}
+
+/// Flags of the plain comparison that none of the `--compare-*` modes applies: they run the
+/// plain problem, without any of its wrappers, and every method once from the same init.
+const COMPARE_CONFLICTS: [&str; 11] = [
+    "approx-grad",
+    "clamp-bounds",
+    "equal-iters-per-eval",
+    "init-jitter",
+    "maximize",
+    "noise-stddev",
+    "project-bounds",
+    "repeat-best-of",
+    "rescale",
+    "retry-on-failure",
+    "seed-sweep",
+];
+
+/// Modes printing a comparison of their own instead of the plain one, see
+/// [`run_compare_mode`].
+#[derive(Debug, Clone, Copy)]
+enum CompareMode {
+    Containers,
+    LineSearch,
+    QuasiNewton,
+    TrustRegion,
+    NewtonVariants,
+}
+
+impl CompareMode {
+    const ALL: [Self; 5] = [
+        Self::Containers,
+        Self::LineSearch,
+        Self::QuasiNewton,
+        Self::TrustRegion,
+        Self::NewtonVariants,
+    ];
+
+    fn flag(self) -> &'static str {
+        match self {
+            Self::Containers => "compare-containers",
+            Self::LineSearch => "compare-linesearch",
+            Self::QuasiNewton => "compare-quasinewton",
+            Self::TrustRegion => "compare-trustregion",
+            Self::NewtonVariants => "compare-newton-variants",
+        }
+    }
+
+    /// Flags the mode doesn't apply on top of [`COMPARE_CONFLICTS`]. Only the containers are
+    /// timed, with warmup runs and repeats.
+    fn conflicts(self) -> &'static [&'static str] {
+        match self {
+            Self::Containers => &[],
+            Self::LineSearch => &["repeat", "target-param", "warmup"],
+            Self::QuasiNewton | Self::TrustRegion | Self::NewtonVariants => &["repeat", "warmup"],
+        }
+    }
+}
+
+/// Exits when any of `conflicts` is given along with `--{mode}`.
+fn reject_conflicts(args: &Args, mode: &str, conflicts: &[&str]) {
+    if let Some(name) = conflicts.iter().find(|&&x| x != mode && args.flag(x)) {
+        exit_with_errors(&[format!("`--{name}` doesn't apply to `--{mode}`")]);
+    }
+}
+
+/// Settings of the command line the `--compare-*` modes use.
+struct CompareSettings<'a> {
+    args: &'a Args,
+    problem: &'a Problem,
+    init: &'a Array1<f64>,
+    init_source: &'a str,
+    iterations: u64,
+    warmup: usize,
+    repeat: usize,
+    lbfgs_m: &'a [usize],
+    tr_radii: &'a [f64],
+    seed: Option<u64>,
+    seed_drawn: bool,
+    format: Format,
+    options: &'a RunOptions,
+    stream_ndjson: bool,
+}
+
+impl CompareSettings<'_> {
+    fn dry_run(&self) -> bool {
+        self.args.flag("dry-run")
+    }
+
+    /// Prints the rows of a comparison run from `init`, under `title` as a table.
+    fn print<T: Tabled + Serialize>(&self, init: &Array1<f64>, title: &str, rows: Vec<T>) {
+        let params = RunParams {
+            init: Some(init.to_vec()),
+            seed: self.seed,
+            seed_drawn: self.seed_drawn,
+            max_iters: self.iterations,
+            noise_stddev: None,
+            eval_budget: None,
+            maximize: false,
+        };
+        let output = self
+            .format
+            .render(&Report::new(self.problem.name(), params, rows));
+        let text = match self.format {
+            Format::Table => format!("{title}:\n{output}"),
+            _ => output,
+        };
+        match self.stream_ndjson {
+            true => eprintln!("{text}"),
+            false => println!("{text}"),
+        }
+    }
+}
+
+/// Runs the `--compare-*` mode given on the command line, returning whether there was one.
+fn run_compare_mode(settings: &CompareSettings) -> bool {
+    let args = settings.args;
+    let Some(mode) = CompareMode::ALL.into_iter().find(|x| args.flag(x.flag())) else {
+        return false;
+    };
+    reject_conflicts(args, mode.flag(), &CompareMode::ALL.map(CompareMode::flag));
+    reject_conflicts(args, mode.flag(), &COMPARE_CONFLICTS);
+    reject_conflicts(args, mode.flag(), mode.conflicts());
+    match mode {
+        CompareMode::Containers => containers_mode(settings),
+        CompareMode::LineSearch => linesearch_mode(settings),
+        CompareMode::QuasiNewton => quasinewton_mode(settings),
+        CompareMode::TrustRegion => trustregion_mode(settings),
+        CompareMode::NewtonVariants => newton_variants_mode(settings),
+    }
+    true
+}
+
+/// `--compare-containers`, see [`compare_containers`].
+fn containers_mode(settings: &CompareSettings) {
+    let CompareSettings {
+        problem,
+        init,
+        iterations,
+        warmup,
+        repeat,
+        ..
+    } = *settings;
+    if !matches!(problem, Problem::Rosenbrock(_)) {
+        exit_with_errors(&[format!(
+            "`--compare-containers` only supports `rosenbrock`, got `{problem}`"
+        )]);
+    }
+    if settings.dry_run() {
+        println!(
+            "Compare containers: steepest descent on rosenbrock (dim {}) with Array1 and Vec \
+             params",
+            problem.dim()
+        );
+        println!("Init: {init}");
+        println!("Max iters: {iterations}, warmup: {warmup}, repeat: {repeat}");
+        return;
+    }
+    let results = compare_containers(init, iterations, (warmup, repeat), settings.options);
+    settings.print(
+        init,
+        &format!("Array1 vs Vec using {iterations} iterations (time averaged over {repeat} runs)"),
+        results,
+    );
+}
+
+/// `--compare-linesearch`, see [`compare_linesearches`].
+fn linesearch_mode(settings: &CompareSettings) {
+    let CompareSettings {
+        problem,
+        init,
+        iterations,
+        ..
+    } = *settings;
+    if !problem.has_gradient() {
+        exit_with_errors(&[format!(
+            "`--compare-linesearch` needs a gradient, `{problem}` has none"
+        )]);
+    }
+    let tol = settings
+        .args
+        .value("param-tol")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(1e-3);
+    let target = ParamTarget::new(problem, tol).unwrap_or_else(|| {
+        exit_with_errors(&[format!(
+            "`{problem}` has no known optimum for `--compare-linesearch`"
+        )])
+    });
+    if settings.dry_run() {
+        println!(
+            "Compare line searches: steepest descent on {problem} (dim {})",
+            problem.dim()
+        );
+        println!("Init: {init}");
+        println!(
+            "Max iters: {iterations}, {} and {}, converged within {tol} of the optimum",
+            iterations * 10,
+            iterations * 100
+        );
+        return;
+    }
+    let rows = compare_linesearches(problem, init, iterations, target, settings.options);
+    settings.print(
+        init,
+        &format!("Steepest descent iterations to get within {tol} of the optimum, by line search"),
+        rows,
+    );
+}
+
+/// `--compare-quasinewton`, see [`compare_quasinewtons`].
+fn quasinewton_mode(settings: &CompareSettings) {
+    let CompareSettings {
+        problem,
+        init,
+        iterations,
+        lbfgs_m,
+        ..
+    } = *settings;
+    if !matches!(problem, Problem::Rosenbrock(_)) {
+        exit_with_errors(&[format!(
+            "`--compare-quasinewton` only supports `rosenbrock`, got `{problem}`"
+        )]);
+    }
+    if settings.dry_run() {
+        println!(
+            "Compare quasi-Newton methods: BFGS, DFP and L-BFGS on {problem} (dim {})",
+            problem.dim()
+        );
+        println!("Init: {init}");
+        println!("Max iters: {iterations}, L-BFGS memories: {lbfgs_m:?}");
+        return;
+    }
+    let results = compare_quasinewtons(problem, init, iterations, lbfgs_m, settings.options);
+    settings.print(
+        init,
+        &format!(
+            "Quasi-Newton memory (in doubles) vs accuracy on {problem} (dim {}) using \
+             {iterations} iterations",
+            problem.dim()
+        ),
+        results,
+    );
+}
+
+/// `--compare-trustregion`, see [`compare_trustregions`].
+fn trustregion_mode(settings: &CompareSettings) {
+    let CompareSettings {
+        problem,
+        init,
+        iterations,
+        tr_radii,
+        ..
+    } = *settings;
+    if !problem.has_hessian() {
+        exit_with_errors(&[format!(
+            "`--compare-trustregion` needs a hessian, `{problem}` has none"
+        )]);
+    }
+    if settings.dry_run() {
+        println!(
+            "Compare trust region subproblems: Cauchy point, dogleg and Steihaug on {problem} \
+             (dim {})",
+            problem.dim()
+        );
+        println!("Init: {init}");
+        println!("Max iters: {iterations}, initial radii: {tr_radii:?}");
+        return;
+    }
+    let results = compare_trustregions(problem, init, iterations, tr_radii, settings.options)
+        .unwrap_or_else(|e| exit_with_errors(&[e]));
+    settings.print(
+        init,
+        &format!(
+            "Trust region subproblems by initial radius on {problem} using {iterations} \
+             iterations"
+        ),
+        results,
+    );
+}
+
+/// `--compare-newton-variants`, see [`compare_newton_variants`].
+fn newton_variants_mode(settings: &CompareSettings) {
+    let CompareSettings {
+        args,
+        problem,
+        iterations,
+        ..
+    } = *settings;
+    if !matches!(problem, Problem::Rosenbrock(_)) {
+        exit_with_errors(&[format!(
+            "`--compare-newton-variants` only supports `rosenbrock`, got `{problem}`"
+        )]);
+    }
+    let (init, init_source) = match args.flag("init") || args.flag("init-strategy") {
+        true => (settings.init.clone(), settings.init_source),
+        false => (
+            Array1::from_shape_fn(problem.dim(), |i| NEWTON_VARIANTS_INIT[i % 2]),
+            "far start",
+        ),
+    };
+    if settings.dry_run() {
+        println!(
+            "Compare Newton variants: Newton, LAPACK Newton, Newton-CG and safeguarded \
+             Newton on {problem} (dim {})",
+            problem.dim()
+        );
+        println!("Init: {init} ({init_source})");
+        println!(
+            "Max iters: {iterations}, LAPACK Newton {}",
+            match cfg!(feature = "linalg") {
+                true => "compiled in",
+                false => "skipped, built without the `linalg` feature",
+            }
+        );
+        return;
+    }
+    let results = compare_newton_variants(problem, &init, iterations, settings.options);
+    settings.print(
+        &init,
+        &format!(
+            "Newton variants on {problem} from {init} ({init_source}) using {iterations} \
+             iterations"
+        ),
+        results,
+    );
+}
+
+/// Checks that the command line settings are consistent with each other, returning every
+/// problem found.
+fn validate(
+    problem: &Problem,
+    init: &Array1<f64>,
+    runs: &[SolverRun<Objective>],
+    only: Option<&[String]>,
+    only_flag: &str,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    if init.len() != problem.dim() {
+        errors.push(format!(
+            "`--init` has {} values but `{problem}` has dimension {}",
+            init.len(),
+            problem.dim()
+        ));
+    }
+    let (lower, upper) = problem.bounds();
+    if lower.iter().zip(&upper).any(|(l, u)| l > u) {
+        errors.push(format!("Invalid bounds for `{problem}`: {lower} > {upper}"));
+    }
+    for name in only.unwrap_or_default() {
+        match name.parse::<Method>() {
+            Err(e) => errors.push(format!("Invalid `{only_flag}`: {e}")),
+            Ok(method) if !runs.iter().any(|x| x.method == method) => {
+                let keys = runs.iter().map(|x| x.key()).collect::<Vec<_>>();
+                errors.push(format!(
+                    "`{}` in `{only_flag}` is not available for `{problem}`, expected one of: {}",
+                    method.key(),
+                    keys.join(", ")
+                ));
+            }
+            Ok(_) => {}
+        }
+    }
+    errors
+}
+
+/// Description of the problem and a note on every method about to run, for `--explain`.
+fn explain(problem: &Problem, runs: &[(usize, SolverRun<Objective>)]) -> String {
+    let mut out = format!(
+        "{problem} (dim {}): {}\n",
+        problem.dim(),
+        problem.description()
+    );
+    for (_, run) in runs {
+        let family = match run.method.family() {
+            "" => String::new(),
+            family => format!(" [{family}]"),
+        };
+        out.push_str(&format!("  {}{family}: {}\n", run.label, run.method.note()));
+    }
+    out
+}
+
+/// Terminal heatmap of a 2-D problem over its bounds, sized to the terminal (`COLUMNS` and
+/// `LINES`, 80x24 when unset). Colors are only used when stdout is a terminal.
+fn heatmap(problem: &Problem) -> Result<String, String> {
+    if problem.dim() != 2 {
+        return Err(format!(
+            "`--heatmap` needs a 2-D problem, `{problem}` has dimension {}",
+            problem.dim()
+        ));
+    }
+    let size = |name: &str, default: usize| {
+        env::var(name)
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(default)
+    };
+    // Terminal cells are about twice as tall as wide.
+    let cols = size("COLUMNS", 80).saturating_sub(1).clamp(10, 120);
+    let rows = (cols / 2).min(size("LINES", 24).saturating_sub(3)).max(5);
+    let (lower, upper) = problem.bounds();
+    let surface = cost_surface(problem, &lower, &upper, (rows, cols)).map_err(|e| e.to_string())?;
+    let mark = problem
+        .optima()
+        .first()
+        .and_then(|x| grid_cell(&x.to_vec(), &lower, &upper, (rows, cols)));
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    Ok(format!(
+        "{problem} on [{}, {}] x [{}, {}], log scale{}:\n{}",
+        lower[0],
+        upper[0],
+        lower[1],
+        upper[1],
+        if mark.is_some() { ", X = optimum" } else { "" },
+        render_heatmap(&surface, mark, color)
+    ))
+}
+
+/// Contents of `run.json`: the resolved settings, along with the command line they come from.
+#[derive(Serialize)]
+struct RunJson<'a> {
+    #[serde(flatten)]
+    config: &'a RunConfig,
+    args: &'a Args,
+}
+
+/// Writes the results of `--output` into `dir`: as markdown, CSV and JSON, plus the settings in
+/// `run.json`. `history.csv` is written during the runs by the `--csv-history` observer.
+fn write_output(
+    dir: &Path,
+    report: &Report<results::Result>,
+    config: &RunConfig,
+    args: &Args,
+) -> io::Result<()> {
+    let files = [
+        ("results.md", Format::Markdown),
+        ("results.csv", Format::Csv),
+        ("results.json", Format::JsonPretty),
+    ];
+    for (name, format) in files {
+        fs::write(dir.join(name), format.render(report))?;
+    }
+    let run =
+        serde_json::to_string_pretty(&RunJson { config, args }).expect("settings are serializable");
+    fs::write(dir.join("run.json"), run)
+}
+
+/// Prints the outcome of `--check-optimum`, then exits with an error unless some method got
+/// within the tolerance of the optimum, and every `required` one did.
+fn check_optimum(
+    checks: &[(Method, OptimumCheck)],
+    required: &[Method],
+    stderr: bool,
+    digits: usize,
+) {
+    let mut lines = vec!["Optimum check:".to_string()];
+    lines.extend(
+        checks
+            .iter()
+            .map(|(_, check)| format!("  {check:.digits$}")),
+    );
+    match stderr {
+        true => eprintln!("{}", lines.join("\n")),
+        false => println!("{}", lines.join("\n")),
+    }
+    let mut errors = Vec::new();
+    if !checks.iter().any(|(_, check)| check.passed) {
+        errors.push("`--check-optimum`: no method reached the optimum".to_string());
+    }
+    errors.extend(
+        checks
+            .iter()
+            .filter(|(method, check)| required.contains(method) && !check.passed)
+            .map(|(method, _)| {
+                format!(
+                    "`--check-optimum`: required method `{}` didn't reach the optimum",
+                    method.key()
+                )
+            }),
+    );
+    if !errors.is_empty() {
+        exit_with_errors(&errors);
+    }
+}
+
+/// Writes the trajectory of the method with the best cost, the largest one with `maximize`.
+fn save_best_trajectory(results: &[results::Result], maximize: bool, path: &Path) {
+    let best = best_result(results, maximize, "save-best-trajectory");
+    let trajectory = best
+        .trajectory
+        .as_ref()
+        .expect("trajectories are recorded with `--save-best-trajectory`");
+    trajectory.save(path).unwrap_or_else(|e| {
+        exit_with_errors(&[format!("Could not write `{}`: {e}", path.display())])
+    });
+}
+
+/// The row with the best cost that didn't fail, exits when they all did.
+fn best_result<'a>(
+    results: &'a [results::Result],
+    maximize: bool,
+    flag: &str,
+) -> &'a results::Result {
+    let sign = if maximize { -1.0 } else { 1.0 };
+    results
+        .iter()
+        .filter(|x| !x.is_failure())
+        .min_by(|x, y| (sign * x.best_cost).total_cmp(&(sign * y.best_cost)))
+        .unwrap_or_else(|| exit_with_errors(&[format!("`--{flag}`: every method failed")]))
+}
+
+/// Exits with an error unless every `required` method converged, every method without any, so
+/// scripts can rely on the exit code.
+fn check_status(statuses: &[(Method, RunStatus)], required: &[Method]) {
+    let errors = statuses
+        .iter()
+        .filter(|(method, status)| {
+            (required.is_empty() || required.contains(method)) && *status != RunStatus::Converged
         })
-        .unwrap_or(100);
-    let log_every = args
-        .next()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|x| panic!("Invalid number for `log_every`: {x}"))
+        .map(|(method, status)| {
+            format!(
+                "`--exit-status`: `{}` didn't converge ({status})",
+                method.key()
+            )
         })
-        .unwrap_or(10);
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        exit_with_errors(&errors);
+    }
+}
 
-    let init_param = array![10.2, -20.0];
-    let problem = RosenbrockND::default();
-    let problem_vec = RosenbrockVec::default();
-    let mut results = Vec::new();
+/// Settings of the command line deciding what is printed and written after the runs.
+struct OutputSettings<'a> {
+    args: &'a Args,
+    problem: &'a Problem,
+    init: &'a Array1<f64>,
+    /// What every method got, e.g. `100 iterations`.
+    budget: String,
+    precision: usize,
+    stream_ndjson: bool,
+    sort: bool,
+    relative: Option<Method>,
+    columns: Option<&'a Columns>,
+    tol_report: bool,
+    metrics: Option<&'a MetricsWeights>,
+    pareto: Option<&'a Option<PathBuf>>,
+    /// Hash of the config with the digits the results are rounded to for `--repro-hash`.
+    repro: Option<(String, usize)>,
+    bounds_report: bool,
+    profile_rows: Vec<ProfileRow>,
+    save_trajectory: Option<&'a Path>,
+    output: Option<&'a Path>,
+    checks: Option<Vec<(Method, OptimumCheck)>>,
+    statuses: Option<Vec<(Method, RunStatus)>>,
+    required: &'a [Method],
+}
 
-    // Linear search - Backtracking
-    let backtracking = BacktrackingLineSearch::new(ArmijoCondition::new(0.0001).unwrap());
-    let backtracking_solver = SteepestDescent::new(backtracking);
-    let backtracking_res = Executor::new(problem.clone(), backtracking_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("Backtracking: {backtracking_res}");
-
-    results.push(Result::new(
-        "Linear search",
-        "Backtracking",
-        backtracking_res.state.get_best_cost(),
-        backtracking_res.state.get_time(),
-        backtracking_res.state.get_iter(),
-        backtracking_res.state.get_termination_reason(),
-    ));
+impl OutputSettings<'_> {
+    /// Prints to stdout, or to stderr while it streams NDJSON.
+    fn print(&self, text: String) {
+        match self.stream_ndjson {
+            true => eprintln!("{text}"),
+            false => println!("{text}"),
+        }
+    }
 
-    // Linear search - More-Thuente
-    let morethuente = MoreThuenteLineSearch::new();
-    let morethuente_solver = SteepestDescent::new(morethuente);
-    let morethuente_res = Executor::new(problem.clone(), morethuente_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("More-Thuente: {morethuente_res}");
-    results.push(Result::new(
-        "Linear search",
-        "More-Thuente",
-        morethuente_res.state.get_best_cost(),
-        morethuente_res.state.get_time(),
-        morethuente_res.state.get_iter(),
-        morethuente_res.state.get_termination_reason(),
-    ));
+    /// Prints to stdout only for the table, so the other formats stay parseable.
+    fn print_extra(&self, format: Format, text: &str) {
+        match format {
+            Format::Table if !self.stream_ndjson => println!("{text}"),
+            _ => eprintln!("{text}"),
+        }
+    }
 
-    // Linear search - Hager-Zhang
-    let hagerzhang = HagerZhangLineSearch::new();
-    let hagerzhang_solver = SteepestDescent::new(hagerzhang);
-    let hagerzhang_res = Executor::new(problem.clone(), hagerzhang_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("Hager-Zhang: {hagerzhang_res}");
-    results.push(Result::new(
-        "Linear search",
-        "Hager-Zhang",
-        hagerzhang_res.state.get_best_cost(),
-        hagerzhang_res.state.get_time(),
-        hagerzhang_res.state.get_iter(),
-        hagerzhang_res.state.get_termination_reason(),
-    ));
+    /// Writes the files asked for, then prints the rows of `summary` as the table of
+    /// `--tol-report`, `--metrics` or the results, followed by the reports and checks.
+    fn render(&self, mut summary: Summary) {
+        let format = summary.config.format;
+        let params = &summary.config.params;
+        let maximize = params.maximize;
+        if self.sort {
+            sort_by_cost(&mut summary.rows, maximize);
+        }
+        if let Some(path) = self.save_trajectory {
+            save_best_trajectory(&summary.rows, maximize, path);
+        }
+        let report = summary.report();
+        if let Some(path) = self.args.value::<String>("dump").unwrap_or_else(exit_usage) {
+            std::fs::write(&path, Format::Json.render(&report))
+                .unwrap_or_else(|e| exit_with_errors(&[format!("Could not write `{path}`: {e}")]));
+        }
+        if let Some(dir) = self.output {
+            write_output(dir, &report, &summary.config, self.args).unwrap_or_else(|e| {
+                exit_with_errors(&[format!("Could not write to `{}`: {e}", dir.display())])
+            });
+        }
+        let pareto_plot = match self.pareto {
+            Some(Some(path)) => {
+                let plot =
+                    ParetoPlot::new(&report.results, maximize).with_precision(self.precision);
+                let title = format!("{}: best cost against time", self.problem);
+                fs::write(path, plot.render_svg(&title)).unwrap_or_else(|e| {
+                    exit_with_errors(&[format!("Could not write `{}`: {e}", path.display())])
+                });
+                None
+            }
+            Some(None) => Some(
+                ParetoPlot::new(&report.results, maximize)
+                    .with_precision(self.precision)
+                    .render_ascii(60, 16),
+            ),
+            None => None,
+        };
 
-    // Trust Region - Cauchy Point
-    let cauchy_point = CauchyPoint::new();
-    let cauchy_point_solver = TrustRegion::new(cauchy_point);
-    let cauchy_point_res = Executor::new(problem.clone(), cauchy_point_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("Cauchy-Point: {cauchy_point_res}");
-    results.push(Result::new(
-        "Trust region",
-        "Cauchy-Point",
-        cauchy_point_res.state.get_best_cost(),
-        cauchy_point_res.state.get_time(),
-        cauchy_point_res.state.get_iter(),
-        cauchy_point_res.state.get_termination_reason(),
-    ));
+        let (budget, init, init_source) = (&self.budget, self.init, &summary.config.init_source);
+        if self.tol_report {
+            let rows = report.results.iter().map(ThresholdRow::new).collect();
+            let output = format.render(&Report::new(&report.problem, params.clone(), rows));
+            match format {
+                Format::Table => self.print(format!(
+                    "Iterations to reach each best cost threshold (max {}):\n{output}",
+                    params.max_iters
+                )),
+                _ => self.print(output),
+            }
+        } else if let Some(weights) = self.metrics {
+            let rows = MetricsRow::leaderboard(&report.results, weights, maximize);
+            let output = format.render(&Report::new(&report.problem, params.clone(), rows));
+            match format {
+                Format::Table => self.print(format!(
+                    "Leaderboard using {budget} from {init} ({init_source}), {}:\n{output}",
+                    weights.formula()
+                )),
+                _ => self.print(output),
+            }
+        } else {
+            let output = match self.relative {
+                Some(baseline) => {
+                    let rows = relative_to(report.results.clone(), baseline)
+                        .unwrap_or_else(|e| exit_with_errors(&[format!("`--relative-to`: {e}")]));
+                    format.render(&Report::new(&report.problem, params.clone(), rows))
+                }
+                None => {
+                    let columns = self.columns.cloned().unwrap_or_default();
+                    format.render_columns(&report, &columns, self.precision)
+                }
+            };
+            match format {
+                Format::Table => {
+                    let results = match maximize {
+                        true => "Largest values found",
+                        false => "Results",
+                    };
+                    self.print(format!(
+                        "{results} using {budget} from {init} ({init_source}):\n{output}"
+                    ));
+                    if let (true, Some(seed)) = (params.seed_drawn, params.seed) {
+                        self.print(format!(
+                            "Seed {seed} was drawn at random, replay with `--seed {seed}`"
+                        ));
+                    }
+                }
+                _ => self.print(output),
+            }
+        }
 
-    // Trust Region - Dogleg
-    let dogleg = Dogleg::new();
-    let dogleg_solver = TrustRegion::new(dogleg);
-    let dogleg_res = Executor::new(problem.clone(), dogleg_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("Dogleg: {dogleg_res}");
-    results.push(Result::new(
-        "Trust region",
-        "Dogleg",
-        dogleg_res.state.get_best_cost(),
-        dogleg_res.state.get_time(),
-        dogleg_res.state.get_iter(),
-        dogleg_res.state.get_termination_reason(),
-    ));
+        if let Some(plot) = &pareto_plot {
+            self.print_extra(format, plot);
+        }
+        if let Some((config_hash, digits)) = &self.repro {
+            let repro = format!(
+                "config_hash: {config_hash}\nresult_hash: {}",
+                result_hash(&report.results, *digits)
+            );
+            self.print_extra(format, &repro);
+        }
+        if !self.profile_rows.is_empty() {
+            let rows = self.profile_rows.clone();
+            let output = format.render(&Report::new(&report.problem, params.clone(), rows));
+            match format {
+                Format::Table => self.print(format!(
+                    "Time per run spent evaluating the problem:\n{output}"
+                )),
+                _ => self.print(output),
+            }
+        }
+        if self.bounds_report {
+            let (lower, upper) = self.problem.bounds();
+            let rows = report
+                .results
+                .iter()
+                .map(|x| BoundsRow::new(x, &lower, &upper))
+                .collect();
+            let output = format.render(&Report::new(&report.problem, params.clone(), rows));
+            match format {
+                Format::Table => self.print(format!(
+                    "Iterates outside the bounds [{lower}, {upper}]:\n{output}"
+                )),
+                _ => self.print(output),
+            }
+        }
+        if let Some(checks) = &self.checks {
+            check_optimum(
+                checks,
+                self.required,
+                format != Format::Table || self.stream_ndjson,
+                self.precision,
+            );
+        }
+        if let Some(statuses) = &self.statuses {
+            check_status(statuses, self.required);
+        }
+    }
+}
 
-    // Trust Region - Steighaug
-    let steighaug = Steihaug::new();
-    let steighaug_solver = TrustRegion::new(steighaug);
-    let steighaug_res = Executor::new(problem.clone(), steighaug_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("steighaug: {steighaug_res}");
-    results.push(Result::new(
-        "Trust region",
-        "Steighaug",
-        steighaug_res.state.get_best_cost(),
-        steighaug_res.state.get_time(),
-        steighaug_res.state.get_iter(),
-        steighaug_res.state.get_termination_reason(),
-    ));
+/// Seed and iterations of the `--snapshot` run.
+const SNAPSHOT_SEED: u64 = 0;
+const SNAPSHOT_ITERS: u64 = 100;
 
-    // Conjugate Gradient - Non-linear Conjugate Gradient
-    let linesearch = MoreThuenteLineSearch::new();
-    let beta_method = PolakRibiere::new();
-    let nlcg_solver = NonlinearConjugateGradient::new(linesearch, beta_method)
-        .restart_iters(10)
-        .restart_orthogonality(0.1);
-    let nlcg_res = Executor::new(problem.clone(), nlcg_solver)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("non-linear conjugate gradient: {nlcg_res}");
-    results.push(Result::new(
-        "Conjugate Gradient",
-        "Non-linear CG",
-        nlcg_res.state.get_best_cost(),
-        nlcg_res.state.get_time(),
-        nlcg_res.state.get_iter(),
-        nlcg_res.state.get_termination_reason(),
-    ));
+/// Hidden `--snapshot` mode guarding the output end to end: runs every deterministic method on
+/// the 2-D rosenbrock from its default init for [`SNAPSHOT_ITERS`] iterations, ignoring every
+/// other option, and compares the table of [`SNAPSHOT_COLUMNS`] to the golden file at `path`.
+/// Exits with the diff on mismatch, `update` rewrites the file instead.
+///
+/// `snapshots/02-rosenbrock.txt` is the golden file of a build without features, the `linalg`
+/// one labels its Newton row differently.
+fn run_snapshot(path: &Path, update: bool) {
+    let problem = Problem::Rosenbrock(RosenbrockND::default());
+    let rescale = Rescale::new(problem.clone(), Array1::ones(problem.dim()));
+    let (lower, upper) = problem.bounds();
+    let init = InitStrategy::default().init_param(&lower, &upper, Some(SNAPSHOT_SEED));
+    let runs = solver_runs(
+        &rescale,
+        &init,
+        |_| SNAPSHOT_ITERS,
+        &AnnealingConfig::default(),
+        &CgRestart::default(),
+        LBFGS_M,
+        None,
+        &DeSettings::default(),
+        &MomentumSettings::default(),
+        &RestartSettings::default(),
+        false,
+        Some(SNAPSHOT_SEED),
+    )
+    .unwrap_or_else(|e| exit_with_errors(&[e]));
+    let options = RunOptions {
+        quiet: true,
+        ..Default::default()
+    };
+    let results = runs
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| !x.method.is_stochastic())
+        .map(|(index, x)| {
+            let objective = objective(
+                &rescale,
+                false,
+                Transform::None,
+                None,
+                None,
+                NoiseDist::default(),
+                BoundsMode::Unbounded,
+                Some(SNAPSHOT_SEED),
+                index,
+                None,
+                None,
+            );
+            x.run(objective, init.clone(), &options)
+        })
+        .collect();
+    let params = RunParams {
+        init: Some(init.to_vec()),
+        seed: Some(SNAPSHOT_SEED),
+        seed_drawn: false,
+        max_iters: SNAPSHOT_ITERS,
+        noise_stddev: None,
+        eval_budget: None,
+        maximize: false,
+    };
+    let report = Report::new(problem.name(), params, results);
+    let columns = SNAPSHOT_COLUMNS.parse().expect("snapshot columns exist");
+    let table = Format::Table.render_columns(&report, &columns, DEFAULT_PRECISION) + "\n";
+    if update {
+        fs::write(path, table).unwrap_or_else(|e| {
+            exit_with_errors(&[format!("Could not write `{}`: {e}", path.display())])
+        });
+        println!("Snapshot written to `{}`", path.display());
+        return;
+    }
+    let expected = fs::read_to_string(path).unwrap_or_else(|e| {
+        exit_with_errors(&[format!(
+            "Could not read `{}`: {e}, create it with `--snapshot-update`",
+            path.display()
+        )])
+    });
+    match snapshot::diff(&expected, &table) {
+        Some(diff) => exit_with_errors(&[format!(
+            "Snapshot `{}` differs (- expected, + actual):\n{diff}",
+            path.display()
+        )]),
+        None => println!("Snapshot `{}` matches", path.display()),
+    }
+}
 
-    // Newton - Newton's method
-    let newton = Newton::new();
-    let newton_res = Executor::new(problem.clone(), newton)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("newton: {newton_res}");
-    results.push(Result::new(
-        "Newton methods",
-        "Newton",
-        newton_res.state.get_best_cost(),
-        newton_res.state.get_time(),
-        newton_res.state.get_iter(),
-        newton_res.state.get_termination_reason(),
-    ));
+fn exit_with_errors(errors: &[String]) -> ! {
+    for e in errors {
+        eprintln!("Error: {e}");
+    }
+    process::exit(1);
+}
 
-    // Newton - Newton-CG method
-    let linesearch = MoreThuenteLineSearch::new();
-    let newton_cg = NewtonCG::new(linesearch);
-    let newton_cg_res = Executor::new(problem.clone(), newton_cg)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("newton_cg: {newton_cg_res}");
-    results.push(Result::new(
-        "Newton methods",
-        "Newton-CG",
-        newton_cg_res.state.get_best_cost(),
-        newton_cg_res.state.get_time(),
-        newton_cg_res.state.get_iter(),
-        newton_cg_res.state.get_termination_reason(),
-    ));
+/// Flags that never take a value.
+const SWITCHES: &[&str] = &[
+    "cache",
+    "check-optimum",
+    "clamp-bounds",
+    "compare-containers",
+    "compare-linesearch",
+    "compare-newton-variants",
+    "compare-quasinewton",
+    "compare-trustregion",
+    "convergence-rate",
+    "dry-run",
+    "equal-iters-per-eval",
+    "exit-status",
+    "explain",
+    "fail-fast",
+    "heatmap",
+    "json-pretty",
+    "keep-going",
+    "maximize",
+    "metrics",
+    "parallel",
+    "param-bounds-report",
+    "profile",
+    "project-bounds",
+    "repro-hash",
+    "retry-linesearch",
+    "snapshot-update",
+    "sort",
+    "stream-ndjson",
+    "target-param",
+    "time-per-iter",
+    "tol-report",
+];
 
-    // Quasi Newton - BFGS
-    let linesearch = MoreThuenteLineSearch::new();
-    let bfgs = BFGS::new(linesearch);
-    let bfgs_res = Executor::new(problem.clone(), bfgs)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| {
-            state
-                .param(init_param.clone())
-                // Hessian type required to initialize
-                .inv_hessian(Array2::eye(2))
-                .max_iters(iterations)
+fn main() {
+    let args = Args::from_env(SWITCHES);
+    let iterations = args
+        .positional(0, "max_iters")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(100);
+    let log_every = args
+        .positional(1, "log_every")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(10);
+    let format = if args.flag("json-pretty") {
+        Format::JsonPretty
+    } else {
+        args.value::<Format>("format")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or_default()
+    };
+    let precision = match args
+        .value::<usize>("precision-digits")
+        .unwrap_or_else(exit_usage)
+    {
+        Some(0) => exit_with_errors(&["`--precision-digits` must be at least 1".to_string()]),
+        Some(digits) => digits,
+        None => DEFAULT_PRECISION,
+    };
+    match args.value::<PathBuf>("snapshot").unwrap_or_else(exit_usage) {
+        Some(path) => {
+            run_snapshot(&path, args.flag("snapshot-update"));
+            return;
+        }
+        None if args.flag("snapshot-update") => {
+            exit_with_errors(&["`--snapshot-update` only applies with `--snapshot`".to_string()])
+        }
+        None => {}
+    }
+    let sort = args.flag("sort");
+    let relative = args
+        .value::<Method>("relative-to")
+        .unwrap_or_else(exit_usage);
+    let columns = args.value::<Columns>("columns").unwrap_or_else(exit_usage);
+    let (seed, seed_drawn) = run_seed(args.value::<u64>("seed").unwrap_or_else(exit_usage));
+    let seed = Some(seed);
+    let annealing = AnnealingConfig {
+        schedule: args
+            .value::<Schedule>("sa-schedule")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or_default(),
+        reanneal: args.value("sa-reanneal").unwrap_or_else(exit_usage),
+        ..Default::default()
+    };
+    let cg_restart = CgRestart {
+        iters: args
+            .value("cg-restart-iters")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(CgRestart::default().iters),
+        orthogonality: args
+            .value("cg-restart-ortho")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(CgRestart::default().orthogonality),
+    };
+    let restart = RestartSettings {
+        restarts: args
+            .value("restarts")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(0),
+        radius: args
+            .value("restart-radius")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(RestartSettings::default().radius),
+        target: args
+            .value("restart-target")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(RestartSettings::default().target),
+    };
+    if restart.restarts == 0 {
+        if let Some(name) = ["restart-radius", "restart-target"]
+            .iter()
+            .find(|&&x| args.flag(x))
+        {
+            exit_with_errors(&[format!("`--{name}` only applies with `--restarts`")]);
+        }
+    }
+    let retry_linesearch = args.flag("retry-linesearch");
+    let compare_quasinewton = args.flag("compare-quasinewton");
+    // One memory per row of `--compare-quasinewton`, a single one otherwise.
+    let lbfgs_m = args
+        .value::<String>("lbfgs-m")
+        .unwrap_or_else(exit_usage)
+        .map(|x| {
+            parse_list::<usize>(&x)
+                .unwrap_or_else(|e| exit_with_errors(&[format!("Invalid `--lbfgs-m`: {e}")]))
         })
-        .run()
-        .unwrap();
-    println!("bfgs: {bfgs_res}");
-    results.push(Result::new(
-        "Quasi-Newton methods",
-        "BFGS",
-        bfgs_res.state.get_best_cost(),
-        bfgs_res.state.get_time(),
-        bfgs_res.state.get_iter(),
-        bfgs_res.state.get_termination_reason(),
-    ));
+        .unwrap_or_else(|| match compare_quasinewton {
+            true => COMPARE_LBFGS_M.to_vec(),
+            false => vec![LBFGS_M],
+        });
+    if lbfgs_m.contains(&0) {
+        exit_with_errors(&["`--lbfgs-m` must be at least 1".to_string()]);
+    }
+    if !compare_quasinewton && lbfgs_m.len() > 1 {
+        exit_with_errors(&[
+            "`--lbfgs-m` takes a single value outside `--compare-quasinewton`".to_string(),
+        ]);
+    }
+    let compare_trustregion = args.flag("compare-trustregion");
+    let tr_radii = match args.value::<String>("tr-radius").unwrap_or_else(exit_usage) {
+        Some(_) if !compare_trustregion => exit_with_errors(&[
+            "`--tr-radius` only applies with `--compare-trustregion`".to_string(),
+        ]),
+        Some(x) => parse_list::<f64>(&x)
+            .unwrap_or_else(|e| exit_with_errors(&[format!("Invalid `--tr-radius`: {e}")])),
+        None => COMPARE_TR_RADII.to_vec(),
+    };
+    if let Some(radius) = tr_radii.iter().find(|&&x| !(x > 0.0 && x.is_finite())) {
+        exit_with_errors(&[format!("`--tr-radius` must be positive, got {radius}")]);
+    }
+    let de = DeSettings {
+        population: args
+            .value("de-pop")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(DE_POPULATION),
+        mutation: args
+            .value("de-f")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(DeSettings::default().mutation),
+        crossover: args
+            .value("de-cr")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(DeSettings::default().crossover),
+    };
+    // Mutants need three members besides the one they challenge.
+    if de.population < 4 {
+        exit_with_errors(&[format!(
+            "`--de-pop` must be at least 4, got {}",
+            de.population
+        )]);
+    }
+    let momentum = MomentumSettings {
+        learning_rate: args
+            .value("lr")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(MomentumSettings::default().learning_rate),
+        beta: args
+            .value("momentum")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(MomentumSettings::default().beta),
+    };
+    let eval_budget = match (
+        args.flag("equal-iters-per-eval"),
+        args.value::<u64>("eval-budget").unwrap_or_else(exit_usage),
+    ) {
+        (true, Some(budget)) => Some(budget),
+        (true, None) => {
+            exit_with_errors(&["`--equal-iters-per-eval` needs an `--eval-budget`".to_string()])
+        }
+        (false, Some(_)) => exit_with_errors(&[
+            "`--eval-budget` only applies with `--equal-iters-per-eval`".to_string(),
+        ]),
+        (false, None) => None,
+    };
+    // Without a budget every method gets the same number of iterations, however many
+    // evaluations each of them takes.
+    let max_iters = |method: Method| iteration_cap(method, iterations, eval_budget, de.population);
+    let lm_lambda = args.value::<f64>("lm-lambda").unwrap_or_else(exit_usage);
+    let default_solvers = annealing == AnnealingConfig::default()
+        && cg_restart == CgRestart::default()
+        && lbfgs_m[0] == LBFGS_M
+        && lm_lambda.is_none()
+        && de == DeSettings::default()
+        && momentum == MomentumSettings::default();
+    let only = args
+        .value::<String>("only")
+        .unwrap_or_else(exit_usage)
+        .map(|x| {
+            x.split(',')
+                .map(|x| x.trim().to_string())
+                .collect::<Vec<_>>()
+        });
+    let methods_file = args
+        .value::<PathBuf>("methods-from-file")
+        .unwrap_or_else(exit_usage);
+    let only_flag = match methods_file {
+        Some(_) => "--methods-from-file",
+        None => "--only",
+    };
+    let only = match (only, &methods_file) {
+        (Some(_), Some(_)) => exit_with_errors(&[
+            "`--only` and `--methods-from-file` can't be used together".to_string(),
+        ]),
+        (None, Some(path)) => {
+            let text = fs::read_to_string(path).unwrap_or_else(|e| {
+                exit_with_errors(&[format!("Could not read `{}`: {e}", path.display())])
+            });
+            let methods = parse_method_list(&text).unwrap_or_else(|e| {
+                exit_with_errors(&[format!(
+                    "Invalid `--methods-from-file` `{}`: {e}",
+                    path.display()
+                )])
+            });
+            if methods.is_empty() {
+                exit_with_errors(&[format!("`{}` lists no method", path.display())]);
+            }
+            Some(methods.iter().map(Method::key).collect())
+        }
+        (only, None) => only,
+    };
 
-    // Quasi Newton - DFP
-    let linesearch = MoreThuenteLineSearch::new();
-    let dfp = DFP::new(linesearch);
-    let dfp_res = Executor::new(problem.clone(), dfp)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| {
-            state
-                .param(init_param.clone())
-                // Hessian type required to initialize
-                .inv_hessian(Array2::eye(2))
-                .max_iters(iterations)
+    let problem = args
+        .value::<Problem>("problem")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or_else(|| "rosenbrock".parse().unwrap());
+    let dim = args
+        .value::<usize>("dim")
+        .unwrap_or_else(exit_usage)
+        .or_else(|| {
+            (compare_quasinewton && matches!(problem, Problem::Rosenbrock(_)))
+                .then_some(COMPARE_QUASINEWTON_DIM)
+        });
+    let problem = match dim {
+        Some(dim) => problem
+            .with_dim(dim)
+            .unwrap_or_else(|e| exit_with_errors(&[e])),
+        None => problem,
+    };
+    let problem = match (
+        problem,
+        args.value::<f64>("michalewicz-m")
+            .unwrap_or_else(exit_usage),
+    ) {
+        (Problem::Michalewicz(f), Some(m)) => Problem::Michalewicz(f.with_m(m)),
+        (problem, Some(_)) => exit_with_errors(&[format!(
+            "`--michalewicz-m` only applies to `--problem michalewicz`, got `{problem}`"
+        )]),
+        (problem, None) => problem,
+    };
+    let problem = match (
+        problem,
+        args.value::<f64>("perm-beta").unwrap_or_else(exit_usage),
+    ) {
+        (Problem::Perm(f), Some(beta)) => Problem::Perm(f.with_beta(beta)),
+        (problem, Some(_)) => exit_with_errors(&[format!(
+            "`--perm-beta` only applies to `--problem perm`, got `{problem}`"
+        )]),
+        (problem, None) => problem,
+    };
+    let problem = match (
+        problem,
+        args.value::<usize>("shekel-m").unwrap_or_else(exit_usage),
+    ) {
+        (Problem::Shekel(_), Some(m)) => match Shekel::new(m) {
+            Some(f) => Problem::Shekel(f),
+            None => exit_with_errors(&[format!("`--shekel-m` must be 5, 7 or 10, got {m}")]),
+        },
+        (problem, Some(_)) => exit_with_errors(&[format!(
+            "`--shekel-m` only applies to `--problem shekel`, got `{problem}`"
+        )]),
+        (problem, None) => problem,
+    };
+    let problem = match (
+        problem,
+        args.value::<f64>("ellipsoid-condition")
+            .unwrap_or_else(exit_usage),
+    ) {
+        (Problem::Ellipsoid(f), Some(condition)) if condition >= 1.0 => {
+            Problem::Ellipsoid(f.with_condition(condition))
+        }
+        (Problem::Ellipsoid(_), Some(condition)) => exit_with_errors(&[format!(
+            "`--ellipsoid-condition` must be at least 1, got {condition}"
+        )]),
+        (problem, Some(_)) => exit_with_errors(&[format!(
+            "`--ellipsoid-condition` only applies to `--problem ellipsoid`, `cigar` or `discus`, \
+             got `{problem}`"
+        )]),
+        (problem, None) => problem,
+    };
+    let problem = match args.value::<f64>("sa-step").unwrap_or_else(exit_usage) {
+        Some(step) if step > 0.0 && step <= 1.0 => problem.with_anneal_step(step),
+        Some(step) => exit_with_errors(&[format!("`--sa-step` must be in (0, 1], got {step}")]),
+        None => problem,
+    };
+    let scale = match args.value::<String>("rescale").unwrap_or_else(exit_usage) {
+        Some(x) => {
+            let scale = parse_list::<f64>(&x)
+                .unwrap_or_else(|e| exit_with_errors(&[format!("Invalid `--rescale`: {e}")]));
+            if scale.len() != problem.dim() {
+                exit_with_errors(&[format!(
+                    "`--rescale` has {} values but `{problem}` has dimension {}",
+                    scale.len(),
+                    problem.dim()
+                )]);
+            }
+            if let Some(d) = scale.iter().find(|&&x| !(x > 0.0 && x.is_finite())) {
+                exit_with_errors(&[format!("`--rescale` must be positive, got {d}")]);
+            }
+            Array1::from_vec(scale)
+        }
+        None => Array1::ones(problem.dim()),
+    };
+    let rescale = Rescale::new(problem.clone(), scale);
+    let maximize = args.flag("maximize");
+    let transform = args
+        .value::<Transform>("cost-transform")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or_default();
+    if maximize && transform != Transform::None {
+        exit_with_errors(&[
+            "`--cost-transform` needs a non-negative cost, it can't be used with `--maximize`"
+                .to_string(),
+        ]);
+    }
+    // Step of the central differences standing in for the gradient.
+    let approx_grad = args.value::<f64>("approx-grad").unwrap_or_else(exit_usage);
+    if let Some(step) = approx_grad.filter(|x| !(*x > 0.0 && x.is_finite())) {
+        exit_with_errors(&[format!("`--approx-grad` must be positive, got {step}")]);
+    }
+    if maximize && lm_lambda.is_some() {
+        exit_with_errors(&["`--lm-lambda` doesn't apply with `--maximize`".to_string()]);
+    }
+    if lm_lambda.is_some() && problem.residuals().is_none() {
+        exit_with_errors(&[format!(
+            "`--lm-lambda` only applies to problems with residuals, got `{problem}`"
+        )]);
+    }
+    let (init_param, init_source) = match (
+        args.value::<String>("init").unwrap_or_else(exit_usage),
+        args.value::<InitStrategy>("init-strategy")
+            .unwrap_or_else(exit_usage),
+    ) {
+        (Some(_), Some(_)) => {
+            exit_with_errors(&["`--init` and `--init-strategy` can't be used together".to_string()])
+        }
+        (Some(x), None) => {
+            let init = parse_list(&x)
+                .unwrap_or_else(|e| exit_with_errors(&[format!("Invalid `--init` ({x}): {e}")]));
+            (Array1::from_vec(init), "custom".to_string())
+        }
+        (None, strategy) => {
+            let strategy = strategy.unwrap_or_default();
+            let (lower, upper) = problem.bounds();
+            let init = strategy.init_param(&lower, &upper, seed);
+            (init, strategy.to_string())
+        }
+    };
+    let param_target = args.flag("target-param").then(|| {
+        let tol = args
+            .value("param-tol")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(1e-6);
+        ParamTarget::new(&problem, tol).unwrap_or_else(|| {
+            exit_with_errors(&[format!(
+                "`{problem}` has no known optimum for `--target-param`"
+            )])
         })
-        .run()
-        .unwrap();
-    println!("dfp: {dfp_res}");
-    results.push(Result::new(
-        "Quasi-Newton methods",
-        "DFP",
-        dfp_res.state.get_best_cost(),
-        dfp_res.state.get_time(),
-        dfp_res.state.get_iter(),
-        dfp_res.state.get_termination_reason(),
-    ));
+    });
+    // The target is checked during the run, against the rescaled params.
+    if param_target.is_some() && args.flag("rescale") {
+        exit_with_errors(&["`--target-param` can't be used with `--rescale`".to_string()]);
+    }
+    // Where the solvers start, in the variables they work in.
+    let init_scaled = rescale.to_scaled(&init_param);
+    let stall_tol = args.value::<f64>("stall-tol").unwrap_or_else(exit_usage);
+    let stall = match (
+        args.value::<usize>("stall-iters")
+            .unwrap_or_else(exit_usage),
+        stall_tol,
+    ) {
+        (Some(0), _) => exit_with_errors(&["`--stall-iters` must be at least 1".to_string()]),
+        (Some(iters), tol) => Some(Stall {
+            iters,
+            tol: tol.unwrap_or(0.0),
+        }),
+        (None, Some(_)) => {
+            exit_with_errors(&["`--stall-tol` only applies with `--stall-iters`".to_string()])
+        }
+        (None, None) => None,
+    };
+    let check_target = args.flag("check-optimum").then(|| {
+        let tol = args.value("tol").unwrap_or_else(exit_usage).unwrap_or(1e-4);
+        ParamTarget::new(&problem, tol).unwrap_or_else(|| {
+            exit_with_errors(&[format!(
+                "`{problem}` has no known optimum for `--check-optimum`"
+            )])
+        })
+    });
+    let exit_status = args.flag("exit-status");
+    let save_trajectory = args
+        .value::<PathBuf>("save-best-trajectory")
+        .unwrap_or_else(exit_usage);
+    let bounds_report = args.flag("param-bounds-report");
+    // Cost at the optimum the convergence rate is measured against.
+    let rate_optimum = args.flag("convergence-rate").then(|| {
+        if maximize {
+            exit_with_errors(&["`--convergence-rate` can't be used with `--maximize`".to_string()]);
+        }
+        match problem.optima().first() {
+            Some(x) => problem.cost(x).unwrap_or(f64::NAN),
+            None => exit_with_errors(&[format!(
+                "`{problem}` has no known optimum for `--convergence-rate`"
+            )]),
+        }
+    });
+    // An SVG file, or a text plot on stdout without a path.
+    let pareto = args
+        .optional_value::<PathBuf>("pareto")
+        .unwrap_or_else(exit_usage);
+    let repro_hash = args.flag("repro-hash");
+    let repro_digits = match args
+        .value::<usize>("repro-digits")
+        .unwrap_or_else(exit_usage)
+    {
+        Some(_) if !repro_hash => {
+            exit_with_errors(&["`--repro-digits` only applies with `--repro-hash`".to_string()])
+        }
+        Some(0) => exit_with_errors(&["`--repro-digits` must be at least 1".to_string()]),
+        Some(digits) => digits,
+        None => DEFAULT_REPRO_DIGITS,
+    };
+    // Every run starts from an empty copy.
+    let cache = match (
+        args.flag("cache"),
+        args.value::<u32>("cache-bits").unwrap_or_else(exit_usage),
+    ) {
+        (true, bits) => Some(
+            CostCache::new()
+                .with_bits(bits.unwrap_or(EXACT_BITS))
+                .unwrap_or_else(|e| exit_with_errors(&[format!("Invalid `--cache-bits`: {e}")])),
+        ),
+        (false, Some(_)) => {
+            exit_with_errors(&["`--cache-bits` only applies with `--cache`".to_string()])
+        }
+        (false, None) => None,
+    };
+    // Standard deviation of the gaussian moving the start of every method.
+    let init_jitter = args.value::<f64>("init-jitter").unwrap_or_else(exit_usage);
+    if let Some(sigma) = init_jitter.filter(|x| !(*x >= 0.0 && x.is_finite())) {
+        exit_with_errors(&[format!("`--init-jitter` must be non-negative, got {sigma}")]);
+    }
+    let best_of = args
+        .value::<usize>("repeat-best-of")
+        .unwrap_or_else(exit_usage);
+    if best_of == Some(0) {
+        exit_with_errors(&["`--repeat-best-of` must be at least 1".to_string()]);
+    }
+    // Modes without the results of a plain comparison.
+    let plain_conflicts = [
+        "seed-sweep",
+        "heatmap",
+        "compare-containers",
+        "compare-linesearch",
+        "compare-quasinewton",
+        "compare-trustregion",
+        "compare-newton-variants",
+    ];
+    let plain_conflict = plain_conflicts.iter().find(|&&x| args.flag(x));
+    for (flag, used) in [
+        ("exit-status", exit_status),
+        ("save-best-trajectory", save_trajectory.is_some()),
+        ("param-bounds-report", bounds_report),
+        ("convergence-rate", rate_optimum.is_some()),
+        ("repeat-best-of", best_of.is_some()),
+        ("pareto", pareto.is_some()),
+        ("repro-hash", repro_hash),
+    ] {
+        if let (true, Some(name)) = (used, plain_conflict) {
+            exit_with_errors(&[format!("`--{flag}` can't be used with `--{name}`")]);
+        }
+    }
+    if check_target.is_none()
+        && args
+            .value::<f64>("tol")
+            .unwrap_or_else(exit_usage)
+            .is_some()
+    {
+        exit_with_errors(&["`--tol` only applies with `--check-optimum`".to_string()]);
+    }
+    if check_target.is_none()
+        && !exit_status
+        && args
+            .value::<String>("require")
+            .unwrap_or_else(exit_usage)
+            .is_some()
+    {
+        exit_with_errors(&[
+            "`--require` only applies with `--check-optimum` or `--exit-status`".to_string(),
+        ]);
+    }
+    let required = args
+        .value::<String>("require")
+        .unwrap_or_else(exit_usage)
+        .map(|x| {
+            x.split(',')
+                .map(|x| {
+                    x.trim().parse::<Method>().unwrap_or_else(|e| {
+                        exit_with_errors(&[format!("Invalid `--require`: {e}")])
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // Zero adds no noise, like leaving the flag out.
+    let noise_stddev = match args.value::<f64>("noise-stddev").unwrap_or_else(exit_usage) {
+        Some(x) if !(x >= 0.0 && x.is_finite()) => exit_with_errors(&[format!(
+            "`--noise-stddev` must be finite and non-negative, got {x}"
+        )]),
+        x => x.filter(|&x| x > 0.0),
+    };
+    let noise_dist = args
+        .value::<NoiseDist>("noise-dist")
+        .unwrap_or_else(exit_usage);
+    if noise_dist.is_some() && noise_stddev.is_none() {
+        exit_with_errors(&["`--noise-dist` needs a positive `--noise-stddev`".to_string()]);
+    }
+    let noise_dist = noise_dist.unwrap_or_default();
+    let tol_report = args.flag("tol-report");
+    let metrics = args.flag("metrics");
+    let metrics_weights = args
+        .value::<MetricsWeights>("metrics-weights")
+        .unwrap_or_else(exit_usage);
+    if metrics_weights.is_some() && !metrics {
+        exit_with_errors(&["`--metrics-weights` only applies with `--metrics`".to_string()]);
+    }
+    let metrics_weights = metrics_weights.unwrap_or_default();
+    if metrics && (tol_report || relative.is_some()) {
+        exit_with_errors(&[
+            "`--metrics` can't be used with `--tol-report` or `--relative-to`".to_string(),
+        ]);
+    }
+    if columns.is_some() && (tol_report || relative.is_some() || metrics) {
+        exit_with_errors(&[
+            "`--columns` only applies to the plain results table, not with `--tol-report`, \
+             `--relative-to` or `--metrics`"
+                .to_string(),
+        ]);
+    }
+    // Both assume the minimum of the original problem.
+    if maximize && (tol_report || param_target.is_some() || check_target.is_some()) {
+        exit_with_errors(&[
+            "`--maximize` can't be used with `--tol-report`, `--target-param` or \
+             `--check-optimum`"
+                .to_string(),
+        ]);
+    }
+    let output = args.value::<PathBuf>("output").unwrap_or_else(exit_usage);
+    if let Some(dir) = &output {
+        fs::create_dir_all(dir).unwrap_or_else(|e| {
+            exit_with_errors(&[format!("Could not create `{}`: {e}", dir.display())])
+        });
+    }
+    let csv_history = match (
+        args.value::<PathBuf>("csv-history")
+            .unwrap_or_else(exit_usage),
+        &output,
+    ) {
+        (Some(_), Some(_)) => exit_with_errors(&[
+            "`--output` already writes `history.csv`, drop `--csv-history`".to_string(),
+        ]),
+        (Some(path), None) => Some(path),
+        (None, dir) => dir.as_ref().map(|x| x.join("history.csv")),
+    };
+    let csv_history = csv_history.map(|path| {
+        CsvHistoryObserver::create(&path).unwrap_or_else(|e| {
+            exit_with_errors(&[format!("Could not create `{}`: {e}", path.display())])
+        })
+    });
+    let total_budget = args
+        .value::<f64>("total-budget-secs")
+        .unwrap_or_else(exit_usage)
+        .map(Duration::from_secs_f64);
+    // Streams to stdout, everything else goes to stderr.
+    let stream_ndjson = args.flag("stream-ndjson");
+    let logger = args
+        .value::<Logger>("observer")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or_default();
+    #[cfg(feature = "log")]
+    if logger == Logger::Log {
+        // Silent unless enabled, e.g. with `RUST_LOG=info`.
+        env_logger::init();
+    }
+    let mut options = RunOptions {
+        log_every,
+        param_target,
+        stall,
+        cost_history: tol_report,
+        trajectory: save_trajectory.is_some() || bounds_report || rate_optimum.is_some(),
+        csv_history: csv_history.clone(),
+        ndjson: stream_ndjson.then(NdjsonObserver::stdout),
+        time_budget: None,
+        time_per_iter: args.flag("time-per-iter"),
+        quiet: stream_ndjson,
+        logger,
+    };
+    let print = |text: String| match stream_ndjson {
+        true => eprintln!("{text}"),
+        false => println!("{text}"),
+    };
 
-    // Quasi Newton - L-BFGS
-    let linesearch = MoreThuenteLineSearch::new();
-    let lbfgs = LBFGS::new(linesearch, 5);
-    let lbfgs_res = Executor::new(problem.clone(), lbfgs)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("lbfgs: {lbfgs_res}");
-    results.push(Result::new(
-        "Quasi-Newton methods",
-        "L-BFGS",
-        lbfgs_res.state.get_best_cost(),
-        lbfgs_res.state.get_time(),
-        lbfgs_res.state.get_iter(),
-        lbfgs_res.state.get_termination_reason(),
-    ));
+    let repeat = args
+        .value::<usize>("repeat")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(1)
+        .max(1);
+    let seed_sweep = args.value::<u64>("seed-sweep").unwrap_or_else(exit_usage);
+    let sweep_conflicts = [
+        "seed",
+        "retry-on-failure",
+        "tol-report",
+        "relative-to",
+        "columns",
+        "check-optimum",
+        "output",
+        "dump",
+        "profile",
+    ];
+    let sweep_conflict = sweep_conflicts.iter().find(|&&x| args.flag(x));
+    if let (Some(_), Some(name)) = (seed_sweep, sweep_conflict) {
+        exit_with_errors(&[format!("`--seed-sweep` can't be used with `--{name}`")]);
+    }
+    // Every run of a stochastic method gets its own seed, derived from the seed of the run, and
+    // so its own solvers.
+    let best_of_seeds = best_of.zip(seed).map(|(runs, seed)| {
+        (0..runs as u64)
+            .map(|i| child_seed(seed, i))
+            .collect::<Vec<_>>()
+    });
+    let warmup = args
+        .value::<usize>("warmup")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(0);
+    // Retries draw new random numbers for the problem and rebuild the solver, see
+    // `run_with_fresh_retries`.
+    let retries = args
+        .value::<usize>("retry-on-failure")
+        .unwrap_or_else(exit_usage);
+    let retry_tol = args.value::<f64>("retry-tol").unwrap_or_else(exit_usage);
+    if retry_tol.is_some() && retries.is_none() {
+        exit_with_errors(&["`--retry-tol` only applies with `--retry-on-failure`".to_string()]);
+    }
+    let bench_json = args
+        .value::<PathBuf>("bench-json")
+        .unwrap_or_else(exit_usage);
+    // Each sample must be one timed run of the same solver.
+    let bench_conflicts = [
+        "seed-sweep",
+        "retry-on-failure",
+        "repeat-best-of",
+        "compare-containers",
+        "compare-linesearch",
+        "compare-quasinewton",
+        "compare-trustregion",
+        "compare-newton-variants",
+    ];
+    let bench_conflict = bench_conflicts.iter().find(|&&x| args.flag(x));
+    if let (Some(_), Some(name)) = (&bench_json, bench_conflict) {
+        exit_with_errors(&[format!("`--bench-json` can't be used with `--{name}`")]);
+    }
+    let retry_tol = retry_tol.unwrap_or(1e-6);
+    let parallel = args.flag("parallel");
+    let bounds_mode = match (args.flag("project-bounds"), args.flag("clamp-bounds")) {
+        (true, true) => exit_with_errors(&[
+            "`--project-bounds` and `--clamp-bounds` can't be used together".to_string(),
+        ]),
+        (true, false) => BoundsMode::Project,
+        (false, true) => BoundsMode::Clamp,
+        (false, false) => BoundsMode::Unbounded,
+    };
+    let policy = match (args.flag("fail-fast"), args.flag("keep-going")) {
+        (true, true) => exit_with_errors(&[
+            "`--fail-fast` and `--keep-going` can't be used together".to_string(),
+        ]),
+        (true, false) => FailurePolicy::FailFast,
+        _ => FailurePolicy::KeepGoing,
+    };
 
-    // Quasi Newton - SR1-Trust Region
-    let subproblem = Steihaug::new();
-    let sr1tr = SR1TrustRegion::new(subproblem);
-    let sr1tr_res = Executor::new(problem.clone(), sr1tr)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("sr1tr: {sr1tr_res}");
-    results.push(Result::new(
-        "Quasi-Newton methods",
-        "SR1-TrustRegion",
-        sr1tr_res.state.get_best_cost(),
-        sr1tr_res.state.get_time(),
-        sr1tr_res.state.get_iter(),
-        sr1tr_res.state.get_termination_reason(),
-    ));
+    if args.flag("heatmap") {
+        let heatmap = heatmap(&problem).unwrap_or_else(|e| exit_with_errors(&[e]));
+        print!("{heatmap}");
+        return;
+    }
 
-    // Landweber Iteration
-    let landweber = Landweber::new(0.001);
-    let landweber_res = Executor::new(problem.clone(), landweber)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("landweber: {landweber_res}");
-    results.push(Result::new(
-        "",
-        "Landweber Iteration",
-        landweber_res.state.get_best_cost(),
-        landweber_res.state.get_time(),
-        landweber_res.state.get_iter(),
-        landweber_res.state.get_termination_reason(),
-    ));
+    // Every method of the comparison with its solvers seeded from `seed`, only failing on
+    // invalid settings: every later build succeeds once this first one did.
+    let build_runs = |seed| {
+        solver_runs(
+            &rescale,
+            &init_scaled,
+            max_iters,
+            &annealing,
+            &cg_restart,
+            lbfgs_m[0],
+            lm_lambda,
+            &de,
+            &momentum,
+            &restart,
+            retry_linesearch,
+            seed,
+        )
+        .unwrap_or_else(|e| exit_with_errors(&[e]))
+    };
+    let runs = build_runs(seed);
+    let errors = validate(&problem, &init_param, &runs, only.as_deref(), only_flag);
+    if !errors.is_empty() {
+        exit_with_errors(&errors);
+    }
+    // Every name was validated above.
+    let only = only.map(|x| {
+        x.iter()
+            .map(|x| x.parse::<Method>().unwrap())
+            .collect::<Vec<_>>()
+    });
 
-    // Nelder-Mead
-    let nelder_mead = NelderMead::new(vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]]);
-    let nelder_mead_res = Executor::new(problem.clone(), nelder_mead)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("nelder_mead: {nelder_mead_res}");
-    results.push(Result::new(
-        "",
-        "Nelder-Mead",
-        nelder_mead_res.state.get_best_cost(),
-        nelder_mead_res.state.get_time(),
-        nelder_mead_res.state.get_iter(),
-        nelder_mead_res.state.get_termination_reason(),
-    ));
+    let compare = CompareSettings {
+        args: &args,
+        problem: &problem,
+        init: &init_param,
+        init_source: &init_source,
+        iterations,
+        warmup,
+        repeat,
+        lbfgs_m: &lbfgs_m,
+        tr_radii: &tr_radii,
+        seed,
+        seed_drawn,
+        format,
+        options: &options,
+        stream_ndjson,
+    };
+    if run_compare_mode(&compare) {
+        return;
+    }
+    // Keep the position in the full list, the seeds of a method depend on it.
+    let mut runs = runs
+        .into_iter()
+        .enumerate()
+        .filter(|(_, x)| only.as_ref().is_none_or(|only| only.contains(&x.method)))
+        // Least squares only minimizes.
+        .filter(|(_, x)| !(maximize && x.method.needs_residuals()))
+        .collect::<Vec<_>>();
+    // Run in the order of `--only` or the file, so the methods listed first fit in
+    // `--total-budget-secs`.
+    if let Some(only) = &only {
+        runs.sort_by_key(|(_, x)| only.iter().position(|method| *method == x.method));
+    }
+    if let Some(method) = only
+        .iter()
+        .flatten()
+        .find(|x| maximize && x.needs_residuals())
+    {
+        exit_with_errors(&[format!(
+            "`{}` in `{only_flag}` is a least-squares method, it can't `--maximize`",
+            method.key()
+        )]);
+    }
+    if let Some(baseline) = relative {
+        if !runs.iter().any(|(_, x)| x.method == baseline) {
+            exit_with_errors(&[format!(
+                "The baseline `{}` of `--relative-to` is not among the methods to run",
+                baseline.key()
+            )]);
+        }
+    }
 
-    // Simulated Annealing
-    let simulated_annealing = SimulatedAnnealing::new(15.0).unwrap();
-    let simulated_annealing_res = Executor::new(problem.clone(), simulated_annealing)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.param(init_param.clone()).max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("simulated_annealing: {simulated_annealing_res}");
-    results.push(Result::new(
-        "",
-        "Simulated Annealing",
-        simulated_annealing_res.state.get_best_cost(),
-        simulated_annealing_res.state.get_time(),
-        simulated_annealing_res.state.get_iter(),
-        simulated_annealing_res.state.get_termination_reason(),
-    ));
+    if let Some(method) = required
+        .iter()
+        .find(|&&method| !runs.iter().any(|(_, x)| x.method == method))
+    {
+        exit_with_errors(&[format!(
+            "`{}` of `--require` is not among the methods to run",
+            method.key()
+        )]);
+    }
 
-    // Particle swarm optimization
-    let particle_swarm = ParticleSwarm::new((vec![-5.0, -5.0], vec![5.0, 5.0]), 500);
-    let particle_swarm_res = Executor::new(problem_vec.clone(), particle_swarm)
-        .add_observer(SlogLogger::term(), ObserverMode::Every(log_every))
-        .configure(|state| state.max_iters(iterations))
-        .run()
-        .unwrap();
-    println!("particle_swarm: {particle_swarm_res}");
-    results.push(Result::new(
-        "",
-        "Particle Swarm",
-        particle_swarm_res.state.get_best_cost(),
-        particle_swarm_res.state.get_time(),
-        particle_swarm_res.state.get_iter(),
-        particle_swarm_res.state.get_termination_reason(),
-    ));
+    if args.flag("explain") {
+        // Keep stdout parseable for the other formats.
+        match format {
+            Format::Table if !stream_ndjson => println!("{}", explain(&problem, &runs)),
+            _ => eprintln!("{}", explain(&problem, &runs)),
+        }
+    }
+
+    let params = RunParams {
+        init: Some(init_param.to_vec()),
+        seed,
+        seed_drawn,
+        max_iters: iterations,
+        noise_stddev,
+        eval_budget,
+        maximize,
+    };
+    let mut tolerances = BTreeMap::new();
+    if let Some(target) = &options.param_target {
+        tolerances.insert("param-tol".to_string(), target.tol());
+    }
+    if let Some(stall) = &options.stall {
+        tolerances.insert("stall-tol".to_string(), stall.tol);
+    }
+    if let Some(target) = &check_target {
+        tolerances.insert("tol".to_string(), target.tol());
+    }
+    if retries.is_some() {
+        tolerances.insert("retry-tol".to_string(), retry_tol);
+    }
+    let (a, b) = match &problem {
+        Problem::Rosenbrock(f) => (Some(f.a()), Some(f.b())),
+        _ => (None, None),
+    };
+    let config_hash = repro_hash.then(|| {
+        let methods = runs
+            .iter()
+            .map(|(_, run)| run.label.as_str())
+            .collect::<Vec<_>>();
+        config_hash(&problem, &params, &methods)
+    });
+    // Settings beyond the fields of the config, listed by `--dry-run`.
+    let mut notes = Vec::new();
+    let (lower, upper) = problem.bounds();
+    let outside = init_param
+        .iter()
+        .zip(lower.iter().zip(&upper))
+        .any(|(x, (l, u))| x < l || x > u);
+    if outside {
+        notes.push(format!(
+            "Note: init is outside the problem bounds {lower} - {upper}"
+        ));
+    }
+    if noise_stddev.is_some() {
+        notes.push(format!("Noise: {noise_dist}"));
+    }
+    if maximize {
+        notes.push("Maximizing: the methods minimize the negated problem".to_string());
+    }
+    if transform != Transform::None {
+        notes.push(format!(
+            "Cost transform: {transform}, best costs reported untransformed"
+        ));
+    }
+    if args.flag("rescale") {
+        notes.push(format!(
+            "Rescale: solvers work in u = x / {}, results reported in x",
+            rescale.scale()
+        ));
+    }
+    if let Some(stall) = &stall {
+        notes.push(format!(
+            "Stall: stop once the best cost improved by at most {} over {} iterations",
+            stall.tol, stall.iters
+        ));
+    }
+    if exit_status {
+        match required.is_empty() {
+            true => notes.push("Exit status: failure unless every method converged".to_string()),
+            false => notes
+                .push("Exit status: failure unless every `--require` method converged".to_string()),
+        }
+    }
+    if metrics {
+        notes.push(format!("Metrics: {}", metrics_weights.formula()));
+    }
+    if let Some(target) = &check_target {
+        notes.push(format!(
+            "Optimum check: within {} of the optimum",
+            target.tol()
+        ));
+    }
+    match bounds_mode {
+        BoundsMode::Project => {
+            notes.push(format!(
+                "Iterates projected onto the bounds {lower} - {upper}"
+            ));
+        }
+        BoundsMode::Clamp => {
+            notes.push(format!(
+                "Iterates clamped into the bounds {lower} - {upper}, Nelder-Mead and \
+                 simulated annealing penalized outside of them"
+            ));
+        }
+        BoundsMode::Unbounded => (),
+    }
+    if parallel {
+        notes.push("Parallel: one thread per method".to_string());
+    }
+    if stream_ndjson {
+        notes.push("Streaming every iteration to stdout as NDJSON, results on stderr".to_string());
+    }
+    #[cfg(feature = "log")]
+    if logger == Logger::Log {
+        notes.push(
+            "Observer: iterations logged through the `log` crate, see `RUST_LOG`".to_string(),
+        );
+    }
+    if let Some(sweep) = seed_sweep {
+        notes.push(format!(
+            "Seed sweep: stochastic methods run with seeds 0..{sweep}"
+        ));
+    }
+    match &pareto {
+        Some(Some(path)) => notes.push(format!(
+            "Pareto: best cost against time plotted to `{}`",
+            path.display()
+        )),
+        Some(None) => {
+            notes.push("Pareto: best cost against time plotted below the results".to_string())
+        }
+        None => {}
+    }
+    if let Some(hash) = &config_hash {
+        notes.push(format!(
+            "Repro hash: config_hash {hash}, results rounded to {repro_digits} digits"
+        ));
+    }
+    if let Some(best_of) = best_of {
+        notes.push(format!(
+            "Best of: stochastic methods keep the lowest cost of {best_of} seeded runs"
+        ));
+    }
+    if warmup > 0 || repeat > 1 {
+        notes.push(format!(
+            "Timing: {warmup} discarded warmup runs, then {repeat} timed runs per method"
+        ));
+    }
+    if let Some(path) = &save_trajectory {
+        notes.push(format!(
+            "Best trajectory: iterates of the best method written to `{}`",
+            path.display()
+        ));
+    }
+    if bounds_report {
+        notes.push(format!(
+            "Bounds report: iterates checked against the bounds of `{problem}`"
+        ));
+    }
+    if let Some(optimum) = rate_optimum {
+        notes.push(format!(
+            "Convergence rate: errors of the iterates to the optimal cost {optimum}"
+        ));
+    }
+    if let Some(path) = &bench_json {
+        notes.push(format!(
+            "Bench JSON: timings written to `{}`",
+            path.display()
+        ));
+    }
+    if let Some(retries) = retries {
+        notes.push(format!(
+            "Retries: up to {retries} for stochastic methods above a best cost of {retry_tol}"
+        ));
+    }
+    if let Some(cache) = &cache {
+        notes.push(format!(
+            "Cache: costs memoized per run, params keyed on {} mantissa bits",
+            cache.bits()
+        ));
+    }
+    if let Some(step) = approx_grad {
+        notes.push(format!(
+            "Approx gradient: central differences with a step of {step}"
+        ));
+    }
+    if let Some(sigma) = init_jitter {
+        notes.push(format!(
+            "Init jitter: every method starts from the init plus noise of stddev {sigma}"
+        ));
+    }
+    if retry_linesearch {
+        notes.push(
+            "Retry line search: rows failing in their line search rerun with backtracking"
+                .to_string(),
+        );
+    }
+    if restart.restarts > 0 {
+        notes.push(format!(
+            "Restarts: up to {} for local solvers above a best cost of {}, within {}",
+            restart.restarts, restart.target, restart.radius
+        ));
+    }
+    if let Some(budget) = total_budget {
+        notes.push(format!(
+            "Total budget: {budget:?}, methods started after it are skipped"
+        ));
+    }
+    if let Some(path) = &methods_file {
+        notes.push(format!(
+            "Methods listed in `{}`, in that order",
+            path.display()
+        ));
+    }
+    let config = RunConfig {
+        problem: problem.to_string(),
+        a,
+        b,
+        dim: problem.dim(),
+        init_source: init_source.clone(),
+        params: params.clone(),
+        format,
+        methods: runs.iter().map(|(_, run)| run.label.clone()).collect(),
+        tolerances,
+        iteration_caps: match eval_budget {
+            Some(_) => runs.iter().map(|(_, run)| max_iters(run.method)).collect(),
+            None => Vec::new(),
+        },
+        notes,
+    };
+
+    if args.flag("dry-run") {
+        print!("{}", config.dry_run());
+        return;
+    }
+    // Keep stdout parseable for the other formats.
+    match format {
+        Format::Table if !stream_ndjson => println!("{config}\n"),
+        _ => eprintln!("{config}\n"),
+    }
+
+    options.time_budget = total_budget.map(TimeBudget::new);
+    // Keyed by the index of the method, every repeat and retry adds to the same profile.
+    let profiles = match args.flag("profile") {
+        true => runs
+            .iter()
+            .map(|(index, _)| (*index, Arc::default()))
+            .collect::<BTreeMap<_, Arc<Mutex<Profile>>>>(),
+        false => BTreeMap::new(),
+    };
+    // Time of every repeat, for `--bench-json`.
+    let samples = match bench_json {
+        Some(_) => runs
+            .iter()
+            .map(|(index, _)| (*index, Arc::default()))
+            .collect::<BTreeMap<_, Arc<Mutex<Vec<Duration>>>>>(),
+        None => BTreeMap::new(),
+    };
+    let warmup_options = options.for_warmup();
+    let run_seeded = |seed: Option<u64>, index: usize, x: &SolverRun<Objective>| {
+        // Shared by every repeat and retry. Nelder-Mead keeps the simplex built around the init.
+        let start = init_jitter.map(|sigma| match x.method {
+            Method::NelderMead => init_param.clone(),
+            _ => jitter(&init_param, sigma, run_seeds(seed, index).map(|[.., x]| x)),
+        });
+        let start_scaled = match &start {
+            Some(start) => rescale.to_scaled(start),
+            None => init_scaled.clone(),
+        };
+        let objective = |attempt, profile, cache| {
+            objective(
+                &rescale,
+                maximize,
+                transform,
+                approx_grad,
+                noise_stddev,
+                noise_dist,
+                bounds_mode_for(x.method, bounds_mode),
+                attempt_seed(seed, attempt),
+                index,
+                profile,
+                cache,
+            )
+        };
+        // Discarded, and left out of the profile.
+        for _ in 0..warmup {
+            x.run(
+                objective(0, None, None),
+                start_scaled.clone(),
+                &warmup_options,
+            );
+        }
+        let attempt = |x: &SolverRun<Objective>, attempt| {
+            run_repeated(repeat, || {
+                let cache = cache.clone().map(|x| Arc::new(Mutex::new(x)));
+                let objective = objective(attempt, profiles.get(&index).cloned(), cache.clone());
+                let result = x
+                    .run(objective, start_scaled.clone(), &options)
+                    .with_cache_hit_rate(cache.and_then(|x| x.lock().unwrap().hit_rate()));
+                if let (Some(profile), Some(duration)) = (profiles.get(&index), result.duration) {
+                    profile.lock().unwrap().add_run(duration);
+                }
+                if let (Some(samples), Some(duration)) = (samples.get(&index), result.duration) {
+                    samples.lock().unwrap().push(duration);
+                }
+                result
+            })
+        };
+        match retries {
+            Some(retries) if x.method.is_stochastic() => run_with_fresh_retries(
+                retries,
+                |x| x.best_cost <= retry_tol,
+                x,
+                seed,
+                |seed| build_runs(seed).swap_remove(index),
+                attempt,
+            ),
+            _ => attempt(x, 0),
+        }
+        .with_start(start.map(|x| x.to_vec()))
+    };
+    let best_of_runs = best_of_seeds
+        .iter()
+        .flatten()
+        .map(|&seed| build_runs(Some(seed)))
+        .collect::<Vec<_>>();
+    let run = |(index, x): &(usize, SolverRun<Objective>)| match &best_of_seeds {
+        Some(seeds) if x.method.is_stochastic() => run_best_of(seeds, |i| {
+            run_seeded(Some(seeds[i]), *index, &best_of_runs[i][*index])
+        }),
+        _ => run_seeded(seed, *index, x),
+    };
+    let flush_csv = || {
+        if let Some(csv) = &csv_history {
+            csv.flush().unwrap_or_else(|e| {
+                exit_with_errors(&[format!("Could not write `--csv-history`: {e}")])
+            });
+        }
+    };
+    // Least squares minimizes the residuals, its costs were never transformed.
+    let restore = |result, method: Method| {
+        let result = rescale.restore(result);
+        match method.needs_residuals() {
+            true => result,
+            false => transform.restore(result),
+        }
+    };
+    let budget = match eval_budget {
+        Some(budget) => format!("{budget} evaluations per method"),
+        None => format!("{iterations} iterations"),
+    };
 
-    // Results table
-    let table = Table::new(results).with(Style::modern()).to_string();
-    println!("Results using {iterations} iterations:\n{table}");
+    if let Some(sweep) = seed_sweep {
+        // Seeded like a run with `--seed`, every seed gets its own solvers.
+        let sweep_runs = (0..sweep)
+            .map(|seed| build_runs(Some(seed)))
+            .collect::<Vec<_>>();
+        let sweep_method = |(index, x): &(usize, SolverRun<Objective>)| {
+            // Without noise or jitter the other methods give the same result for every seed.
+            let results =
+                match x.method.is_stochastic() || noise_stddev.is_some() || init_jitter.is_some() {
+                    true => sweep_runs
+                        .iter()
+                        .zip(0..)
+                        .map(|(runs, seed)| run_seeded(Some(seed), *index, &runs[*index]))
+                        .collect(),
+                    false => vec![run_seeded(None, *index, x)],
+                };
+            let results = results
+                .into_iter()
+                .map(|result| match maximize {
+                    true => unnegate(restore(result, x.method)),
+                    false => restore(result, x.method),
+                })
+                .collect::<Vec<_>>();
+            SweepRow::new(&results)
+        };
+        let rows = map_runs(&runs, parallel, sweep_method);
+        flush_csv();
+        let params = RunParams {
+            init: Some(init_param.to_vec()),
+            seed: None,
+            seed_drawn: false,
+            max_iters: iterations,
+            noise_stddev,
+            eval_budget,
+            maximize,
+        };
+        let output = format.render(&Report::new(problem.name(), params, rows));
+        match format {
+            Format::Table => print(format!(
+                "Best cost over seeds 0..{sweep} using {budget} from {init_param} \
+                 ({init_source}):\n{output}"
+            )),
+            _ => print(output),
+        }
+        return;
+    }
+
+    // Nothing beyond the config and the run options, with the default solver settings its
+    // labels name: the rows of `run_comparison`.
+    let plain = config.notes.is_empty()
+        && default_solvers
+        && !args.flag("profile")
+        && policy == FailurePolicy::KeepGoing;
+    let results = match plain {
+        true => {
+            let summary = run_comparison(&config, &options);
+            flush_csv();
+            summary
+                .unwrap_or_else(|e| exit_with_errors(&[e.to_string()]))
+                .rows
+        }
+        false => {
+            // Sequential runs are pulled lazily so `--fail-fast` skips the remaining methods.
+            let results = if parallel {
+                policy.collect(map_runs(&runs, true, run))
+            } else {
+                policy.collect(runs.iter().map(run))
+            };
+            flush_csv();
+            let mut results = results.unwrap_or_else(|e| exit_with_errors(&[e]));
+            if eval_budget.is_some() {
+                for (result, (_, run)) in results.iter_mut().zip(&runs) {
+                    result.iteration_cap = Some(max_iters(run.method));
+                }
+            }
+            results
+                .into_iter()
+                .zip(&runs)
+                .map(|(result, (_, run))| restore(result, run.method))
+                .map(|result| match rate_optimum {
+                    Some(optimum) => {
+                        let rate = convergence_rate(&problem, &init_param, optimum, &result);
+                        result.with_convergence_rate(rate)
+                    }
+                    None => result,
+                })
+                .map(|result| match maximize {
+                    true => unnegate(result),
+                    false => result,
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+    let profile_rows = results
+        .iter()
+        .zip(&runs)
+        .filter_map(|(result, (index, _))| {
+            let profile = profiles.get(index)?.lock().unwrap();
+            Some(ProfileRow::new(result, &profile))
+        })
+        .collect::<Vec<_>>();
+    if let Some(path) = &bench_json {
+        let benchmarks = results
+            .iter()
+            .zip(&runs)
+            .filter_map(|(result, (index, _))| {
+                BenchEntry::new(&result.method, &samples.get(index)?.lock().unwrap())
+            })
+            .collect();
+        let bench = BenchReport {
+            problem: problem.name().to_string(),
+            dim: problem.dim(),
+            benchmarks,
+        };
+        let json = serde_json::to_string_pretty(&bench).expect("reports are serializable");
+        fs::write(path, json).unwrap_or_else(|e| {
+            exit_with_errors(&[format!("Could not write `{}`: {e}", path.display())])
+        });
+    }
+    let checks = check_target.map(|target| {
+        results
+            .iter()
+            .zip(&runs)
+            .map(|(result, (_, run))| (run.method, target.check(result)))
+            .collect::<Vec<_>>()
+    });
+    let statuses = exit_status.then(|| {
+        results
+            .iter()
+            .zip(&runs)
+            .map(|(result, (_, run))| (run.method, result.status))
+            .collect::<Vec<_>>()
+    });
+    let settings = OutputSettings {
+        args: &args,
+        problem: &problem,
+        init: &init_param,
+        budget,
+        precision,
+        stream_ndjson,
+        sort,
+        relative,
+        columns: columns.as_ref(),
+        tol_report,
+        metrics: metrics.then_some(&metrics_weights),
+        pareto: pareto.as_ref(),
+        repro: config_hash.map(|hash| (hash, repro_digits)),
+        bounds_report,
+        profile_rows,
+        save_trajectory: save_trajectory.as_deref(),
+        output: output.as_deref(),
+        checks,
+        statuses,
+        required: &required,
+    };
+    settings.render(Summary {
+        rows: results,
+        config,
+    });
 }
@@ -0,0 +1,142 @@
+//! Fits a two parameter model to `x,y` points with Gauss-Newton (with a line search) and
+//! Levenberg-Marquardt, a small curve-fitting tool comparing both.
+//!
+//! The synthetic points are deterministic, so the comparison is reproducible.
+//!
+//! Points are read as CSV from stdin (`x,y` per line, an optional header), synthetic points from
+//! `y = 2 exp(0.3 x)` are used when stdin is empty or a terminal.
+//!
+//! Usage: `03-gauss-newton [max_iters] [--model exponential|linear] [--init 1.0,0.1]
+//! [--lm-lambda 1e-3] < data.csv`
+use std::{
+    io::{self, IsTerminal, Read},
+    process,
+};
+
+use argmin::solver::{gaussnewton::GaussNewtonLS, linesearch::MoreThuenteLineSearch};
+use argmin_exploring::{
+    cli::{exit_usage, parse_list, Args},
+    curve_fit::{parse_points, CurveFit, Model},
+    results::{Format, Report, RunParams},
+    run_solver, LevenbergMarquardt, Method, RunOptions,
+};
+use ndarray::{array, Array1};
+
+/// Points of `y = 2 exp(0.3 x)` for `x` in `[0, 10)`.
+fn synthetic_points() -> Vec<(f64, f64)> {
+    let truth = array![2.0, 0.3];
+    (0..20)
+        .map(|i| {
+            let x = i as f64 * 0.5;
+            (x, Model::Exponential.eval(&truth, x))
+        })
+        .collect()
+}
+
+fn exit_with_error(error: &str) -> ! {
+    eprintln!("Error: {error}");
+    process::exit(1);
+}
+
+fn main() {
+    let args = Args::from_env(&[]);
+    let iterations = args
+        .positional(0, "max_iters")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(100);
+    let model = args
+        .value::<Model>("model")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or_default();
+    let init = match args.value::<String>("init").unwrap_or_else(exit_usage) {
+        Some(x) => Array1::from_vec(
+            parse_list(&x)
+                .unwrap_or_else(|e| exit_with_error(&format!("Invalid `--init` ({x}): {e}"))),
+        ),
+        None => match model {
+            Model::Linear => array![0.0, 1.0],
+            Model::Exponential => array![1.0, 0.1],
+        },
+    };
+    if init.len() != 2 {
+        exit_with_error(&format!("`--init` needs 2 values, got {}", init.len()));
+    }
+    let levenberg_marquardt = match args.value::<f64>("lm-lambda").unwrap_or_else(exit_usage) {
+        Some(lambda) => LevenbergMarquardt::new()
+            .with_lambda(lambda)
+            .unwrap_or_else(|e| exit_with_error(&format!("Invalid `--lm-lambda`: {e}"))),
+        None => LevenbergMarquardt::new(),
+    };
+
+    let mut input = String::new();
+    if !io::stdin().is_terminal() {
+        io::stdin()
+            .read_to_string(&mut input)
+            .unwrap_or_else(|e| exit_with_error(&format!("Could not read stdin: {e}")));
+    }
+    let points = parse_points(&input).unwrap_or_else(|e| exit_with_error(&format!("stdin {e}")));
+    let points = if points.is_empty() {
+        let points = synthetic_points();
+        println!(
+            "No data on stdin, using {} synthetic points of y = 2 exp(0.3 x)",
+            points.len()
+        );
+        points
+    } else {
+        println!("Read {} points from stdin", points.len());
+        points
+    };
+    if points.len() < 2 {
+        exit_with_error("At least 2 points are needed to fit 2 parameters");
+    }
+
+    let problem = CurveFit::new(model, points);
+    let options = RunOptions {
+        log_every: 10,
+        ..Default::default()
+    };
+    let gauss_newton = run_solver(
+        Method::GaussNewtonLs.family(),
+        &Method::GaussNewtonLs.to_string(),
+        problem.clone(),
+        // Plain Gauss-Newton steps overshoot on the exponential model far from the fit.
+        GaussNewtonLS::new(MoreThuenteLineSearch::new()),
+        |state| state.param(init.clone()).max_iters(iterations),
+        &options,
+    );
+    let lm_label =
+        Method::LevenbergMarquardt.label_with(&format!("lambda {}", levenberg_marquardt.lambda()));
+    let levenberg_marquardt = run_solver(
+        Method::LevenbergMarquardt.family(),
+        &lm_label,
+        problem,
+        levenberg_marquardt,
+        |state| state.param(init.clone()).max_iters(iterations),
+        &options,
+    );
+    let results = vec![gauss_newton, levenberg_marquardt];
+
+    for result in &results {
+        let Some(p) = &result.best_param else {
+            continue;
+        };
+        match model {
+            Model::Linear => println!("Fit ({}): y = {} + {} x", result.method, p[0], p[1]),
+            Model::Exponential => {
+                println!("Fit ({}): y = {} exp({} x)", result.method, p[0], p[1])
+            }
+        }
+    }
+    let params = RunParams {
+        init: Some(init.to_vec()),
+        seed: None,
+        seed_drawn: false,
+        max_iters: iterations,
+        noise_stddev: None,
+        eval_budget: None,
+        maximize: false,
+    };
+    let report = Report::new(format!("{model} fit"), params, results);
+    // Both methods report the residual norm as their cost.
+    println!("{}", Format::Table.render(&report));
+}
@@ -0,0 +1,244 @@
+//! Runs the chosen solvers on the N-dimensional rosenbrock for `n = 2, 4, 8, ...` to study how
+//! iterations, time and final cost grow with the dimension.
+//!
+//! Usage: `04-scaling [--solver lbfgs,bfgs] [--max-dim 32] [--max-iters 1000] [--format table]
+//! [--dimension-sweep-plot out.svg] [--plot-metric iters|cost]`
+use argmin::solver::{
+    conjugategradient::{beta::PolakRibiere, NonlinearConjugateGradient},
+    gradientdescent::SteepestDescent,
+    linesearch::MoreThuenteLineSearch,
+    neldermead::NelderMead,
+    newton::{Newton, NewtonCG},
+    quasinewton::{SR1TrustRegion, BFGS, DFP, LBFGS},
+    trustregion::{Steihaug, TrustRegion},
+};
+use argmin_exploring::{
+    cli::{exit_usage, parse_list, Args},
+    dimension_plot::{DimensionPlot, SweepMetric},
+    results::{self, Format, Report, RunParams},
+    run_solver, Method, RosenbrockND, RunOptions,
+};
+use ndarray::{Array1, Array2};
+use serde::Serialize;
+use tabled::Tabled;
+
+const SOLVERS: [&str; 10] = [
+    "steepest-descent",
+    "nlcg",
+    "newton",
+    "newton-cg",
+    "bfgs",
+    "dfp",
+    "lbfgs",
+    "sr1-trustregion",
+    "trustregion-steihaug",
+    "nelder-mead",
+];
+
+#[derive(Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+struct ScalingResult {
+    dim: usize,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+/// Classic rosenbrock starting point `(-1.2, 1.0)` repeated along every pair of dimensions.
+fn init_param(dim: usize) -> Array1<f64> {
+    Array1::from_shape_fn(dim, |i| if i % 2 == 0 { -1.2 } else { 1.0 })
+}
+
+fn run(solver: &str, dim: usize, max_iters: u64, options: &RunOptions) -> results::Result {
+    let problem = RosenbrockND::with_dim(dim);
+    let init = init_param(dim);
+    match solver {
+        "steepest-descent" => run_solver(
+            Method::MoreThuente.family(),
+            &Method::MoreThuente.to_string(),
+            problem,
+            SteepestDescent::new(MoreThuenteLineSearch::new()),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "nlcg" => run_solver(
+            Method::NonlinearCg.family(),
+            &Method::NonlinearCg.to_string(),
+            problem,
+            NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), PolakRibiere::new())
+                .restart_iters(10)
+                .restart_orthogonality(0.1),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "newton" => run_solver(
+            Method::Newton.family(),
+            &Method::Newton.to_string(),
+            problem,
+            Newton::new(),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "newton-cg" => run_solver(
+            Method::NewtonCg.family(),
+            &Method::NewtonCg.to_string(),
+            problem,
+            NewtonCG::new(MoreThuenteLineSearch::new()),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "bfgs" => run_solver(
+            Method::Bfgs.family(),
+            &Method::Bfgs.to_string(),
+            problem,
+            BFGS::new(MoreThuenteLineSearch::new()),
+            |state| {
+                state
+                    .param(init)
+                    .inv_hessian(Array2::eye(dim))
+                    .max_iters(max_iters)
+            },
+            options,
+        ),
+        "dfp" => run_solver(
+            Method::Dfp.family(),
+            &Method::Dfp.to_string(),
+            problem,
+            DFP::new(MoreThuenteLineSearch::new()),
+            |state| {
+                state
+                    .param(init)
+                    .inv_hessian(Array2::eye(dim))
+                    .max_iters(max_iters)
+            },
+            options,
+        ),
+        "lbfgs" => run_solver(
+            Method::Lbfgs.family(),
+            &Method::Lbfgs.to_string(),
+            problem,
+            LBFGS::new(MoreThuenteLineSearch::new(), 5),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "sr1-trustregion" => run_solver(
+            Method::Sr1TrustRegion.family(),
+            &Method::Sr1TrustRegion.to_string(),
+            problem,
+            SR1TrustRegion::new(Steihaug::new()),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "trustregion-steihaug" => run_solver(
+            Method::Steihaug.family(),
+            &Method::Steihaug.to_string(),
+            problem,
+            TrustRegion::new(Steihaug::new()),
+            |state| state.param(init).max_iters(max_iters),
+            options,
+        ),
+        "nelder-mead" => {
+            // Initial simplex: the starting point plus a unit step along every axis.
+            let simplex = std::iter::once(init.clone())
+                .chain((0..dim).map(|i| {
+                    let mut x = init.clone();
+                    x[i] += 1.0;
+                    x
+                }))
+                .collect();
+            run_solver(
+                Method::NelderMead.family(),
+                &Method::NelderMead.to_string(),
+                problem,
+                NelderMead::new(simplex),
+                |state| state.param(init).max_iters(max_iters),
+                options,
+            )
+        }
+        _ => panic!(
+            "Unknown solver `{solver}`, expected one of: {}",
+            SOLVERS.join(", ")
+        ),
+    }
+}
+
+/// Flags that never take a value.
+const SWITCHES: &[&str] = &["json-pretty"];
+
+fn main() {
+    let args = Args::from_env(SWITCHES);
+    let solvers = args
+        .value::<String>("solver")
+        .unwrap_or_else(exit_usage)
+        .map(|x| parse_list::<String>(&x).unwrap())
+        .unwrap_or_else(|| vec!["lbfgs".to_string(), "bfgs".to_string()]);
+    let max_dim = args
+        .value("max-dim")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(32);
+    let max_iters = args
+        .value("max-iters")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(1000);
+    let options = RunOptions {
+        log_every: args
+            .value("log-every")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or(100),
+        ..Default::default()
+    };
+    let format = if args.flag("json-pretty") {
+        Format::JsonPretty
+    } else {
+        args.value::<Format>("format")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or_default()
+    };
+
+    // An SVG chart of the metric against the dimension, next to the table.
+    let plot_path = args
+        .value::<String>("dimension-sweep-plot")
+        .unwrap_or_else(exit_usage);
+    let metric = args
+        .value::<SweepMetric>("plot-metric")
+        .unwrap_or_else(exit_usage);
+    if plot_path.is_none() && metric.is_some() {
+        eprintln!("Error: `--plot-metric` only applies with `--dimension-sweep-plot`");
+        std::process::exit(1);
+    }
+
+    let dims = std::iter::successors(Some(2), |&n| Some(n * 2)).take_while(|&n| n <= max_dim);
+    let mut results = Vec::new();
+    for dim in dims {
+        for solver in &solvers {
+            results.push(ScalingResult {
+                dim,
+                result: run(solver, dim, max_iters, &options),
+            });
+        }
+    }
+
+    let params = RunParams {
+        init: None,
+        seed: None,
+        seed_drawn: false,
+        max_iters,
+        noise_stddev: None,
+        eval_budget: None,
+        maximize: false,
+    };
+    if let Some(path) = &plot_path {
+        let rows = results.iter().map(|x| (x.dim, &x.result));
+        let plot = DimensionPlot::new(rows, metric.unwrap_or_default());
+        let title = format!("rosenbrock, {max_iters} iterations");
+        if let Err(e) = std::fs::write(path, plot.render_svg(&title)) {
+            eprintln!("Error: could not write `{path}`: {e}");
+            std::process::exit(1);
+        }
+    }
+    let output = format.render(&Report::new("rosenbrock", params, results));
+    match format {
+        Format::Table => println!("Results using {max_iters} iterations:\n{output}"),
+        _ => println!("{output}"),
+    }
+}
@@ -0,0 +1,110 @@
+//! Minimizes the 2-D rosenbrock inside a circle around the origin with L-BFGS and a quadratic
+//! penalty, for an increasing penalty weight `mu`. The unconstrained minimum `(1, 1)` lies
+//! outside the unit circle, so the solutions move onto its boundary as `mu` grows.
+//!
+//! Every run starts from the solution of the previous one, as usual with penalty methods.
+//!
+//! Usage: `05-constrained [max_iters] [--radius 1.0] [--mu 1,10,100,1000,10000] [--format table]`
+use argmin::solver::{linesearch::MoreThuenteLineSearch, quasinewton::LBFGS};
+use argmin_exploring::{
+    cli::{exit_usage, parse_list, Args},
+    results::{self, Format, Report, RunParams},
+    run_solver, Constraint, Method, PenaltyProblem, RosenbrockND, RunOptions,
+};
+use ndarray::{array, Array1};
+use serde::Serialize;
+use tabled::Tabled;
+
+#[derive(Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+struct PenaltyResult {
+    mu: f64,
+    #[tabled(display_with = "display_param")]
+    param: Vec<f64>,
+    /// Distance of the solution to the center of the circle.
+    norm: f64,
+    violation: f64,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+fn display_param(param: &[f64]) -> String {
+    results::format_param(param, results::DEFAULT_PRECISION)
+}
+
+/// Flags that never take a value.
+const SWITCHES: &[&str] = &["json-pretty"];
+
+fn main() {
+    let args = Args::from_env(SWITCHES);
+    let max_iters = args
+        .positional(0, "max_iters")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(200);
+    let radius = args
+        .value::<f64>("radius")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(1.0);
+    let mus = args
+        .value::<String>("mu")
+        .unwrap_or_else(exit_usage)
+        .map(|x| parse_list::<f64>(&x).unwrap())
+        .unwrap_or_else(|| vec![1.0, 10.0, 100.0, 1_000.0, 10_000.0]);
+    let options = RunOptions {
+        quiet: true,
+        ..Default::default()
+    };
+    let format = if args.flag("json-pretty") {
+        Format::JsonPretty
+    } else {
+        args.value::<Format>("format")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or_default()
+    };
+
+    let init = array![0.0, 0.0];
+    let mut param = init.clone();
+    let mut results = Vec::new();
+    for mu in mus {
+        let problem = PenaltyProblem::new(RosenbrockND::default())
+            .with_constraint(Constraint::ball(array![0.0, 0.0], radius))
+            .with_mu(mu)
+            .unwrap_or_else(|e| panic!("Invalid `--mu`: {e}"));
+        let result = run_solver(
+            Method::Lbfgs.family(),
+            &Method::Lbfgs.to_string(),
+            problem.clone(),
+            LBFGS::new(MoreThuenteLineSearch::new(), 5),
+            |state| state.param(param.clone()).max_iters(max_iters),
+            &options,
+        );
+        if let Some(best) = &result.best_param {
+            param = Array1::from_vec(best.clone());
+        }
+        results.push(PenaltyResult {
+            mu,
+            param: param.to_vec(),
+            norm: param.dot(&param).sqrt(),
+            violation: problem.violation(&param),
+            result,
+        });
+    }
+
+    let params = RunParams {
+        init: Some(init.to_vec()),
+        seed: None,
+        seed_drawn: false,
+        max_iters,
+        noise_stddev: None,
+        eval_budget: None,
+        maximize: false,
+    };
+    let output = format.render(&Report::new("rosenbrock", params, results));
+    match format {
+        Format::Table => println!(
+            "Rosenbrock inside the circle of radius {radius} using {max_iters} iterations:\n{output}"
+        ),
+        _ => println!("{output}"),
+    }
+}
@@ -0,0 +1,117 @@
+//! Minimizes the weighted sum `w * f1 + (1 - w) * f2` of two spheres centered at `(1, 1)` and
+//! `(-1, -1)` with L-BFGS, for `w` going from 1 to 0. The minimizer moves along the segment
+//! between both centers, tracing the trade-off between the two objectives.
+//!
+//! Usage: `06-weighted-sum [max_iters] [--steps 11] [--format table]`
+use argmin::{
+    core::CostFunction,
+    solver::{linesearch::MoreThuenteLineSearch, quasinewton::LBFGS},
+};
+use argmin_exploring::{
+    cli::{exit_usage, Args},
+    results::{self, Format, Report, RunParams},
+    run_solver, Method, Quadratic, RunOptions, WeightedSum,
+};
+use ndarray::{array, Array1, Array2};
+use serde::Serialize;
+use tabled::Tabled;
+
+#[derive(Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+struct WeightedResult {
+    w: f64,
+    #[tabled(display_with = "display_param")]
+    param: Vec<f64>,
+    /// Costs of each objective at the solution.
+    f1: f64,
+    f2: f64,
+    #[tabled(inline)]
+    #[serde(flatten)]
+    result: results::Result,
+}
+
+fn display_param(param: &[f64]) -> String {
+    results::format_param(param, results::DEFAULT_PRECISION)
+}
+
+/// Sphere centered at `center`, up to a constant.
+fn sphere(center: Array1<f64>) -> Quadratic {
+    Quadratic::new(Array2::eye(center.len()), center)
+}
+
+/// Flags that never take a value.
+const SWITCHES: &[&str] = &["json-pretty"];
+
+fn main() {
+    let args = Args::from_env(SWITCHES);
+    let max_iters = args
+        .positional(0, "max_iters")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(100);
+    let steps = args
+        .value::<usize>("steps")
+        .unwrap_or_else(exit_usage)
+        .unwrap_or(11);
+    if steps < 2 {
+        eprintln!("Error: `--steps` must be at least 2, got {steps}");
+        std::process::exit(1);
+    }
+    let options = RunOptions {
+        quiet: true,
+        ..Default::default()
+    };
+    let format = if args.flag("json-pretty") {
+        Format::JsonPretty
+    } else {
+        args.value::<Format>("format")
+            .unwrap_or_else(exit_usage)
+            .unwrap_or_default()
+    };
+
+    let first = sphere(array![1.0, 1.0]);
+    let second = sphere(array![-1.0, -1.0]);
+    let init = array![0.0, 0.0];
+    let mut results = Vec::new();
+    for i in 0..steps {
+        let w = 1.0 - i as f64 / (steps - 1) as f64;
+        let problem = WeightedSum::new(first.clone(), second.clone(), w)
+            .unwrap_or_else(|e| panic!("Invalid weight: {e}"));
+        let result = run_solver(
+            Method::Lbfgs.family(),
+            &Method::Lbfgs.to_string(),
+            problem,
+            LBFGS::new(MoreThuenteLineSearch::new(), 5),
+            |state| state.param(init.clone()).max_iters(max_iters),
+            &options,
+        );
+        let param = result
+            .best_param
+            .clone()
+            .map(Array1::from_vec)
+            .unwrap_or_else(|| init.clone());
+        results.push(WeightedResult {
+            w,
+            param: param.to_vec(),
+            f1: first.cost(&param).unwrap(),
+            f2: second.cost(&param).unwrap(),
+            result,
+        });
+    }
+
+    let params = RunParams {
+        init: Some(init.to_vec()),
+        seed: None,
+        seed_drawn: false,
+        max_iters,
+        noise_stddev: None,
+        eval_budget: None,
+        maximize: false,
+    };
+    let output = format.render(&Report::new("weighted-sum", params, results));
+    match format {
+        Format::Table => println!(
+            "Weighted sum of two spheres for {steps} weights using {max_iters} iterations:\n{output}"
+        ),
+        _ => println!("{output}"),
+    }
+}
@@ -0,0 +1,61 @@
+//! Compares two result dumps (written with `--dump` or `--format json`) method by method, flagging
+//! the methods whose best cost, iterations or best param changed beyond `--tol`.
+//!
+//! Usage: `compare-runs <before.json> <after.json> [--tol 1e-8]`
+//!
+//! Exits with status 1 when any method changed or is missing from one of the runs.
+use std::{fs, process};
+
+use argmin_exploring::{
+    cli::{exit_usage, Args},
+    compare::{compare, Status},
+    results::{self, Report},
+};
+use tabled::{Style, Table};
+
+fn load(path: &str) -> Report<results::Result> {
+    let report = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|x| serde_json::from_str(&x).map_err(|e| e.to_string()));
+    report.unwrap_or_else(|e| {
+        eprintln!("Could not load `{path}`: {e}");
+        process::exit(2);
+    })
+}
+
+fn main() {
+    let args = Args::from_env(&[]);
+    let (Some(before), Some(after)) = (
+        args.positional::<String>(0, "before")
+            .unwrap_or_else(exit_usage),
+        args.positional::<String>(1, "after")
+            .unwrap_or_else(exit_usage),
+    ) else {
+        eprintln!("Usage: compare-runs <before.json> <after.json> [--tol 1e-8]");
+        process::exit(2);
+    };
+    let tol = args.value("tol").unwrap_or_else(exit_usage).unwrap_or(1e-8);
+    let (before, after) = (load(&before), load(&after));
+
+    if before.problem != after.problem {
+        println!(
+            "Warning: comparing different problems ({} vs {})",
+            before.problem, after.problem
+        );
+    }
+    if before.params != after.params {
+        println!(
+            "Warning: run parameters differ ({:?} vs {:?})",
+            before.params, after.params
+        );
+    }
+
+    let diffs = compare(&before.results, &after.results, tol);
+    println!("{}", Table::new(&diffs).with(Style::modern()));
+    let changed = diffs.iter().filter(|x| x.status != Status::Same).count();
+    if changed > 0 {
+        println!("{changed} of {} methods differ (tol = {tol})", diffs.len());
+        process::exit(1);
+    }
+    println!("All {} methods match (tol = {tol})", diffs.len());
+}
@@ -0,0 +1,127 @@
+use std::f64::consts::PI;
+
+use argmin::core::{CostFunction, Gradient};
+use ndarray::{array, Array1};
+
+/// The three Bohachevsky functions, smooth and mildly multimodal:
+/// $ f_1(x,y) = x^2 + 2y^2 - 0.3\cos(3\pi x) - 0.4\cos(4\pi y) + 0.7 $
+/// $ f_2(x,y) = x^2 + 2y^2 - 0.3\cos(3\pi x)\cos(4\pi y) + 0.3 $
+/// $ f_3(x,y) = x^2 + 2y^2 - 0.3\cos(3\pi x + 4\pi y) + 0.3 $
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BohachevskyVariant {
+    One,
+    Two,
+    Three,
+}
+
+/// The global minimum of every variant is $f(0,0) = 0$, usually evaluated on $[-100, 100]^2$.
+#[derive(Debug, Clone, Copy)]
+pub struct Bohachevsky {
+    variant: BohachevskyVariant,
+}
+
+impl Bohachevsky {
+    pub fn new(variant: BohachevskyVariant) -> Self {
+        Self { variant }
+    }
+
+    pub fn variant(&self) -> BohachevskyVariant {
+        self.variant
+    }
+
+    /// The usual evaluation box $[-100, 100]^2$, as `(lower, upper)`.
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (Array1::from_elem(2, -100.0), Array1::from_elem(2, 100.0))
+    }
+}
+
+impl CostFunction for Bohachevsky {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let (x, y) = (param[0], param[1]);
+        let base = x.powi(2) + 2.0 * y.powi(2);
+        let cost = match self.variant {
+            BohachevskyVariant::One => {
+                base - 0.3 * (3.0 * PI * x).cos() - 0.4 * (4.0 * PI * y).cos() + 0.7
+            }
+            BohachevskyVariant::Two => {
+                base - 0.3 * (3.0 * PI * x).cos() * (4.0 * PI * y).cos() + 0.3
+            }
+            BohachevskyVariant::Three => base - 0.3 * (3.0 * PI * x + 4.0 * PI * y).cos() + 0.3,
+        };
+        Ok(cost)
+    }
+}
+
+impl Gradient for Bohachevsky {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        let (x, y) = (param[0], param[1]);
+        let gradient = match self.variant {
+            BohachevskyVariant::One => array![
+                2.0 * x + 0.9 * PI * (3.0 * PI * x).sin(),
+                4.0 * y + 1.6 * PI * (4.0 * PI * y).sin(),
+            ],
+            BohachevskyVariant::Two => array![
+                2.0 * x + 0.9 * PI * (3.0 * PI * x).sin() * (4.0 * PI * y).cos(),
+                4.0 * y + 1.2 * PI * (3.0 * PI * x).cos() * (4.0 * PI * y).sin(),
+            ],
+            BohachevskyVariant::Three => {
+                let s = (3.0 * PI * x + 4.0 * PI * y).sin();
+                array![2.0 * x + 0.9 * PI * s, 4.0 * y + 1.2 * PI * s]
+            }
+        };
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARIANTS: [BohachevskyVariant; 3] = [
+        BohachevskyVariant::One,
+        BohachevskyVariant::Two,
+        BohachevskyVariant::Three,
+    ];
+
+    #[test]
+    fn test_optimum() {
+        let origin = array![0.0, 0.0];
+        for variant in VARIANTS {
+            let f = Bohachevsky::new(variant);
+            assert!(f.cost(&origin).unwrap().abs() < 1e-15, "{variant:?}");
+            assert_eq!(
+                f.gradient(&origin).unwrap(),
+                array![0.0, 0.0],
+                "{variant:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_gradient() {
+        let eps = 1e-6;
+        for variant in VARIANTS {
+            let f = Bohachevsky::new(variant);
+            for param in [array![0.3, -0.2], array![1.5, 2.5], array![-10.2, 20.0]] {
+                let gradient = f.gradient(&param).unwrap();
+                for i in 0..2 {
+                    let mut step = Array1::zeros(2);
+                    step[i] = eps;
+                    let fd = (f.cost(&(&param + &step)).unwrap()
+                        - f.cost(&(&param - &step)).unwrap())
+                        / (2.0 * eps);
+                    assert!(
+                        (fd - gradient[i]).abs() < 1e-4,
+                        "{variant:?}: {fd} != {gradient}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,458 @@
+use argmin::{
+    core::{
+        CostFunction, Error, Gradient, Hessian, IterState, Jacobian, Operator,
+        Problem as ArgminProblem, Solver, State, TerminationStatus, KV,
+    },
+    solver::simulatedannealing::Anneal,
+};
+use ndarray::{Array1, Array2};
+
+use crate::{
+    bohachevsky::Bohachevsky, caching::CachingProblem, ellipsoid::Ellipsoid, hartmann::Hartmann,
+    noisy::NoisyProblem, perm::Perm, profile::Profiled, shekel::Shekel, Michalewicz, Problem,
+    RosenbrockND,
+};
+
+/// Problems defined on a box.
+///
+/// Only the `Anneal` moves and particle swarm respect the box on their own, [`BoundedProblem`]
+/// and [`Projected`] keep every other solver inside it, as [`BoundsMode`] says.
+pub trait ParameterBounds {
+    fn lower(&self) -> Array1<f64>;
+
+    fn upper(&self) -> Array1<f64>;
+
+    /// Closest point of the box to `param`.
+    fn project(&self, param: &Array1<f64>) -> Array1<f64> {
+        let (lower, upper) = (self.lower(), self.upper());
+        let mut param = param.clone();
+        param.zip_mut_with(&lower, |x, l| *x = x.max(*l));
+        param.zip_mut_with(&upper, |x, u| *x = x.min(*u));
+        param
+    }
+
+    fn contains(&self, param: &Array1<f64>) -> bool {
+        let (lower, upper) = (self.lower(), self.upper());
+        param
+            .iter()
+            .zip(lower.iter().zip(&upper))
+            .all(|(x, (l, u))| l <= x && x <= u)
+    }
+}
+
+impl ParameterBounds for RosenbrockND {
+    fn lower(&self) -> Array1<f64> {
+        self.lower_bound().clone()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.upper_bound().clone()
+    }
+}
+
+impl ParameterBounds for Michalewicz {
+    fn lower(&self) -> Array1<f64> {
+        self.lower_bound().clone()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.upper_bound().clone()
+    }
+}
+
+impl ParameterBounds for Perm {
+    fn lower(&self) -> Array1<f64> {
+        self.bounds().0
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.bounds().1
+    }
+}
+
+impl ParameterBounds for Hartmann {
+    fn lower(&self) -> Array1<f64> {
+        self.bounds().0
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.bounds().1
+    }
+}
+
+impl ParameterBounds for Shekel {
+    fn lower(&self) -> Array1<f64> {
+        self.bounds().0
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.bounds().1
+    }
+}
+
+impl ParameterBounds for Ellipsoid {
+    fn lower(&self) -> Array1<f64> {
+        self.bounds().0
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.bounds().1
+    }
+}
+
+impl ParameterBounds for Bohachevsky {
+    fn lower(&self) -> Array1<f64> {
+        self.bounds().0
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.bounds().1
+    }
+}
+
+impl ParameterBounds for Problem {
+    fn lower(&self) -> Array1<f64> {
+        self.bounds().0
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.bounds().1
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for NoisyProblem<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner().lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner().upper()
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for CachingProblem<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner().lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner().upper()
+    }
+}
+
+impl<P: ParameterBounds> ParameterBounds for Profiled<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.inner().lower()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.inner().upper()
+    }
+}
+
+/// How the box of a problem is enforced, see [`BoundedProblem::with_mode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoundsMode {
+    /// No box at all.
+    #[default]
+    Unbounded,
+    /// Trial points are evaluated on the box and [`Projected`] projects the iterates onto it.
+    Project,
+    /// Trial points are evaluated untouched, only the iterates are clamped into the box.
+    Clamp,
+}
+
+/// Evaluates the inner problem at the projection of the param onto the box, so trial points
+/// outside of it (e.g. from a line search) see the value on its border.
+///
+/// A quadratic penalty on the distance to the box is added outside of it: without it the cost is
+/// flat there and line searches pushing outwards never stop expanding their bracket.
+/// [`BoundedProblem::unbounded`] uses an infinite box and [`BoundedProblem::clamped`] keeps the
+/// box only for [`Projected`], both evaluating the inner problem untouched.
+#[derive(Debug, Clone)]
+pub struct BoundedProblem<P> {
+    inner: P,
+    lower: Array1<f64>,
+    upper: Array1<f64>,
+    evaluate_outside: bool,
+}
+
+impl<P: ParameterBounds> BoundedProblem<P> {
+    pub fn new(inner: P) -> Self {
+        let (lower, upper) = (inner.lower(), inner.upper());
+        Self {
+            inner,
+            lower,
+            upper,
+            evaluate_outside: false,
+        }
+    }
+
+    pub fn unbounded(inner: P) -> Self {
+        let dim = inner.lower().len();
+        Self {
+            inner,
+            lower: Array1::from_elem(dim, f64::NEG_INFINITY),
+            upper: Array1::from_elem(dim, f64::INFINITY),
+            evaluate_outside: false,
+        }
+    }
+
+    /// Keeps the box of the inner problem for [`Projected`] to clamp the iterates into, but
+    /// evaluates the inner problem untouched everywhere. Lighter than [`BoundedProblem::new`]:
+    /// line searches see the actual cost outside of the box, only where they end is clamped.
+    pub fn clamped(inner: P) -> Self {
+        Self {
+            evaluate_outside: true,
+            ..Self::new(inner)
+        }
+    }
+
+    pub fn with_mode(inner: P, mode: BoundsMode) -> Self {
+        match mode {
+            BoundsMode::Unbounded => Self::unbounded(inner),
+            BoundsMode::Project => Self::new(inner),
+            BoundsMode::Clamp => Self::clamped(inner),
+        }
+    }
+}
+
+impl<P> BoundedProblem<P> {
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Where the inner problem is evaluated for `param`.
+    fn evaluated_at(&self, param: &Array1<f64>) -> Array1<f64> {
+        match self.evaluate_outside {
+            true => param.clone(),
+            false => self.project(param),
+        }
+    }
+}
+
+impl<P> ParameterBounds for BoundedProblem<P> {
+    fn lower(&self) -> Array1<f64> {
+        self.lower.clone()
+    }
+
+    fn upper(&self) -> Array1<f64> {
+        self.upper.clone()
+    }
+}
+
+impl<P: CostFunction<Param = Array1<f64>, Output = f64>> CostFunction for BoundedProblem<P> {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let projected = self.evaluated_at(param);
+        let penalty = (param - &projected).mapv(|x| x.powi(2)).sum();
+        Ok(self.inner.cost(&projected)? + penalty)
+    }
+}
+
+impl<P: Gradient<Param = Array1<f64>, Gradient = Array1<f64>>> Gradient for BoundedProblem<P> {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let projected = self.evaluated_at(param);
+        let mut gradient = self.inner.gradient(&projected)?;
+        for ((g, x), p) in gradient.iter_mut().zip(param).zip(&projected) {
+            if x != p {
+                *g = 2.0 * (x - p);
+            }
+        }
+        Ok(gradient)
+    }
+}
+
+impl<P: Hessian<Param = Array1<f64>, Hessian = Array2<f64>>> Hessian for BoundedProblem<P> {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.inner.hessian(&self.evaluated_at(param))
+    }
+}
+
+impl<P: Operator<Param = Array1<f64>>> Operator for BoundedProblem<P> {
+    type Param = Array1<f64>;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.apply(&self.evaluated_at(param))
+    }
+}
+
+impl<P: Jacobian<Param = Array1<f64>>> Jacobian for BoundedProblem<P> {
+    type Param = Array1<f64>;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.inner.jacobian(&self.evaluated_at(param))
+    }
+}
+
+impl<P: Anneal<Param = Array1<f64>>> Anneal for BoundedProblem<P> {
+    type Param = Array1<f64>;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        self.inner.anneal(param, temp)
+    }
+}
+
+/// Wraps a solver to project its iterate back into the box of the problem after every step,
+/// re-evaluating the cost there.
+///
+/// Pair it with [`BoundedProblem::new`], so the solver itself never evaluates points outside of
+/// the box. With [`BoundedProblem::clamped`], or any problem with finite bounds, it is the
+/// lighter option: the line searches may still evaluate the problem outside, but every iterate
+/// is clamped back in.
+///
+/// This can't be an observer: argmin's `Observe` only gets a shared reference to the state, so
+/// none of its solvers lets an observer modify the param. Wrapping the solver works for all of
+/// those with an `IterState` and `Array1` params, i.e. every gradient, Newton and quasi-Newton
+/// method; Nelder-Mead keeps its simplex and particle swarm its population in its own state.
+#[derive(Debug, Clone)]
+pub struct Projected<S> {
+    solver: S,
+}
+
+impl<S> Projected<S> {
+    pub fn new(solver: S) -> Self {
+        Self { solver }
+    }
+
+    fn project<O, G, J, H>(
+        problem: &mut ArgminProblem<O>,
+        mut state: IterState<Array1<f64>, G, J, H, f64>,
+    ) -> Result<IterState<Array1<f64>, G, J, H, f64>, Error>
+    where
+        O: ParameterBounds + CostFunction<Param = Array1<f64>, Output = f64>,
+    {
+        let projected = match (problem.problem.as_ref(), state.get_param()) {
+            (Some(bounds), Some(param)) if !bounds.contains(param) => bounds.project(param),
+            _ => return Ok(state),
+        };
+        let cost = problem.cost(&projected)?;
+        // Setting a param shifts the current one into the previous one, keep the actual previous
+        // param rather than the unprojected one.
+        let prev = state.take_prev_param();
+        state.take_param();
+        let state = match prev {
+            Some(prev) => state.param(prev),
+            None => state,
+        };
+        Ok(state.param(projected).cost(cost))
+    }
+}
+
+impl<O, S, G, J, H> Solver<O, IterState<Array1<f64>, G, J, H, f64>> for Projected<S>
+where
+    O: ParameterBounds + CostFunction<Param = Array1<f64>, Output = f64>,
+    S: Solver<O, IterState<Array1<f64>, G, J, H, f64>>,
+{
+    const NAME: &'static str = S::NAME;
+
+    fn init(
+        &mut self,
+        problem: &mut ArgminProblem<O>,
+        state: IterState<Array1<f64>, G, J, H, f64>,
+    ) -> Result<(IterState<Array1<f64>, G, J, H, f64>, Option<KV>), Error> {
+        let (state, kv) = self.solver.init(problem, state)?;
+        Ok((Self::project(problem, state)?, kv))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut ArgminProblem<O>,
+        state: IterState<Array1<f64>, G, J, H, f64>,
+    ) -> Result<(IterState<Array1<f64>, G, J, H, f64>, Option<KV>), Error> {
+        let (state, kv) = self.solver.next_iter(problem, state)?;
+        Ok((Self::project(problem, state)?, kv))
+    }
+
+    fn terminate(&mut self, state: &IterState<Array1<f64>, G, J, H, f64>) -> TerminationStatus {
+        self.solver.terminate(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::Executor,
+        solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch},
+    };
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_project() {
+        let f = RosenbrockND::with_dim(3);
+        assert_eq!(f.project(&array![-7.0, 0.5, 9.0]), array![-5.0, 0.5, 5.0]);
+        assert!(f.contains(&array![-5.0, 0.5, 5.0]));
+        assert!(!f.contains(&array![-7.0, 0.5, 9.0]));
+        let unbounded = BoundedProblem::unbounded(f);
+        assert!(unbounded.contains(&array![-1e300, 0.0, 1e300]));
+    }
+
+    #[test]
+    fn test_projected_solver_stays_in_bounds() {
+        // The unconstrained minimum (1, 1) is outside of the box.
+        let f = RosenbrockND::new(1.0, 100.0, array![-2.0, -2.0], array![0.5, 0.5]);
+        let solver = || SteepestDescent::new(MoreThuenteLineSearch::new());
+        let init = array![-1.2, 0.0];
+
+        let res = Executor::new(f.clone(), solver())
+            .configure(|state| state.param(init.clone()).max_iters(100))
+            .run()
+            .unwrap();
+        assert!(!f.contains(res.state.get_best_param().unwrap()));
+
+        let res = Executor::new(BoundedProblem::new(f.clone()), Projected::new(solver()))
+            .configure(|state| state.param(init.clone()).max_iters(100))
+            .run()
+            .unwrap();
+        let best_param = res.state.get_best_param().unwrap();
+        assert!(f.contains(best_param), "{best_param}");
+        // The constrained minimum is on the border, at x = 0.5.
+        assert!((best_param[0] - 0.5).abs() < 1e-3, "{best_param}");
+
+        // Without the bounded problem only the iterates are clamped, which is enough to end
+        // inside.
+        let res = Executor::new(f.clone(), Projected::new(solver()))
+            .configure(|state| state.param(init.clone()).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_param().unwrap();
+        assert!(f.contains(param), "{param}");
+        assert!(f.contains(res.state.get_best_param().unwrap()));
+    }
+
+    #[test]
+    fn test_clamped_unconstrained_method() {
+        // Steepest descent knows nothing about the box, around the minimum (1, 1) outside of it.
+        let f = RosenbrockND::new(1.0, 100.0, array![-2.0, -2.0], array![0.5, 0.8]);
+        let clamped = BoundedProblem::clamped(f.clone());
+        // Evaluated untouched, without the penalty of the projecting mode.
+        let outside = array![1.0, 1.0];
+        assert_eq!(clamped.cost(&outside).unwrap(), 0.0);
+        assert!(BoundedProblem::new(f.clone()).cost(&outside).unwrap() > 0.0);
+
+        let solver = Projected::new(SteepestDescent::new(MoreThuenteLineSearch::new()));
+        let res = Executor::new(clamped, solver)
+            .configure(|state| state.param(array![-1.2, 0.0]).max_iters(100))
+            .run()
+            .unwrap();
+        let best_param = res.state.get_best_param().unwrap();
+        for ((x, l), u) in best_param.iter().zip(&f.lower()).zip(&f.upper()) {
+            assert!(l <= x && x <= u, "{best_param}");
+        }
+        assert!((best_param[0] - 0.5).abs() < 1e-3, "{best_param}");
+    }
+}
@@ -0,0 +1,129 @@
+use ndarray::Array1;
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::results::{self, format_float, DEFAULT_PRECISION};
+
+/// Largest distance of `param` outside the box along any axis, 0 inside it.
+pub fn violation(param: &[f64], lower: &Array1<f64>, upper: &Array1<f64>) -> f64 {
+    param
+        .iter()
+        .zip(lower.iter().zip(upper))
+        .map(|(x, (l, u))| (l - x).max(x - u).max(0.0))
+        .fold(0.0, f64::max)
+}
+
+/// Whether the iterates of a run left the `[lower, upper]` box of the problem, read from its
+/// trajectory: tells a solver that stayed inside by luck from one respecting the bounds.
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+pub struct BoundsRow {
+    pub family: String,
+    pub method: String,
+    /// `None` without a trajectory, e.g. for a failed run.
+    #[tabled(display_with = "display_left")]
+    pub left_box: Option<bool>,
+    /// First iterate outside the box, counted from 0 like the rows of the trajectory.
+    #[tabled(display_with = "display_iter")]
+    pub first_exit: Option<usize>,
+    /// Largest distance outside the box along any axis over all the iterates.
+    #[tabled(display_with = "display_violation")]
+    pub max_violation: Option<f64>,
+}
+
+impl BoundsRow {
+    pub fn new(result: &results::Result, lower: &Array1<f64>, upper: &Array1<f64>) -> Self {
+        let violations = result.trajectory.as_ref().map(|x| {
+            x.params
+                .iter()
+                .map(|param| violation(param, lower, upper))
+                .collect::<Vec<_>>()
+        });
+        let first_exit = violations
+            .as_ref()
+            .and_then(|x| x.iter().position(|&v| v > 0.0));
+        Self {
+            family: result.family.clone(),
+            method: result.method.clone(),
+            left_box: violations.as_ref().map(|_| first_exit.is_some()),
+            first_exit,
+            max_violation: violations.map(|x| x.into_iter().fold(0.0, f64::max)),
+        }
+    }
+}
+
+fn display_left(left: &Option<bool>) -> String {
+    match left {
+        Some(true) => "yes".to_string(),
+        Some(false) => "no".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn display_iter(iter: &Option<usize>) -> String {
+    match iter {
+        Some(x) => x.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn display_violation(violation: &Option<f64>) -> String {
+    match violation {
+        Some(x) => format_float(*x, DEFAULT_PRECISION),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::IterState,
+        solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch},
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::{
+        bounds::{BoundedProblem, ParameterBounds, Projected},
+        run_solver, RosenbrockND, RunOptions,
+    };
+
+    #[test]
+    fn test_unconstrained_steps_leave_the_box() {
+        // The unconstrained minimum (1, 1) is outside of the box.
+        let f = RosenbrockND::new(1.0, 100.0, array![-2.0, -2.0], array![0.5, 0.5]);
+        let (lower, upper) = (f.lower(), f.upper());
+        let options = RunOptions {
+            quiet: true,
+            trajectory: true,
+            ..Default::default()
+        };
+        let solver = || SteepestDescent::new(MoreThuenteLineSearch::new());
+        let configure =
+            |state: IterState<_, _, _, _, _>| state.param(array![-1.2, 0.0]).max_iters(100);
+
+        let free = run_solver("", "Free", f.clone(), solver(), configure, &options);
+        let row = BoundsRow::new(&free, &lower, &upper);
+        assert_eq!(row.left_box, Some(true));
+        assert!(row.max_violation.unwrap() > 0.1, "{row:?}");
+        let exit = row.first_exit.unwrap();
+        let param = &free.trajectory.as_ref().unwrap().params[exit];
+        assert!(!f.contains(&Array1::from(param.clone())));
+
+        let projected = Projected::new(solver());
+        let bounded = run_solver(
+            "",
+            "Bounded",
+            BoundedProblem::new(f),
+            projected,
+            configure,
+            &options,
+        );
+        let row = BoundsRow::new(&bounded, &lower, &upper);
+        assert_eq!((row.left_box, row.first_exit), (Some(false), None));
+        assert_eq!(row.max_violation, Some(0.0));
+
+        let failed = results::Result::failed("", "Failed", &"no gradient");
+        assert_eq!(BoundsRow::new(&failed, &lower, &upper).left_box, None);
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use argmin::{
+    core::{ArgminError, CostFunction, Error, Gradient, Hessian, Jacobian, Operator},
+    solver::simulatedannealing::Anneal,
+};
+use ndarray::Array1;
+
+/// Bits of the mantissa of every coordinate kept in the keys of a [`CostCache`] by default: all
+/// of them, only exact repeats hit.
+pub const EXACT_BITS: u32 = 52;
+
+/// Costs memoized by [`CachingProblem`], with the hits and misses of the lookups.
+#[derive(Debug, Clone)]
+pub struct CostCache {
+    costs: HashMap<Vec<u64>, f64>,
+    bits: u32,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CostCache {
+    /// Cache of exact params, see [`Self::with_bits`].
+    pub fn new() -> Self {
+        Self {
+            costs: HashMap::new(),
+            bits: EXACT_BITS,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Quantizes the params to the `bits` leading bits of the mantissa of every coordinate, in
+    /// `1..=52`: params agreeing on them share an entry, e.g. 40 bits merge params within a
+    /// relative `1e-12` or so.
+    pub fn with_bits(mut self, bits: u32) -> Result<Self, Error> {
+        if !(1..=EXACT_BITS).contains(&bits) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`bits` must be in [1, {EXACT_BITS}], got {bits}"),
+            }
+            .into());
+        }
+        self.bits = bits;
+        Ok(self)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Distinct params evaluated.
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    /// Fraction of the lookups answered from the cache, `None` before any.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+
+    /// `-0` and `0` share a key.
+    fn key(&self, param: &Array1<f64>) -> Vec<u64> {
+        let drop = EXACT_BITS - self.bits;
+        param.iter().map(|x| (x + 0.0).to_bits() >> drop).collect()
+    }
+}
+
+impl Default for CostCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps a problem to memoize its cost in a shared [`CostCache`], so a solver coming back to a
+/// param (e.g. a line search retrying a step) doesn't pay for it twice. Forwards untouched
+/// without a cache.
+///
+/// Only the cost is cached, the derivatives always reach the inner problem. A noisy cost should
+/// be wrapped around the cache, not inside it, or its noise would be frozen.
+#[derive(Debug, Clone)]
+pub struct CachingProblem<P> {
+    inner: P,
+    cache: Option<Arc<Mutex<CostCache>>>,
+}
+
+impl<P> CachingProblem<P> {
+    pub fn new(inner: P, cache: Option<Arc<Mutex<CostCache>>>) -> Self {
+        Self { inner, cache }
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P> CostFunction for CachingProblem<P>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let Some(cache) = &self.cache else {
+            return self.inner.cost(param);
+        };
+        let key = {
+            let mut cache = cache.lock().unwrap();
+            let key = cache.key(param);
+            if let Some(&cost) = cache.costs.get(&key) {
+                cache.hits += 1;
+                return Ok(cost);
+            }
+            key
+        };
+        // Evaluated without holding the lock.
+        let cost = self.inner.cost(param)?;
+        let mut cache = cache.lock().unwrap();
+        cache.misses += 1;
+        cache.costs.insert(key, cost);
+        Ok(cost)
+    }
+}
+
+impl<P: Gradient> Gradient for CachingProblem<P> {
+    type Param = P::Param;
+    type Gradient = P::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.inner.gradient(param)
+    }
+}
+
+impl<P: Hessian> Hessian for CachingProblem<P> {
+    type Param = P::Param;
+    type Hessian = P::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.inner.hessian(param)
+    }
+}
+
+impl<P: Operator> Operator for CachingProblem<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.inner.apply(param)
+    }
+}
+
+impl<P: Jacobian> Jacobian for CachingProblem<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.inner.jacobian(param)
+    }
+}
+
+impl<P: Anneal> Anneal for CachingProblem<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        self.inner.anneal(param, temp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use ndarray::array;
+
+    use super::*;
+
+    /// Sphere counting its evaluations.
+    #[derive(Default)]
+    struct Counting(AtomicUsize);
+
+    impl CostFunction for Counting {
+        type Param = Array1<f64>;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Ok(param.dot(param))
+        }
+    }
+
+    #[test]
+    fn test_repeats_hit_the_cache() {
+        let cache = Arc::new(Mutex::new(CostCache::new()));
+        let problem = CachingProblem::new(Counting::default(), Some(cache.clone()));
+        let (a, b) = (array![1.0, 2.0], array![1.0, 2.0 + 1e-15]);
+        for _ in 0..3 {
+            assert_eq!(problem.cost(&a).unwrap(), 5.0);
+        }
+        problem.cost(&b).unwrap();
+        assert_eq!(problem.inner().0.load(Ordering::Relaxed), 2);
+        let stats = cache.lock().unwrap().clone();
+        assert_eq!((stats.hits, stats.misses, stats.len()), (2, 2, 2));
+        assert_eq!(stats.hit_rate(), Some(0.5));
+
+        // Coarser keys merge the nearby params.
+        let cache = Arc::new(Mutex::new(CostCache::new().with_bits(40).unwrap()));
+        let problem = CachingProblem::new(Counting::default(), Some(cache));
+        problem.cost(&a).unwrap();
+        problem.cost(&b).unwrap();
+        assert_eq!(problem.inner().0.load(Ordering::Relaxed), 1);
+        assert!(CostCache::new().with_bits(0).is_err());
+    }
+}
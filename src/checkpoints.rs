@@ -0,0 +1,173 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use argmin::core::{
+    checkpointing::{Checkpoint, CheckpointingFrequency, FileCheckpoint},
+    DeserializeOwnedAlias, SerializeAlias, State,
+};
+
+/// Extension `FileCheckpoint` gives its files.
+const EXTENSION: &str = "arg";
+
+/// File the checkpoints of `checkpoint` are written to.
+pub fn checkpoint_path(checkpoint: &FileCheckpoint) -> PathBuf {
+    checkpoint.directory.join(&checkpoint.filename)
+}
+
+/// Removes the file of `checkpoint` once its run succeeded, returning whether there was one.
+///
+/// A failed run keeps it to be resumed, while a stale one would resume a finished run: the next
+/// run would stop right away at the iteration it ended on.
+pub fn cleanup<T, E>(checkpoint: &FileCheckpoint, result: &Result<T, E>) -> io::Result<bool> {
+    let path = checkpoint_path(checkpoint);
+    if result.is_err() || !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(path)?;
+    Ok(true)
+}
+
+/// A checkpoint file found by [`list`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckpointFile {
+    pub path: PathBuf,
+    /// Iteration the run was saved at, `None` when the file holds another solver or state.
+    pub iteration: Option<u64>,
+    pub modified: SystemTime,
+}
+
+impl fmt::Display for CheckpointFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let iteration = match self.iteration {
+            Some(x) => format!("iteration {x}"),
+            None => "unknown iteration".to_string(),
+        };
+        let age = SystemTime::now()
+            .duration_since(self.modified)
+            .unwrap_or_default()
+            .as_secs();
+        let age = match age {
+            0..=59 => format!("{age}s"),
+            60..=3599 => format!("{}m", age / 60),
+            3600..=86399 => format!("{}h", age / 3600),
+            _ => format!("{}d", age / 86400),
+        };
+        write!(f, "{}: {iteration}, saved {age} ago", self.path.display())
+    }
+}
+
+/// Checkpoint files in `directory` sorted by name, empty when it doesn't exist. Their iteration
+/// is read assuming they were saved by a solver `S` with a state `I`.
+pub fn list<S, I>(directory: &Path) -> io::Result<Vec<CheckpointFile>>
+where
+    S: SerializeAlias + DeserializeOwnedAlias,
+    I: SerializeAlias + DeserializeOwnedAlias + State,
+{
+    if !directory.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|x| x != EXTENSION) {
+            continue;
+        }
+        let checkpoint = FileCheckpoint {
+            frequency: CheckpointingFrequency::Never,
+            directory: directory.to_path_buf(),
+            filename: path.file_name().unwrap_or_default().into(),
+        };
+        let iteration = Checkpoint::<S, I>::load(&checkpoint)
+            .ok()
+            .flatten()
+            .map(|(_, state)| state.get_iter());
+        files.push(CheckpointFile {
+            modified: fs::metadata(&path)?.modified()?,
+            path,
+            iteration,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use argmin::{
+        core::{ArgminError, CostFunction, Error, Executor, Gradient, IterState},
+        solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch},
+    };
+    use ndarray::{array, Array1};
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    type Solver = SteepestDescent<MoreThuenteLineSearch<Array1<f64>, Array1<f64>, f64>>;
+    type Iter = IterState<Array1<f64>, Array1<f64>, (), (), f64>;
+
+    /// Rosenbrock whose gradient fails after a few calls, in the middle of a run.
+    struct Failing(Cell<usize>);
+
+    impl CostFunction for Failing {
+        type Param = Array1<f64>;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            RosenbrockND::default().cost(param)
+        }
+    }
+
+    impl Gradient for Failing {
+        type Param = Array1<f64>;
+        type Gradient = Array1<f64>;
+
+        fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+            self.0.set(self.0.get() + 1);
+            if self.0.get() > 30 {
+                return Err(ArgminError::NotImplemented {
+                    text: "gradient".to_string(),
+                }
+                .into());
+            }
+            RosenbrockND::default().gradient(param)
+        }
+    }
+
+    #[test]
+    fn test_cleanup_only_on_success() {
+        let directory = std::env::temp_dir().join("argmin-exploring-test-checkpoints");
+        let _ = fs::remove_dir_all(&directory);
+        let checkpoint = FileCheckpoint::new(
+            directory.to_str().unwrap(),
+            "steepest",
+            CheckpointingFrequency::Always,
+        );
+        let path = checkpoint_path(&checkpoint);
+        let solver = || SteepestDescent::new(MoreThuenteLineSearch::new());
+        let result = Executor::new(RosenbrockND::default(), solver())
+            .configure(|state| state.param(array![-1.2, 1.0]).max_iters(3))
+            .checkpointing(checkpoint.clone())
+            .run();
+        let files = list::<Solver, Iter>(&directory).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!((&files[0].path, files[0].iteration), (&path, Some(3)));
+        assert!(cleanup(&checkpoint, &result).unwrap());
+        assert!(!path.exists());
+        assert!(list::<Solver, Iter>(&directory).unwrap().is_empty());
+
+        let result = Executor::new(Failing(Cell::new(0)), solver())
+            .configure(|state| state.param(array![-1.2, 1.0]).max_iters(100))
+            .checkpointing(checkpoint.clone())
+            .run();
+        assert!(result.is_err());
+        assert!(!cleanup(&checkpoint, &result).unwrap());
+        let files = list::<Solver, Iter>(&directory).unwrap();
+        assert!(files[0].iteration.unwrap() < 100, "{}", files[0]);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,165 @@
+use std::{collections::BTreeMap, fmt::Display, process, str::FromStr};
+
+use serde::Serialize;
+
+/// Minimal command line parser shared by the binaries.
+///
+/// Arguments starting with `--` are flags. Switches, the flags the binary lists as such, never
+/// take a value; the other flags take the following argument as their value unless it is another
+/// flag (`--name=value` is also accepted). Everything else is positional.
+///
+/// Invalid values are returned as errors, see [`exit_usage`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Args {
+    positional: Vec<String>,
+    flags: BTreeMap<String, Option<String>>,
+}
+
+impl Args {
+    pub fn from_env(switches: &[&str]) -> Self {
+        Self::parse(std::env::args().skip(1), switches)
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>, switches: &[&str]) -> Self {
+        let mut res = Self::default();
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(flag) => match flag.split_once('=') {
+                    Some((name, value)) => {
+                        res.flags.insert(name.to_string(), Some(value.to_string()));
+                    }
+                    None => {
+                        let value = match switches.contains(&flag) {
+                            true => None,
+                            false => args.next_if(|x| !x.starts_with("--")),
+                        };
+                        res.flags.insert(flag.to_string(), value);
+                    }
+                },
+                None => res.positional.push(arg),
+            }
+        }
+        res
+    }
+
+    /// Whether `--name` was given, with or without a value.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains_key(name)
+    }
+
+    /// Parsed value of `--name`, an error when it is missing or invalid.
+    pub fn value<T>(&self, name: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.flags.get(name) {
+            Some(Some(value)) => parse(value, name).map(Some),
+            Some(None) => Err(format!("Missing value for `--{name}`")),
+            None => Ok(None),
+        }
+    }
+
+    /// Like [`Self::value`] for a flag whose value can be left out, `Some(None)` when `--name` is
+    /// given alone.
+    pub fn optional_value<T>(&self, name: &str) -> Result<Option<Option<T>>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.flags.get(name) {
+            Some(Some(value)) => parse(value, name).map(|x| Some(Some(x))),
+            Some(None) => Ok(Some(None)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parsed value of the positional argument at `idx`, `name` is only used for error messages.
+    pub fn positional<T>(&self, idx: usize, name: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.positional.get(idx).map(|x| parse(x, name)).transpose()
+    }
+}
+
+fn parse<T>(x: &str, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    x.parse()
+        .map_err(|e| format!("Invalid value for `{name}` ({x}): {e}"))
+}
+
+/// Prints a command line error and exits with status 2, for the binaries:
+/// `args.value("dim").unwrap_or_else(exit_usage)`.
+pub fn exit_usage<T>(error: String) -> T {
+    eprintln!("Error: {error}");
+    process::exit(2)
+}
+
+/// Parses a comma separated list, e.g. `--init 1.0,2.0`.
+pub fn parse_list<T>(x: &str) -> Result<Vec<T>, T::Err>
+where
+    T: FromStr,
+{
+    x.split(',').map(|x| x.trim().parse()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(x: &str) -> Args {
+        Args::parse(x.split_whitespace().map(String::from), &["quiet", "sort"])
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = args("100 5 --format csv --quiet --max-dim=16 --init -1.2,1.0");
+        assert_eq!(args.positional::<u64>(0, "max_iters"), Ok(Some(100)));
+        assert_eq!(args.positional::<u64>(1, "log_every"), Ok(Some(5)));
+        assert_eq!(args.positional::<u64>(2, "other"), Ok(None));
+        assert_eq!(args.value::<String>("format"), Ok(Some("csv".to_string())));
+        assert_eq!(args.value::<usize>("max-dim"), Ok(Some(16)));
+        assert_eq!(
+            args.value::<String>("init"),
+            Ok(Some("-1.2,1.0".to_string()))
+        );
+        assert!(args.flag("quiet"));
+        assert_eq!(args.optional_value::<String>("quiet"), Ok(Some(None)));
+        assert_eq!(args.optional_value::<usize>("max-dim"), Ok(Some(Some(16))));
+        assert_eq!(args.optional_value::<String>("pareto"), Ok(None));
+        assert!(!args.flag("format-pretty"));
+        assert_eq!(parse_list::<f64>("-1.2, 1.0"), Ok(vec![-1.2, 1.0]));
+        assert_eq!(
+            serde_json::to_string(&args).unwrap(),
+            r#"{"positional":["100","5"],"flags":{"format":"csv","init":"-1.2,1.0","max-dim":"16","quiet":null}}"#
+        );
+    }
+
+    #[test]
+    fn test_switches_and_errors() {
+        // A switch leaves the following argument positional.
+        let switch = args("--sort 200 --quiet --format csv");
+        assert_eq!(switch.positional::<u64>(0, "max_iters"), Ok(Some(200)));
+        assert_eq!(
+            switch.value::<String>("format"),
+            Ok(Some("csv".to_string()))
+        );
+
+        let invalid = args("abc --max-dim abc --dim");
+        assert_eq!(
+            invalid.value::<usize>("max-dim"),
+            Err("Invalid value for `max-dim` (abc): invalid digit found in string".to_string())
+        );
+        assert_eq!(
+            invalid.value::<usize>("dim"),
+            Err("Missing value for `--dim`".to_string())
+        );
+        assert!(invalid.positional::<u64>(0, "max_iters").is_err());
+    }
+}
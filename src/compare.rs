@@ -0,0 +1,194 @@
+use tabled::Tabled;
+
+use crate::results::Result;
+
+/// How a method's outcome differs between two runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Same,
+    Changed,
+    OnlyBefore,
+    OnlyAfter,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Same => "same",
+            Status::Changed => "CHANGED",
+            Status::OnlyBefore => "only before",
+            Status::OnlyAfter => "only after",
+        }
+    }
+}
+
+/// Per method comparison of two result dumps.
+#[derive(Debug, Clone, Tabled)]
+#[tabled(rename_all = "Pascal")]
+pub struct Diff {
+    pub method: String,
+    #[tabled(display_with = "display_opt")]
+    pub cost_before: Option<f64>,
+    #[tabled(display_with = "display_opt")]
+    pub cost_after: Option<f64>,
+    #[tabled(display_with("display_iterations", args))]
+    pub iterations: (Option<u64>, Option<u64>),
+    /// L2 distance between the best params, when both runs recorded one.
+    #[tabled(display_with = "display_opt")]
+    pub param_distance: Option<f64>,
+    #[tabled(display_with("display_status", args))]
+    pub status: Status,
+}
+
+fn display_opt<T: ToString>(x: &Option<T>) -> String {
+    x.as_ref()
+        .map_or_else(|| "-".to_string(), |x| x.to_string())
+}
+
+fn display_iterations(diff: &Diff) -> String {
+    match diff.iterations {
+        (Some(x), Some(y)) if x == y => x.to_string(),
+        (x, y) => format!("{} -> {}", display_opt(&x), display_opt(&y)),
+    }
+}
+
+fn display_status(diff: &Diff) -> String {
+    diff.status.label().to_string()
+}
+
+/// Whether `x` and `y` differ by more than `tol`, relative to their magnitude when it is above 1.
+/// Two non-finite costs (both diverged or failed) are considered equal.
+fn differs(x: f64, y: f64, tol: f64) -> bool {
+    match (x.is_finite(), y.is_finite()) {
+        (true, true) => (x - y).abs() > tol * x.abs().max(y.abs()).max(1.0),
+        (false, false) => false,
+        _ => true,
+    }
+}
+
+fn distance(x: &[f64], y: &[f64]) -> Option<f64> {
+    (x.len() == y.len()).then(|| {
+        x.iter()
+            .zip(y)
+            .map(|(x, y)| (x - y).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    })
+}
+
+fn diff_pair(before: &Result, after: &Result, tol: f64) -> Diff {
+    let param_distance = match (&before.best_param, &after.best_param) {
+        (Some(x), Some(y)) => distance(x, y),
+        _ => None,
+    };
+    let changed = differs(before.best_cost, after.best_cost, tol)
+        || before.iterations != after.iterations
+        || before.error.is_some() != after.error.is_some()
+        || param_distance.is_some_and(|d| d > tol)
+        // Params of different dimension can't be compared, the runs are not equivalent.
+        || (before.best_param.is_some() && after.best_param.is_some() && param_distance.is_none());
+    Diff {
+        method: before.method.clone(),
+        cost_before: Some(before.best_cost),
+        cost_after: Some(after.best_cost),
+        iterations: (Some(before.iterations), Some(after.iterations)),
+        param_distance,
+        status: if changed {
+            Status::Changed
+        } else {
+            Status::Same
+        },
+    }
+}
+
+fn diff_single(result: &Result, status: Status) -> Diff {
+    let (cost_before, cost_after, iterations) = match status {
+        Status::OnlyBefore => (
+            Some(result.best_cost),
+            None,
+            (Some(result.iterations), None),
+        ),
+        _ => (
+            None,
+            Some(result.best_cost),
+            (None, Some(result.iterations)),
+        ),
+    };
+    Diff {
+        method: result.method.clone(),
+        cost_before,
+        cost_after,
+        iterations,
+        param_distance: None,
+        status,
+    }
+}
+
+/// Matches the rows of two runs by method name and compares them. Methods in `before` keep their
+/// order, methods only present in `after` are appended at the end.
+pub fn compare(before: &[Result], after: &[Result], tol: f64) -> Vec<Diff> {
+    let find = |rows: &[Result], method: &str| rows.iter().find(|x| x.method == method).cloned();
+    let mut diffs = before
+        .iter()
+        .map(|x| match find(after, &x.method) {
+            Some(y) => diff_pair(x, &y, tol),
+            None => diff_single(x, Status::OnlyBefore),
+        })
+        .collect::<Vec<_>>();
+    diffs.extend(
+        after
+            .iter()
+            .filter(|x| find(before, &x.method).is_none())
+            .map(|x| diff_single(x, Status::OnlyAfter)),
+    );
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(method: &str, best_cost: f64, iterations: u64, best_param: Vec<f64>) -> Result {
+        Result::new("", method, best_cost, None, iterations, None).with_best_param(Some(best_param))
+    }
+
+    #[test]
+    fn test_compare() {
+        let before = vec![
+            row("BFGS", 1e-10, 20, vec![1.0, 1.0]),
+            row("Newton", 0.5, 100, vec![0.5, 0.2]),
+            row("Landweber", f64::NAN, 100, vec![f64::NAN, f64::NAN]),
+            row("Removed", 1.0, 1, vec![0.0, 0.0]),
+        ];
+        let after = vec![
+            row("Added", 1.0, 1, vec![0.0, 0.0]),
+            row("Newton", 0.5, 100, vec![0.5, 0.3]),
+            row("BFGS", 1e-10 + 1e-14, 20, vec![1.0, 1.0]),
+            row("Landweber", f64::INFINITY, 100, vec![f64::NAN, f64::NAN]),
+        ];
+        let diffs = compare(&before, &after, 1e-8);
+        let statuses = diffs
+            .iter()
+            .map(|x| (x.method.as_str(), x.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                ("BFGS", Status::Same),
+                ("Newton", Status::Changed),
+                ("Landweber", Status::Same),
+                ("Removed", Status::OnlyBefore),
+                ("Added", Status::OnlyAfter),
+            ]
+        );
+        assert!((diffs[1].param_distance.unwrap() - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_differs() {
+        assert!(!differs(1e6, 1e6 + 1e-3, 1e-8));
+        assert!(differs(1.0, 1.0 + 1e-6, 1e-8));
+        assert!(!differs(f64::NAN, f64::INFINITY, 1e-8));
+        assert!(differs(f64::NAN, 1.0, 1e-8));
+    }
+}
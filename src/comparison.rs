@@ -0,0 +1,787 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use argmin::core::{DeserializeOwnedAlias, IterState, SerializeAlias, Solver, State};
+use argmin::solver::{
+    conjugategradient::{beta::PolakRibiere, NonlinearConjugateGradient},
+    gaussnewton::{GaussNewton, GaussNewtonLS},
+    gradientdescent::SteepestDescent,
+    landweber::Landweber,
+    linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, HagerZhangLineSearch,
+        MoreThuenteLineSearch,
+    },
+    neldermead::NelderMead,
+    newton::{Newton, NewtonCG},
+    quasinewton::{SR1TrustRegion, BFGS, DFP, LBFGS},
+    trustregion::{CauchyPoint, Dogleg, Steihaug, TrustRegion},
+};
+use ndarray::{array, Array1, Array2};
+
+#[cfg(feature = "linalg")]
+use crate::linalg_newton::NewtonLinalg;
+use crate::{
+    adapters::{ApproxGradient, CostTransform, MaybeApprox, Negate, Rescale, Transform},
+    annealing::AnnealingConfig,
+    bounds::{BoundedProblem, BoundsMode, ParameterBounds, Projected},
+    caching::{CachingProblem, CostCache},
+    levenberg_marquardt::LevenbergMarquardt,
+    noisy::{NoiseDist, NoisyProblem},
+    observers::GradientNorm,
+    particle_swarm::SeededParticleSwarm,
+    profile::{Profile, Profiled},
+    restart::RestartingSolver,
+    results,
+    runner::{child_seed, SolverRun, ToParamVec},
+    safeguarded_newton::SafeguardedNewton,
+    solvers::{de::DifferentialEvolution, momentum::Momentum},
+    step_length::StepLengths,
+    Method, Problem,
+};
+
+/// Problem as seen by the solvers, with optional noise on the cost and evaluations timed under
+/// `--profile`. The box is infinite unless `--project-bounds` or `--clamp-bounds` is set, the
+/// variables are only
+/// rescaled with `--rescale`, the costs only cached with `--cache` and the gradient only
+/// approximated with `--approx-grad`.
+pub type Objective = Profiled<
+    NoisyProblem<
+        CachingProblem<BoundedProblem<CostTransform<MaybeApprox<Negate<Rescale<Problem>>>>>>,
+    >,
+>;
+
+/// Size of the particle swarm.
+pub const PARTICLES: usize = 500;
+
+/// Size of the differential evolution population unless given with `--de-pop`.
+pub const DE_POPULATION: usize = 50;
+
+/// Seeds for the `index`-th method of the comparison: its problem, the cost noise, the solver
+/// and the jitter of its start each get an independent stream, so results don't depend on which
+/// other methods run or on `--parallel`.
+pub fn run_seeds(seed: Option<u64>, index: usize) -> Option<[u64; 4]> {
+    let seed = child_seed(seed?, index as u64);
+    Some([0, 1, 2, 3].map(|i| child_seed(seed, i)))
+}
+
+/// Problem handed to the `index`-th method.
+///
+/// # Panics
+///
+/// On an `approx_grad` step [`ApproxGradient::with_step`] rejects.
+#[allow(clippy::too_many_arguments)]
+pub fn objective(
+    problem: &Rescale<Problem>,
+    maximize: bool,
+    transform: Transform,
+    approx_grad: Option<f64>,
+    noise_stddev: Option<f64>,
+    noise_dist: NoiseDist,
+    bounds_mode: BoundsMode,
+    seed: Option<u64>,
+    index: usize,
+    profile: Option<Arc<Mutex<Profile>>>,
+    cache: Option<Arc<Mutex<CostCache>>>,
+) -> Objective {
+    let seeds = run_seeds(seed, index);
+    let problem = match seeds {
+        Some([problem_seed, ..]) => Rescale::new(
+            problem.inner().clone().with_seed(problem_seed),
+            problem.scale().clone(),
+        ),
+        None => problem.clone(),
+    };
+    let problem = match maximize {
+        true => Negate::new(problem),
+        false => Negate::identity(problem),
+    };
+    // Validated by the caller, see the panics above.
+    let problem = match approx_grad {
+        Some(step) => MaybeApprox::Approx(ApproxGradient::new(problem).with_step(step).unwrap()),
+        None => MaybeApprox::Exact(problem),
+    };
+    let problem = CostTransform::new(problem, transform);
+    let problem = BoundedProblem::with_mode(problem, bounds_mode);
+    let problem = CachingProblem::new(problem, cache);
+    let objective = NoisyProblem::new(problem, noise_stddev.unwrap_or(0.0)).with_dist(noise_dist);
+    let objective = match seeds {
+        Some([_, noise_seed, ..]) => objective.with_seed(noise_seed),
+        None => objective,
+    };
+    Profiled::new(objective, profile)
+}
+
+/// Nelder-Mead simplex: the historical fixed simplex in 2-D, otherwise `init` plus a unit step
+/// along every axis.
+fn simplex(init: &Array1<f64>) -> Vec<Array1<f64>> {
+    if init.len() == 2 {
+        return vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+    }
+    let mut simplex = vec![init.clone()];
+    for i in 0..init.len() {
+        let mut vertex = init.clone();
+        vertex[i] += 1.0;
+        simplex.push(vertex);
+    }
+    simplex
+}
+
+/// Memory of L-BFGS, the number of past updates it keeps, unless given with `--lbfgs-m`.
+pub const LBFGS_M: usize = 5;
+
+/// Iterations of `method` out of `eval_budget` evaluations, `max_iters` without a budget. The
+/// population methods spend one evaluation per member every iteration.
+pub fn iteration_cap(
+    method: Method,
+    max_iters: u64,
+    eval_budget: Option<u64>,
+    de_population: usize,
+) -> u64 {
+    match eval_budget {
+        Some(budget) => method.iterations_for_budget(
+            budget,
+            match method {
+                Method::DifferentialEvolution => de_population,
+                _ => PARTICLES,
+            },
+        ),
+        None => max_iters,
+    }
+}
+
+/// Backtracking line search with the Armijo condition, generic over the param type.
+pub fn backtracking<P, G>() -> BacktrackingLineSearch<P, G, ArmijoCondition<f64>, f64> {
+    BacktrackingLineSearch::new(ArmijoCondition::new(0.0001).unwrap())
+}
+
+/// Fills the step length column with the steps of the line search wrapped by `steps`, taking
+/// them after every run so repeated runs don't add up.
+fn with_step_lengths(run: SolverRun<Objective>, steps: StepLengths) -> SolverRun<Objective> {
+    run.map_result(move |result| result.with_step_length(steps.take_stats()))
+}
+
+/// With `--retry-linesearch`, reruns a row of `run` failing in its line search with the solver
+/// `fallback` builds around a backtracking line search recording into the given steps.
+fn with_linesearch_retry<S, I>(
+    run: SolverRun<Objective>,
+    retry: bool,
+    fallback: impl FnOnce(&StepLengths) -> Result<S, String>,
+    configure: impl Fn(I, Array1<f64>) -> I + Send + Sync + 'static,
+) -> Result<SolverRun<Objective>, String>
+where
+    S: Solver<Objective, I> + Clone + Send + Sync + 'static,
+    I: State<Float = f64> + GradientNorm + SerializeAlias + DeserializeOwnedAlias,
+    I::Param: ToParamVec + Debug,
+{
+    if !retry {
+        return Ok(run);
+    }
+    let steps = StepLengths::new();
+    let fallback = SolverRun::new(run.method, fallback(&steps)?, configure);
+    Ok(run.with_linesearch_fallback(
+        with_step_lengths(fallback, steps),
+        Method::Backtracking.label(),
+    ))
+}
+
+/// Restart settings of the non-linear conjugate gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgRestart {
+    /// Restart every given number of iterations.
+    pub iters: u64,
+    /// Restart when consecutive gradients are less orthogonal than this.
+    pub orthogonality: f64,
+}
+
+impl Default for CgRestart {
+    fn default() -> Self {
+        Self {
+            iters: 10,
+            orthogonality: 0.1,
+        }
+    }
+}
+
+impl CgRestart {
+    pub fn label(&self) -> String {
+        Method::NonlinearCg.label_with(&format!(
+            "restart {}, ortho {}",
+            self.iters, self.orthogonality
+        ))
+    }
+
+    /// Polak-Ribiere conjugate gradient around `linesearch` restarting as set.
+    pub fn solver<L>(
+        &self,
+        linesearch: L,
+    ) -> NonlinearConjugateGradient<Array1<f64>, L, PolakRibiere, f64> {
+        NonlinearConjugateGradient::new(linesearch, PolakRibiere::new())
+            .restart_iters(self.iters)
+            .restart_orthogonality(self.orthogonality)
+    }
+}
+
+/// Restarts of the local solvers from perturbed points with `--restarts`, none by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestartSettings {
+    /// Restarts at most, `0` turns them off.
+    pub restarts: u64,
+    /// Radius of the perturbation of every restart.
+    pub radius: f64,
+    /// Best cost below which the solver doesn't restart.
+    pub target: f64,
+}
+
+impl Default for RestartSettings {
+    fn default() -> Self {
+        Self {
+            restarts: 0,
+            radius: 1.0,
+            target: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl RestartSettings {
+    /// Wraps the solver of the `index`-th method, its restarts drawn from the solver seed of the
+    /// run.
+    fn wrap<S: Clone, H>(
+        &self,
+        solver: S,
+        seed: Option<u64>,
+        index: usize,
+    ) -> Result<RestartingSolver<S, H>, String> {
+        let solver = RestartingSolver::new(solver, self.restarts)
+            .with_radius(self.radius)
+            .map_err(|e| format!("Invalid `--restart-radius`: {e}"))?
+            .with_target(self.target);
+        Ok(match run_seeds(seed, index) {
+            Some([_, _, solver_seed, _]) => solver.with_seed(solver_seed),
+            None => solver,
+        })
+    }
+}
+
+/// Differential evolution settings exposed on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeSettings {
+    pub population: usize,
+    /// Scale `F` of the differences added to the mutants.
+    pub mutation: f64,
+    /// Probability `CR` of taking each coordinate from the mutant.
+    pub crossover: f64,
+}
+
+impl Default for DeSettings {
+    fn default() -> Self {
+        Self {
+            population: DE_POPULATION,
+            mutation: 0.8,
+            crossover: 0.9,
+        }
+    }
+}
+
+impl DeSettings {
+    pub fn label(&self) -> String {
+        Method::DifferentialEvolution.label_with(&format!(
+            "pop {}, F {}, CR {}",
+            self.population, self.mutation, self.crossover
+        ))
+    }
+}
+
+/// Momentum settings exposed on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MomentumSettings {
+    /// Step down the gradient, `--lr`.
+    pub learning_rate: f64,
+    /// Fraction of the velocity kept every iteration, `--momentum`.
+    pub beta: f64,
+}
+
+impl Default for MomentumSettings {
+    fn default() -> Self {
+        let momentum = Momentum::new();
+        Self {
+            learning_rate: momentum.learning_rate(),
+            beta: momentum.beta(),
+        }
+    }
+}
+
+impl MomentumSettings {
+    pub fn label(&self) -> String {
+        Method::Momentum.label_with(&format!("lr {}, beta {}", self.learning_rate, self.beta))
+    }
+}
+
+/// Every method of the comparison, in table order, each capped at `max_iters` of its method.
+/// `init` is in the rescaled variables of `problem`.
+///
+/// The local solvers are wrapped in `Projected`, a no-op unless `--project-bounds` or
+/// `--clamp-bounds` gives the objective a finite box. Nelder-Mead and simulated annealing keep
+/// their points in their own state, out of its reach: they only get the penalty of the box, see
+/// [`bounds_mode_for`]. Particle swarm already keeps its particles within the bounds. The local solvers restart as `restart` says, Nelder-Mead and the stochastic methods
+/// don't.
+#[allow(clippy::too_many_arguments)]
+pub fn solver_runs(
+    problem: &Rescale<Problem>,
+    init: &Array1<f64>,
+    max_iters: impl Fn(Method) -> u64,
+    annealing: &AnnealingConfig,
+    cg_restart: &CgRestart,
+    lbfgs_m: usize,
+    lm_lambda: Option<f64>,
+    de: &DeSettings,
+    momentum: &MomentumSettings,
+    restart: &RestartSettings,
+    retry_linesearch: bool,
+    seed: Option<u64>,
+) -> Result<Vec<SolverRun<Objective>>, String> {
+    let dim = init.len();
+    let mut runs = Vec::new();
+
+    // Linear search - Backtracking
+    let steps = StepLengths::new();
+    let backtracking_solver = SteepestDescent::new(steps.record(backtracking()));
+    let iterations = max_iters(Method::Backtracking);
+    runs.push(with_step_lengths(
+        SolverRun::new(
+            Method::Backtracking,
+            Projected::new(restart.wrap(backtracking_solver, seed, runs.len())?),
+            move |state, init| state.param(init).max_iters(iterations),
+        ),
+        steps,
+    ));
+
+    // Linear search - More-Thuente
+    let steps = StepLengths::new();
+    let morethuente = steps.record(MoreThuenteLineSearch::new());
+    let morethuente_solver = SteepestDescent::new(morethuente);
+    let iterations = max_iters(Method::MoreThuente);
+    let configure =
+        move |state: IterState<_, _, _, _, _>, init| state.param(init).max_iters(iterations);
+    let index = runs.len();
+    runs.push(with_linesearch_retry(
+        with_step_lengths(
+            SolverRun::new(
+                Method::MoreThuente,
+                Projected::new(restart.wrap(morethuente_solver, seed, index)?),
+                configure,
+            ),
+            steps,
+        ),
+        retry_linesearch,
+        |steps| {
+            let solver = SteepestDescent::new(steps.record(backtracking()));
+            Ok(Projected::new(restart.wrap(solver, seed, index)?))
+        },
+        configure,
+    )?);
+
+    // Linear search - Hager-Zhang
+    let steps = StepLengths::new();
+    let hagerzhang = steps.record(HagerZhangLineSearch::new());
+    let hagerzhang_solver = SteepestDescent::new(hagerzhang);
+    let iterations = max_iters(Method::HagerZhang);
+    let configure =
+        move |state: IterState<_, _, _, _, _>, init| state.param(init).max_iters(iterations);
+    let index = runs.len();
+    runs.push(with_linesearch_retry(
+        with_step_lengths(
+            SolverRun::new(
+                Method::HagerZhang,
+                Projected::new(restart.wrap(hagerzhang_solver, seed, index)?),
+                configure,
+            ),
+            steps,
+        ),
+        retry_linesearch,
+        |steps| {
+            let solver = SteepestDescent::new(steps.record(backtracking()));
+            Ok(Projected::new(restart.wrap(solver, seed, index)?))
+        },
+        configure,
+    )?);
+
+    // Trust Region - Cauchy Point
+    let cauchy_point = CauchyPoint::new();
+    let cauchy_point_solver = TrustRegion::new(cauchy_point);
+    let iterations = max_iters(Method::CauchyPoint);
+    runs.push(SolverRun::new(
+        Method::CauchyPoint,
+        Projected::new(restart.wrap(cauchy_point_solver, seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Trust Region - Dogleg
+    let dogleg = Dogleg::new();
+    let dogleg_solver = TrustRegion::new(dogleg);
+    let iterations = max_iters(Method::Dogleg);
+    runs.push(SolverRun::new(
+        Method::Dogleg,
+        Projected::new(restart.wrap(dogleg_solver, seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Trust Region - Steighaug
+    let steighaug = Steihaug::new();
+    let steighaug_solver = TrustRegion::new(steighaug);
+    let iterations = max_iters(Method::Steihaug);
+    runs.push(SolverRun::new(
+        Method::Steihaug,
+        Projected::new(restart.wrap(steighaug_solver, seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Conjugate Gradient - Non-linear Conjugate Gradient
+    let steps = StepLengths::new();
+    let linesearch = steps.record(MoreThuenteLineSearch::new());
+    let nlcg_solver = cg_restart.solver(linesearch);
+    let iterations = max_iters(Method::NonlinearCg);
+    let configure =
+        move |state: IterState<_, _, _, _, _>, init| state.param(init).max_iters(iterations);
+    let index = runs.len();
+    runs.push(
+        with_linesearch_retry(
+            with_step_lengths(
+                SolverRun::new(
+                    Method::NonlinearCg,
+                    Projected::new(restart.wrap(nlcg_solver, seed, index)?),
+                    configure,
+                ),
+                steps,
+            ),
+            retry_linesearch,
+            |steps| {
+                let solver = cg_restart.solver(steps.record(backtracking()));
+                Ok(Projected::new(restart.wrap(solver, seed, index)?))
+            },
+            configure,
+        )?
+        .with_label(cg_restart.label()),
+    );
+
+    // Newton - Newton's method, solving the step with LAPACK when built with `linalg`
+    let iterations = max_iters(Method::Newton);
+    #[cfg(not(feature = "linalg"))]
+    runs.push(SolverRun::new(
+        Method::Newton,
+        Projected::new(restart.wrap(Newton::new(), seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+    #[cfg(feature = "linalg")]
+    runs.push(
+        SolverRun::new(
+            Method::Newton,
+            Projected::new(restart.wrap(NewtonLinalg::new(), seed, runs.len())?),
+            move |state, init| state.param(init).max_iters(iterations),
+        )
+        .with_label(Method::Newton.label_with("LAPACK")),
+    );
+
+    // Newton - Newton-CG method
+    let steps = StepLengths::new();
+    let linesearch = steps.record(MoreThuenteLineSearch::new());
+    let newton_cg = NewtonCG::new(linesearch);
+    let iterations = max_iters(Method::NewtonCg);
+    let configure =
+        move |state: IterState<_, _, _, _, _>, init| state.param(init).max_iters(iterations);
+    let index = runs.len();
+    runs.push(with_linesearch_retry(
+        with_step_lengths(
+            SolverRun::new(
+                Method::NewtonCg,
+                Projected::new(restart.wrap(newton_cg, seed, index)?),
+                configure,
+            ),
+            steps,
+        ),
+        retry_linesearch,
+        |steps| {
+            let solver = NewtonCG::new(steps.record(backtracking()));
+            Ok(Projected::new(restart.wrap(solver, seed, index)?))
+        },
+        configure,
+    )?);
+
+    // Newton - Newton's method falling back to steepest descent
+    let iterations = max_iters(Method::SafeguardedNewton);
+    runs.push(SolverRun::new(
+        Method::SafeguardedNewton,
+        Projected::new(restart.wrap(SafeguardedNewton::new(), seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Quasi Newton - BFGS
+    let steps = StepLengths::new();
+    let linesearch = steps.record(MoreThuenteLineSearch::new());
+    let bfgs = BFGS::new(linesearch);
+    let iterations = max_iters(Method::Bfgs);
+    let configure = move |state: IterState<_, _, _, _, _>, init| {
+        state
+            .param(init)
+            // Hessian type required to initialize
+            .inv_hessian(Array2::eye(dim))
+            .max_iters(iterations)
+    };
+    let index = runs.len();
+    runs.push(with_linesearch_retry(
+        with_step_lengths(
+            SolverRun::new(
+                Method::Bfgs,
+                Projected::new(restart.wrap(bfgs, seed, index)?),
+                configure,
+            ),
+            steps,
+        ),
+        retry_linesearch,
+        |steps| {
+            let solver = BFGS::new(steps.record(backtracking()));
+            Ok(Projected::new(restart.wrap(solver, seed, index)?))
+        },
+        configure,
+    )?);
+
+    // Quasi Newton - DFP
+    let steps = StepLengths::new();
+    let linesearch = steps.record(MoreThuenteLineSearch::new());
+    let dfp = DFP::new(linesearch);
+    let iterations = max_iters(Method::Dfp);
+    let configure = move |state: IterState<_, _, _, _, _>, init| {
+        state
+            .param(init)
+            // Hessian type required to initialize
+            .inv_hessian(Array2::eye(dim))
+            .max_iters(iterations)
+    };
+    let index = runs.len();
+    runs.push(with_linesearch_retry(
+        with_step_lengths(
+            SolverRun::new(
+                Method::Dfp,
+                Projected::new(restart.wrap(dfp, seed, index)?),
+                configure,
+            ),
+            steps,
+        ),
+        retry_linesearch,
+        |steps| {
+            let solver = DFP::new(steps.record(backtracking()));
+            Ok(Projected::new(restart.wrap(solver, seed, index)?))
+        },
+        configure,
+    )?);
+
+    // Quasi Newton - L-BFGS
+    let steps = StepLengths::new();
+    let linesearch = steps.record(MoreThuenteLineSearch::new());
+    let lbfgs = LBFGS::new(linesearch, lbfgs_m);
+    let iterations = max_iters(Method::Lbfgs);
+    let configure =
+        move |state: IterState<_, _, _, _, _>, init| state.param(init).max_iters(iterations);
+    let index = runs.len();
+    let lbfgs = with_linesearch_retry(
+        with_step_lengths(
+            SolverRun::new(
+                Method::Lbfgs,
+                Projected::new(restart.wrap(lbfgs, seed, index)?),
+                configure,
+            ),
+            steps,
+        ),
+        retry_linesearch,
+        |steps| {
+            let solver = LBFGS::new(steps.record(backtracking()), lbfgs_m);
+            Ok(Projected::new(restart.wrap(solver, seed, index)?))
+        },
+        configure,
+    )?;
+    let lbfgs = match lbfgs_m {
+        LBFGS_M => lbfgs,
+        m => lbfgs.with_label(lbfgs_label(m)),
+    };
+    runs.push(lbfgs);
+
+    // Quasi Newton - SR1-Trust Region
+    let subproblem = Steihaug::new();
+    let sr1tr = SR1TrustRegion::new(subproblem);
+    let iterations = max_iters(Method::Sr1TrustRegion);
+    runs.push(SolverRun::new(
+        Method::Sr1TrustRegion,
+        Projected::new(restart.wrap(sr1tr, seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Least squares - Gauss-Newton and Levenberg-Marquardt, only for problems with a residual
+    // form. They report the residual norm as cost, squared back so it matches the other methods.
+    if problem.inner().residuals().is_some() {
+        let iterations = max_iters(Method::GaussNewton);
+        runs.push(
+            SolverRun::new(
+                Method::GaussNewton,
+                Projected::new(restart.wrap(GaussNewton::new(), seed, runs.len())?),
+                move |state, init| state.param(init).max_iters(iterations),
+            )
+            .map_result(squared_cost),
+        );
+
+        let linesearch = MoreThuenteLineSearch::new();
+        let gauss_newton_ls = GaussNewtonLS::new(linesearch);
+        let iterations = max_iters(Method::GaussNewtonLs);
+        let configure =
+            move |state: IterState<_, _, _, _, _>, init| state.param(init).max_iters(iterations);
+        let index = runs.len();
+        runs.push(
+            with_linesearch_retry(
+                SolverRun::new(
+                    Method::GaussNewtonLs,
+                    Projected::new(restart.wrap(gauss_newton_ls, seed, index)?),
+                    configure,
+                ),
+                retry_linesearch,
+                |_| {
+                    let solver = GaussNewtonLS::new(backtracking());
+                    Ok(Projected::new(restart.wrap(solver, seed, index)?))
+                },
+                configure,
+            )?
+            .map_result(squared_cost),
+        );
+
+        let levenberg_marquardt = match lm_lambda {
+            Some(lambda) => LevenbergMarquardt::new()
+                .with_lambda(lambda)
+                .map_err(|e| format!("Invalid `--lm-lambda`: {e}"))?,
+            None => LevenbergMarquardt::new(),
+        };
+        let label = Method::LevenbergMarquardt
+            .label_with(&format!("lambda {}", levenberg_marquardt.lambda()));
+        let iterations = max_iters(Method::LevenbergMarquardt);
+        runs.push(
+            SolverRun::new(
+                Method::LevenbergMarquardt,
+                Projected::new(restart.wrap(levenberg_marquardt, seed, runs.len())?),
+                move |state, init| state.param(init).max_iters(iterations),
+            )
+            .with_label(label)
+            .map_result(squared_cost),
+        );
+    }
+
+    // Landweber Iteration
+    let landweber = Landweber::new(0.001);
+    let iterations = max_iters(Method::Landweber);
+    runs.push(SolverRun::new(
+        Method::Landweber,
+        Projected::new(restart.wrap(landweber, seed, runs.len())?),
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Gradient descent with momentum
+    let momentum_solver = Momentum::new()
+        .with_learning_rate(momentum.learning_rate)
+        .map_err(|e| format!("Invalid `--lr`: {e}"))?
+        .with_beta(momentum.beta)
+        .map_err(|e| format!("Invalid `--momentum`: {e}"))?;
+    let iterations = max_iters(Method::Momentum);
+    runs.push(
+        SolverRun::new(
+            Method::Momentum,
+            Projected::new(restart.wrap(momentum_solver, seed, runs.len())?),
+            move |state, init| state.param(init).max_iters(iterations),
+        )
+        .with_label(momentum.label()),
+    );
+
+    // Nelder-Mead
+    let nelder_mead = NelderMead::new(simplex(init));
+    let iterations = max_iters(Method::NelderMead);
+    runs.push(SolverRun::new(
+        Method::NelderMead,
+        nelder_mead,
+        move |state, init| state.param(init).max_iters(iterations),
+    ));
+
+    // Simulated Annealing
+    let solver_seed = run_seeds(seed, runs.len()).map(|[_, _, x, _]| x);
+    let simulated_annealing = annealing
+        .build(solver_seed)
+        .map_err(|e| format!("Could not build the simulated annealing solver: {e}"))?;
+    let iterations = max_iters(Method::SimulatedAnnealing);
+    runs.push(
+        SolverRun::new(
+            Method::SimulatedAnnealing,
+            simulated_annealing,
+            move |state, init| state.param(init).max_iters(iterations),
+        )
+        .with_label(annealing.label()),
+    );
+
+    // Particle swarm optimization, seeded like simulated annealing.
+    let particle_swarm = SeededParticleSwarm::new((problem.lower(), problem.upper()), PARTICLES);
+    let particle_swarm = match run_seeds(seed, runs.len()) {
+        Some([_, _, solver_seed, _]) => particle_swarm.with_seed(solver_seed),
+        None => particle_swarm,
+    };
+    let iterations = max_iters(Method::ParticleSwarm);
+    runs.push(SolverRun::new(
+        Method::ParticleSwarm,
+        particle_swarm,
+        move |state, _| state.max_iters(iterations),
+    ));
+
+    // Differential evolution, seeded like particle swarm. The init joins its population.
+    let differential_evolution =
+        DifferentialEvolution::new((problem.lower(), problem.upper()), de.population)
+            .with_mutation(de.mutation)
+            .map_err(|e| format!("Invalid `--de-f`: {e}"))?
+            .with_crossover(de.crossover)
+            .map_err(|e| format!("Invalid `--de-cr`: {e}"))?;
+    let differential_evolution = match run_seeds(seed, runs.len()) {
+        Some([_, _, solver_seed, _]) => differential_evolution.with_seed(solver_seed),
+        None => differential_evolution,
+    };
+    let iterations = max_iters(Method::DifferentialEvolution);
+    runs.push(
+        SolverRun::new(
+            Method::DifferentialEvolution,
+            differential_evolution,
+            move |state, init| state.param(init).max_iters(iterations),
+        )
+        .with_label(de.label()),
+    );
+
+    Ok(runs)
+}
+
+/// Bounds of the objective of `method`: Nelder-Mead and simulated annealing aren't wrapped in
+/// `Projected`, which clamps the iterates with `--clamp-bounds`, so they get the penalty of
+/// `--project-bounds` instead.
+pub fn bounds_mode_for(method: Method, bounds_mode: BoundsMode) -> BoundsMode {
+    match (method, bounds_mode) {
+        (Method::NelderMead | Method::SimulatedAnnealing, BoundsMode::Clamp) => BoundsMode::Project,
+        (_, mode) => mode,
+    }
+}
+
+/// Gauss-Newton solvers report $\lVert r \rVert$, the other methods $\sum r_i^2$. Only the
+/// results are squared, `--csv-history` keeps the costs as reported by the solver.
+fn squared_cost(mut result: results::Result) -> results::Result {
+    result.best_cost = result.best_cost.powi(2);
+    if let Some(history) = &mut result.best_cost_history {
+        history.iter_mut().for_each(|x| *x = x.powi(2));
+    }
+    if let Some(trajectory) = &mut result.trajectory {
+        trajectory.costs.iter_mut().for_each(|x| *x = x.powi(2));
+    }
+    result
+}
+
+/// Label of L-BFGS keeping the last `m` updates.
+pub fn lbfgs_label(m: usize) -> String {
+    Method::Lbfgs.label_with(&format!("m = {m}"))
+}
@@ -0,0 +1,218 @@
+use std::{fmt, sync::Arc};
+
+use argmin::core::{ArgminError, CostFunction, Error, Gradient};
+use ndarray::Array1;
+
+type ValueFn = Arc<dyn Fn(&Array1<f64>) -> f64 + Send + Sync>;
+type GradientFn = Arc<dyn Fn(&Array1<f64>) -> Array1<f64> + Send + Sync>;
+
+/// Inequality constraint $g(x) \le 0$, with its gradient.
+#[derive(Clone)]
+pub struct Constraint {
+    name: String,
+    value: ValueFn,
+    gradient: GradientFn,
+}
+
+impl Constraint {
+    pub fn new(
+        name: impl ToString,
+        value: impl Fn(&Array1<f64>) -> f64 + Send + Sync + 'static,
+        gradient: impl Fn(&Array1<f64>) -> Array1<f64> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            value: Arc::new(value),
+            gradient: Arc::new(gradient),
+        }
+    }
+
+    /// Stay within the ball of the given center and radius:
+    /// $ \lVert x - c \rVert^2 - r^2 \le 0 $
+    pub fn ball(center: Array1<f64>, radius: f64) -> Self {
+        let gradient_center = center.clone();
+        Self::new(
+            format!("ball of radius {radius} around {center}"),
+            move |x| (x - &center).mapv(|v| v.powi(2)).sum() - radius.powi(2),
+            move |x| 2.0 * (x - &gradient_center),
+        )
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self, param: &Array1<f64>) -> f64 {
+        (self.value)(param)
+    }
+
+    pub fn gradient(&self, param: &Array1<f64>) -> Array1<f64> {
+        (self.gradient)(param)
+    }
+}
+
+impl fmt::Debug for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Constraint")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Turns a constrained problem into an unconstrained one argmin's solvers can minimize, adding
+/// the quadratic penalty $ \mu \sum_i \max(0, g_i(x))^2 $ of the inequality constraints to the
+/// cost (and its gradient).
+///
+/// The minimizer only satisfies the constraints as $\mu \to \infty$: a finite $\mu$ leaves it
+/// slightly outside an active constraint, and a large one makes the problem ill-conditioned.
+/// The usual remedy is to solve for an increasing sequence of $\mu$, each run starting from the
+/// previous solution.
+#[derive(Debug, Clone)]
+pub struct PenaltyProblem<P> {
+    inner: P,
+    constraints: Vec<Constraint>,
+    mu: f64,
+}
+
+impl<P> PenaltyProblem<P> {
+    /// Without any constraint, with $\mu = 10$.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            constraints: Vec::new(),
+            mu: 10.0,
+        }
+    }
+
+    pub fn with_constraint(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Weight of the penalty, must be positive.
+    pub fn with_mu(mut self, mu: f64) -> Result<Self, Error> {
+        if !(mu > 0.0 && mu.is_finite()) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`mu` must be positive, got {mu}"),
+            }
+            .into());
+        }
+        self.mu = mu;
+        Ok(self)
+    }
+
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Largest constraint violation $\max(0, \max_i g_i(x))$, zero for a feasible param.
+    pub fn violation(&self, param: &Array1<f64>) -> f64 {
+        self.constraints
+            .iter()
+            .map(|c| c.value(param))
+            .fold(0.0, f64::max)
+    }
+
+    fn penalty(&self, param: &Array1<f64>) -> f64 {
+        self.constraints
+            .iter()
+            .map(|c| c.value(param).max(0.0).powi(2))
+            .sum::<f64>()
+            * self.mu
+    }
+}
+
+impl<P> CostFunction for PenaltyProblem<P>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.inner.cost(param)? + self.penalty(param))
+    }
+}
+
+impl<P> Gradient for PenaltyProblem<P>
+where
+    P: Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+{
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let mut gradient = self.inner.gradient(param)?;
+        for constraint in &self.constraints {
+            let value = constraint.value(param);
+            if value > 0.0 {
+                gradient = gradient + 2.0 * self.mu * value * constraint.gradient(param);
+            }
+        }
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{Executor, State},
+        solver::{linesearch::MoreThuenteLineSearch, quasinewton::LBFGS},
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    fn constrained(mu: f64) -> PenaltyProblem<RosenbrockND> {
+        PenaltyProblem::new(RosenbrockND::default())
+            .with_constraint(Constraint::ball(array![0.0, 0.0], 1.0))
+            .with_mu(mu)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_penalty_pushes_towards_feasibility() {
+        // The unconstrained minimum (1, 1) lies outside the unit circle.
+        let violations = [1.0, 100.0, 10_000.0].map(|mu| {
+            let problem = constrained(mu);
+            let res = Executor::new(problem.clone(), LBFGS::new(MoreThuenteLineSearch::new(), 7))
+                .configure(|state| state.param(array![0.0, 0.0]).max_iters(200))
+                .run()
+                .unwrap();
+            problem.violation(res.state.get_best_param().unwrap())
+        });
+        assert!(violations[0] > 0.0);
+        assert!(violations.windows(2).all(|x| x[1] < x[0]), "{violations:?}");
+        assert!(violations[2] < 1e-3, "{violations:?}");
+        assert!(PenaltyProblem::new(RosenbrockND::default())
+            .with_mu(-1.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = constrained(50.0);
+        let param = array![0.9, 0.8];
+        assert!(f.violation(&param) > 0.0);
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-7;
+        for k in 0..param.len() {
+            let mut step = param.clone();
+            step[k] += h;
+            let diff = (f.cost(&step).unwrap() - f.cost(&param).unwrap()) / h;
+            assert!(
+                (diff - gradient[k]).abs() < 1e-3 * gradient[k].abs().max(1.0),
+                "{k}"
+            );
+        }
+    }
+}
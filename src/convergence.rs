@@ -0,0 +1,162 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::results::{format_float, DEFAULT_PRECISION};
+
+/// Successive error ratios averaged by [`ConvergenceRate::estimate`], the last ones.
+const TAIL: usize = 3;
+
+/// Empirical order of convergence of a run, see [`ConvergenceRate::estimate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateClass {
+    Linear,
+    Superlinear,
+    Quadratic,
+}
+
+impl fmt::Display for RateClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RateClass::Linear => "linear",
+            RateClass::Superlinear => "superlinear",
+            RateClass::Quadratic => "quadratic",
+        })
+    }
+}
+
+/// How fast the errors $e_k = f_k - f^*$ of the best costs to the optimal cost shrink near the
+/// end of a run: their order $q$ in $e_{k+1} \approx C e_k^q$ and the last ratio
+/// $e_{k+1} / e_k$.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConvergenceRate {
+    pub class: RateClass,
+    /// `None` when only one ratio is known, e.g. a solver reaching the optimum in a single step.
+    pub order: Option<f64>,
+    pub ratio: f64,
+}
+
+impl ConvergenceRate {
+    /// Rate of the best `costs` of a run (the first being the cost at the init) towards
+    /// `optimum`, `None` with fewer than two improvements.
+    ///
+    /// Errors stop at the first one within rounding of the optimum, which counts as reaching
+    /// it: a solver getting there in one step is superlinear. The order averages
+    /// $\log(e_{k+1} / e_k) / \log(e_k / e_{k-1})$ over the last [`TAIL`] steps, up to 1.25
+    /// is linear and from 1.75 quadratic.
+    pub fn estimate(costs: &[f64], optimum: f64) -> Option<Self> {
+        let floor = 4.0 * f64::EPSILON * optimum.abs().max(1.0);
+        let mut errors: Vec<f64> = Vec::new();
+        for &cost in costs.iter().filter(|x| x.is_finite()) {
+            let error = (cost - optimum).max(floor);
+            // Only the iterations improving the best cost.
+            if errors.last().is_some_and(|&x| error >= x) {
+                continue;
+            }
+            errors.push(error);
+            if error == floor {
+                break;
+            }
+        }
+        if errors.len() < 2 {
+            return None;
+        }
+        let ratios = errors.windows(2).map(|x| x[1] / x[0]).collect::<Vec<_>>();
+        let ratio = *ratios.last().unwrap();
+        let orders = ratios
+            .windows(2)
+            .map(|x| x[1].ln() / x[0].ln())
+            .collect::<Vec<_>>();
+        let tail = &orders[orders.len().saturating_sub(TAIL)..];
+        let order = (!tail.is_empty()).then(|| tail.iter().sum::<f64>() / tail.len() as f64);
+        let class = match order {
+            Some(q) if q >= 1.75 => RateClass::Quadratic,
+            Some(q) if q > 1.25 => RateClass::Superlinear,
+            Some(_) => RateClass::Linear,
+            // A single step straight to the optimum.
+            None if errors[1] == floor => RateClass::Superlinear,
+            None => return None,
+        };
+        Some(Self {
+            class,
+            order,
+            ratio,
+        })
+    }
+}
+
+/// The precision, e.g. `{:.3}`, gives the significant digits of the ratio or order.
+impl fmt::Display for ConvergenceRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(DEFAULT_PRECISION);
+        match (self.class, self.order) {
+            (RateClass::Linear, _) => write!(f, "linear ({})", format_float(self.ratio, digits)),
+            (class, Some(order)) => write!(f, "{class} (q {})", format_float(order, digits)),
+            (class, None) => write!(f, "{class}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{CostFunction, IterState},
+        solver::{
+            gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch, newton::Newton,
+        },
+    };
+    use ndarray::{array, Array1, Array2};
+
+    use super::*;
+    use crate::{run_solver, Ellipsoid, EllipsoidKind, RunOptions, TestFnProblem};
+
+    #[test]
+    fn test_newton_beats_steepest_descent() {
+        let options = RunOptions {
+            quiet: true,
+            trajectory: true,
+            ..Default::default()
+        };
+        // Costs at the init and every iterate, Newton doesn't evaluate them itself.
+        fn costs<P>(problem: &P, init: &Array1<f64>, result: crate::results::Result) -> Vec<f64>
+        where
+            P: CostFunction<Param = Array1<f64>, Output = f64>,
+        {
+            let trajectory = result.trajectory.unwrap();
+            std::iter::once(init.clone())
+                .chain(trajectory.params.into_iter().map(Array1::from))
+                .map(|x| problem.cost(&x).unwrap())
+                .collect()
+        }
+
+        let booth = TestFnProblem::booth()
+            .with_gradient(|x| {
+                vec![
+                    10.0 * x[0] + 8.0 * x[1] - 34.0,
+                    8.0 * x[0] + 10.0 * x[1] - 38.0,
+                ]
+            })
+            .with_hessian(|_| vec![10.0, 8.0, 8.0, 10.0]);
+        let init = array![-4.0, 7.5];
+        let configure =
+            |state: IterState<_, _, _, Array2<f64>, _>| state.param(init.clone()).max_iters(5);
+        let newton = run_solver("", "Newton", booth, Newton::new(), configure, &options);
+        let rate = ConvergenceRate::estimate(&costs(&booth, &init, newton), 0.0).unwrap();
+        assert_ne!(rate.class, RateClass::Linear, "{rate}");
+
+        let ellipsoid = Ellipsoid::new(EllipsoidKind::Ellipsoid, 2).with_condition(10.0);
+        let init = array![1.0, 1.0];
+        let configure = |state: IterState<_, _, _, _, _>| state.param(init.clone()).max_iters(30);
+        let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+        let steepest = run_solver("", "Steepest", ellipsoid, solver, configure, &options);
+        let rate = ConvergenceRate::estimate(&costs(&ellipsoid, &init, steepest), 0.0).unwrap();
+        assert_eq!(rate.class, RateClass::Linear, "{rate}");
+        assert!(rate.ratio > 0.01 && rate.ratio < 1.0, "{rate}");
+
+        // Quadratic errors, then too few improvements.
+        let quadratic = ConvergenceRate::estimate(&[1e-1, 1e-2, 1e-4, 1e-8, 1e-8], 0.0).unwrap();
+        assert_eq!(quadratic.class, RateClass::Quadratic);
+        assert_eq!(ConvergenceRate::estimate(&[1.0, 1.0, 2.0], 0.0), None);
+    }
+}
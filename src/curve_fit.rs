@@ -0,0 +1,173 @@
+use std::{fmt, str::FromStr};
+
+use argmin::core::{Error, Jacobian, Operator};
+use ndarray::{Array1, Array2};
+
+/// Model fitted by [`CurveFit`], with two parameters `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Model {
+    /// $ y = p_0 + p_1 x $
+    Linear,
+    /// $ y = p_0 e^{p_1 x} $
+    #[default]
+    Exponential,
+}
+
+impl Model {
+    pub fn eval(&self, p: &Array1<f64>, x: f64) -> f64 {
+        match self {
+            Model::Linear => p[0] + p[1] * x,
+            Model::Exponential => p[0] * (p[1] * x).exp(),
+        }
+    }
+
+    /// Derivatives of the model with respect to `p`.
+    fn derivatives(&self, p: &Array1<f64>, x: f64) -> [f64; 2] {
+        match self {
+            Model::Linear => [1.0, x],
+            Model::Exponential => {
+                let e = (p[1] * x).exp();
+                [e, p[0] * x * e]
+            }
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "linear" => Ok(Model::Linear),
+            "exponential" => Ok(Model::Exponential),
+            _ => Err(format!(
+                "unknown model `{s}`, expected linear or exponential"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Model::Linear => "linear",
+            Model::Exponential => "exponential",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Least-squares fit of a [`Model`] to `(x, y)` points, for Gauss-Newton style solvers. The
+/// residuals are $ r_i = f(x_i; p) - y_i $.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveFit {
+    model: Model,
+    points: Vec<(f64, f64)>,
+}
+
+impl CurveFit {
+    pub fn new(model: Model, points: Vec<(f64, f64)>) -> Self {
+        Self { model, points }
+    }
+
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+}
+
+impl Operator for CurveFit {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self
+            .points
+            .iter()
+            .map(|&(x, y)| self.model.eval(param, x) - y)
+            .collect())
+    }
+}
+
+impl Jacobian for CurveFit {
+    type Param = Array1<f64>;
+    type Jacobian = Array2<f64>;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        let mut jacobian = Array2::zeros((self.points.len(), 2));
+        for (i, &(x, _)) in self.points.iter().enumerate() {
+            let [d0, d1] = self.model.derivatives(param, x);
+            jacobian[[i, 0]] = d0;
+            jacobian[[i, 1]] = d1;
+        }
+        Ok(jacobian)
+    }
+}
+
+/// Parses `x,y` lines. Blank lines and `#` comments are ignored, as is a first line that is not
+/// numeric (a header such as `x,y`). Errors point to the offending line.
+pub fn parse_points(input: &str) -> Result<Vec<(f64, f64)>, String> {
+    let mut points = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let point = match line.split(',').collect::<Vec<_>>()[..] {
+            [x, y] => x
+                .trim()
+                .parse::<f64>()
+                .and_then(|x| Ok((x, y.trim().parse()?))),
+            _ => return Err(format!("line {}: expected `x,y`, got `{line}`", i + 1)),
+        };
+        match point {
+            Ok(point) => points.push(point),
+            Err(_) if points.is_empty() && i == 0 => {}
+            Err(e) => return Err(format!("line {}: {e} in `{line}`", i + 1)),
+        }
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{Executor, State},
+        solver::{gaussnewton::GaussNewtonLS, linesearch::MoreThuenteLineSearch},
+    };
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_points() {
+        let input = "x,y\n0, 1.5\n\n# comment\n1,2e1\n";
+        assert_eq!(parse_points(input), Ok(vec![(0.0, 1.5), (1.0, 20.0)]));
+        assert_eq!(parse_points(""), Ok(vec![]));
+        let err = parse_points("0,1\n1,abc\n").unwrap_err();
+        assert!(err.starts_with("line 2: "), "{err}");
+        let err = parse_points("0,1,2\n").unwrap_err();
+        assert!(err.starts_with("line 1: expected `x,y`"), "{err}");
+    }
+
+    #[test]
+    fn test_fit_exponential() {
+        let truth = array![2.0, 0.3];
+        let points = (0..20)
+            .map(|i| {
+                let x = i as f64 * 0.5;
+                (x, Model::Exponential.eval(&truth, x))
+            })
+            .collect();
+        let problem = CurveFit::new(Model::Exponential, points);
+        let res = Executor::new(problem, GaussNewtonLS::new(MoreThuenteLineSearch::new()))
+            .configure(|state| state.param(array![1.0, 0.1]).max_iters(50))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert!((param - &truth).iter().all(|x| x.abs() < 1e-8), "{param}");
+    }
+}
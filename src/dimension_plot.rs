@@ -0,0 +1,272 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    pareto::{escape, Scale},
+    results::{format_float, Result, RunStatus, DEFAULT_PRECISION},
+};
+
+/// What `--dimension-sweep-plot` draws against the dimension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SweepMetric {
+    /// Iterations to converge, a run that doesn't converge ends the line of its method.
+    #[default]
+    Iterations,
+    /// Best cost at the end of the fixed iteration budget, only failed runs end the line.
+    BestCost,
+}
+
+impl FromStr for SweepMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "iters" => Ok(SweepMetric::Iterations),
+            "cost" => Ok(SweepMetric::BestCost),
+            _ => Err(format!("unknown metric `{s}`, expected iters or cost")),
+        }
+    }
+}
+
+impl fmt::Display for SweepMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SweepMetric::Iterations => "iterations to converge",
+            SweepMetric::BestCost => "best cost",
+        })
+    }
+}
+
+/// The line of one method across the dimensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionSeries {
+    pub method: String,
+    /// `(dim, value)` up to the first failure, by increasing dimension.
+    pub points: Vec<(usize, f64)>,
+    /// Smallest dimension where the method failed, with its status. Larger ones are left out.
+    pub failure: Option<(usize, RunStatus)>,
+}
+
+/// Iterations to converge, or best cost, against the dimension with one line per method, to see
+/// how each method copes as the problem grows. Rendered as an SVG chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionPlot {
+    pub metric: SweepMetric,
+    pub series: Vec<DimensionSeries>,
+}
+
+impl DimensionPlot {
+    /// `rows` are the `(dim, result)` of the sweep in any order, grouped into lines by method in
+    /// order of first appearance.
+    pub fn new<'a>(
+        rows: impl IntoIterator<Item = (usize, &'a Result)>,
+        metric: SweepMetric,
+    ) -> Self {
+        let mut rows = rows.into_iter().collect::<Vec<_>>();
+        rows.sort_by_key(|(dim, _)| *dim);
+        let mut series: Vec<DimensionSeries> = Vec::new();
+        for (dim, result) in rows {
+            let index = match series.iter().position(|x| x.method == result.method) {
+                Some(index) => index,
+                None => {
+                    series.push(DimensionSeries {
+                        method: result.method.clone(),
+                        points: Vec::new(),
+                        failure: None,
+                    });
+                    series.len() - 1
+                }
+            };
+            let line = &mut series[index];
+            if line.failure.is_some() {
+                continue;
+            }
+            let failed = match metric {
+                SweepMetric::Iterations => result.status != RunStatus::Converged,
+                SweepMetric::BestCost => result.is_failure(),
+            };
+            match (failed, metric) {
+                (true, _) => line.failure = Some((dim, result.status)),
+                (false, SweepMetric::Iterations) => {
+                    line.points.push((dim, result.iterations as f64))
+                }
+                (false, SweepMetric::BestCost) => line.points.push((dim, result.best_cost)),
+            }
+        }
+        Self { metric, series }
+    }
+
+    /// Standalone SVG document: dimensions on a log scale, one colored line per method with a
+    /// cross past its last point where it failed, and a legend.
+    pub fn render_svg(&self, title: &str) -> String {
+        const WIDTH: f64 = 760.0;
+        const HEIGHT: f64 = 480.0;
+        const COLORS: [&str; 8] = [
+            "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#17becf",
+        ];
+        let (left, right, top, bottom) = (90.0, 200.0, 40.0, 60.0);
+        let mut dims = self
+            .series
+            .iter()
+            .flat_map(|x| {
+                x.points
+                    .iter()
+                    .map(|(dim, _)| *dim)
+                    .chain(x.failure.map(|x| x.0))
+            })
+            .collect::<Vec<_>>();
+        dims.sort_unstable();
+        dims.dedup();
+        let values = self
+            .series
+            .iter()
+            .flat_map(|x| x.points.iter().map(|x| x.1));
+        let log_value = values.clone().all(|x| x >= 0.0);
+        let dim_scale = Scale::new(dims.iter().map(|&x| x as f64), true);
+        let value_scale = Scale::new(values, log_value);
+        let x = |dim: usize| left + dim_scale.fraction(dim as f64) * (WIDTH - left - right);
+        let y = |v: f64| HEIGHT - bottom - value_scale.fraction(v) * (HEIGHT - top - bottom);
+
+        let mut svg = String::new();
+        let mut line = |s: String| {
+            svg.push_str(&s);
+            svg.push('\n');
+        };
+        line(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="11">"#
+        ));
+        line(format!(
+            r#"<rect width="{WIDTH}" height="{HEIGHT}" fill="white"/>"#
+        ));
+        line(format!(
+            r#"<text x="{}" y="22" text-anchor="middle" font-size="14">{}</text>"#,
+            WIDTH / 2.0,
+            escape(title)
+        ));
+        let (x0, x1, y0, y1) = (left, WIDTH - right, top, HEIGHT - bottom);
+        line(format!(
+            r#"<path d="M{x0},{y0} V{y1} H{x1}" fill="none" stroke="black"/>"#
+        ));
+        for &dim in &dims {
+            line(format!(
+                r#"<text x="{}" y="{}" text-anchor="middle">{dim}</text>"#,
+                x(dim),
+                y1 + 16.0
+            ));
+        }
+        for v in value_scale.ticks() {
+            line(format!(
+                r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                x0 - 6.0,
+                y(v) + 4.0,
+                format_float(v, DEFAULT_PRECISION)
+            ));
+        }
+        line(format!(
+            r#"<text x="{}" y="{}" text-anchor="middle">dimension (log scale)</text>"#,
+            (x0 + x1) / 2.0,
+            HEIGHT - 20.0
+        ));
+        line(format!(
+            r#"<text x="20" y="{}" text-anchor="middle" transform="rotate(-90 20 {})">{}{}</text>"#,
+            (y0 + y1) / 2.0,
+            (y0 + y1) / 2.0,
+            self.metric,
+            if value_scale.log { " (log scale)" } else { "" }
+        ));
+
+        for (i, series) in self.series.iter().enumerate() {
+            let color = COLORS[i % COLORS.len()];
+            let method = escape(&series.method);
+            let path = series
+                .points
+                .iter()
+                .enumerate()
+                .map(|(i, &(dim, v))| {
+                    format!("{}{},{}", if i == 0 { 'M' } else { 'L' }, x(dim), y(v))
+                })
+                .collect::<Vec<_>>();
+            if !path.is_empty() {
+                line(format!(
+                    r#"<path d="{}" fill="none" stroke="{color}" stroke-width="2"/>"#,
+                    path.join(" ")
+                ));
+            }
+            for &(dim, v) in &series.points {
+                line(format!(
+                    r#"<circle cx="{}" cy="{}" r="3" fill="{color}"><title>{method}, dim {dim}: {}</title></circle>"#,
+                    x(dim),
+                    y(v),
+                    format_float(v, DEFAULT_PRECISION)
+                ));
+            }
+            // At the height of the last point, or at the top without any.
+            if let Some((dim, status)) = series.failure {
+                let (cx, cy) = (x(dim), series.points.last().map_or(y0, |&(_, v)| y(v)));
+                line(format!(
+                    r#"<path d="M{},{} l8,8 m0,-8 l-8,8" stroke="{color}" stroke-width="2"><title>{method}, dim {dim}: {status}</title></path>"#,
+                    cx - 4.0,
+                    cy - 4.0
+                ));
+            }
+            let legend = match series.failure {
+                Some((dim, status)) => format!("{method} ({status} at {dim})"),
+                None => method,
+            };
+            let ly = y0 + 16.0 * i as f64;
+            line(format!(
+                r#"<path d="M{},{ly} h16" stroke="{color}" stroke-width="2"/>"#,
+                x1 + 14.0
+            ));
+            line(format!(
+                r#"<text x="{}" y="{}">{legend}</text>"#,
+                x1 + 36.0,
+                ly + 4.0
+            ));
+        }
+        line("</svg>".to_string());
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::TerminationReason;
+
+    use super::*;
+
+    fn row(method: &str, iters: u64, reason: TerminationReason) -> Result {
+        Result::new("", method, 1e-9, None, iters, Some(&reason))
+    }
+
+    #[test]
+    fn test_lines_stop_at_failures() {
+        let converged = TerminationReason::SolverConverged;
+        let rows = [
+            (4, row("BFGS", 40, converged.clone())),
+            (2, row("BFGS", 30, converged.clone())),
+            (2, row("Newton", 10, converged.clone())),
+            (4, row("Newton", 100, TerminationReason::MaxItersReached)),
+            (8, row("Newton", 20, converged)),
+        ];
+        let plot = DimensionPlot::new(
+            rows.iter().map(|(dim, x)| (*dim, x)),
+            SweepMetric::Iterations,
+        );
+        assert_eq!(plot.series[0].points, [(2, 30.0), (4, 40.0)]);
+        assert_eq!(plot.series[0].failure, None);
+        assert_eq!(plot.series[1].points, [(2, 10.0)]);
+        assert_eq!(plot.series[1].failure, Some((4, RunStatus::MaxIters)));
+        // A fixed budget running out isn't a failure.
+        let plot = DimensionPlot::new(rows.iter().map(|(dim, x)| (*dim, x)), SweepMetric::BestCost);
+        assert_eq!(plot.series[1].points.len(), 3);
+
+        let svg = DimensionPlot::new(
+            rows.iter().map(|(dim, x)| (*dim, x)),
+            SweepMetric::Iterations,
+        )
+        .render_svg("rosenbrock");
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains("Newton (max_iters at 4)"), "{svg}");
+    }
+}
@@ -0,0 +1,141 @@
+use argmin::core::{CostFunction, Gradient, Hessian};
+use ndarray::{Array1, Array2};
+
+/// Shape of an [`Ellipsoid`], where its `condition` goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EllipsoidKind {
+    /// Weights growing geometrically from 1 to `condition` along the axes.
+    Ellipsoid,
+    /// One easy axis, every other one `condition` times steeper.
+    Cigar,
+    /// One axis `condition` times steeper than every other one.
+    Discus,
+}
+
+/// Separable quadratic functions with a tunable condition number, as in the CMA-ES benchmarks:
+/// $ f(x) = \sum_{i=1}^{n} w_i x_i^2 $
+///
+/// The weights $w_i$ depend on the [`EllipsoidKind`], their ratio being the condition number
+/// of the hessian. The global minimum is $f = 0$ at the origin, usually evaluated on
+/// $[-5, 5]^n$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    kind: EllipsoidKind,
+    dim: usize,
+    condition: f64,
+}
+
+impl Ellipsoid {
+    /// With the usual condition number of `1e6`.
+    pub fn new(kind: EllipsoidKind, dim: usize) -> Self {
+        Self {
+            kind,
+            dim,
+            condition: 1e6,
+        }
+    }
+
+    /// Same function with a different condition number.
+    pub fn with_condition(mut self, condition: f64) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    pub fn kind(&self) -> EllipsoidKind {
+        self.kind
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn condition(&self) -> f64 {
+        self.condition
+    }
+
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (
+            Array1::from_elem(self.dim, -5.0),
+            Array1::from_elem(self.dim, 5.0),
+        )
+    }
+
+    /// Weights $w_i$ of the `n` coordinates of a param.
+    fn weights(&self, n: usize) -> Array1<f64> {
+        Array1::from_shape_fn(n, |i| match self.kind {
+            EllipsoidKind::Ellipsoid if n > 1 => self.condition.powf(i as f64 / (n - 1) as f64),
+            EllipsoidKind::Ellipsoid => 1.0,
+            EllipsoidKind::Cigar if i > 0 => self.condition,
+            EllipsoidKind::Discus if i == 0 => self.condition,
+            EllipsoidKind::Cigar | EllipsoidKind::Discus => 1.0,
+        })
+    }
+}
+
+impl CostFunction for Ellipsoid {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        Ok((self.weights(param.len()) * param * param).sum())
+    }
+}
+
+impl Gradient for Ellipsoid {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        Ok(2.0 * self.weights(param.len()) * param)
+    }
+}
+
+impl Hessian for Ellipsoid {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, argmin::core::Error> {
+        Ok(Array2::from_diag(&(2.0 * self.weights(param.len()))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [EllipsoidKind; 3] = [
+        EllipsoidKind::Ellipsoid,
+        EllipsoidKind::Cigar,
+        EllipsoidKind::Discus,
+    ];
+
+    #[test]
+    fn test_origin() {
+        for kind in KINDS {
+            let f = Ellipsoid::new(kind, 5);
+            let origin = Array1::zeros(5);
+            assert_eq!(f.cost(&origin).unwrap(), 0.0);
+            assert_eq!(f.gradient(&origin).unwrap(), origin);
+            assert!(f.cost(&Array1::ones(5)).unwrap() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_condition() {
+        for kind in KINDS {
+            for dim in [2, 10] {
+                let f = Ellipsoid::new(kind, dim).with_condition(1e4);
+                let hessian = f.hessian(&Array1::ones(dim)).unwrap();
+                let diag = hessian.diag();
+                let max = diag.fold(f64::MIN, |a, &b| a.max(b));
+                let min = diag.fold(f64::MAX, |a, &b| a.min(b));
+                assert!(
+                    (max / min - 1e4).abs() < 1e-6,
+                    "{kind:?} {dim}: {}",
+                    max / min
+                );
+                assert_eq!(hessian.sum(), diag.sum());
+            }
+        }
+    }
+}
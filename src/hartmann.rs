@@ -0,0 +1,160 @@
+use argmin::core::{ArgminError, CostFunction, Error, Gradient};
+use ndarray::Array1;
+
+const ALPHA: [f64; 4] = [1.0, 1.2, 3.0, 3.2];
+
+const A3: [[f64; 3]; 4] = [
+    [3.0, 10.0, 30.0],
+    [0.1, 10.0, 35.0],
+    [3.0, 10.0, 30.0],
+    [0.1, 10.0, 35.0],
+];
+
+/// Scaled by `1e-4`.
+const P3: [[f64; 3]; 4] = [
+    [3689.0, 1170.0, 2673.0],
+    [4699.0, 4387.0, 7470.0],
+    [1091.0, 8732.0, 5547.0],
+    [381.0, 5743.0, 8828.0],
+];
+
+const A6: [[f64; 6]; 4] = [
+    [10.0, 3.0, 17.0, 3.5, 1.7, 8.0],
+    [0.05, 10.0, 17.0, 0.1, 8.0, 14.0],
+    [3.0, 3.5, 1.7, 10.0, 17.0, 8.0],
+    [17.0, 8.0, 0.05, 10.0, 0.1, 14.0],
+];
+
+/// Scaled by `1e-4`.
+const P6: [[f64; 6]; 4] = [
+    [1312.0, 1696.0, 5569.0, 124.0, 8283.0, 5886.0],
+    [2329.0, 4135.0, 8307.0, 3736.0, 1004.0, 9991.0],
+    [2348.0, 1451.0, 3522.0, 2883.0, 3047.0, 6650.0],
+    [4047.0, 8828.0, 8732.0, 5743.0, 1091.0, 381.0],
+];
+
+/// Rows of `A` and `P` of every term.
+type Coefficients = Vec<(&'static [f64], &'static [f64])>;
+
+/// The Hartmann functions in 3 and 6 dimensions, the coefficients being chosen by the length of
+/// the param:
+/// $ f(x) = -\sum_{i=1}^{4} \alpha_i \exp\left(-\sum_j A_{ij} (x_j - P_{ij})^2\right) $
+///
+/// Evaluated on $[0, 1]^d$, they are multimodal with a global minimum of $-3.86278$ in 3-D and
+/// $-3.32237$ in 6-D, only known numerically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hartmann {
+    dim: usize,
+}
+
+impl Hartmann {
+    /// `None` unless `dim` is 3 or 6.
+    pub fn new(dim: usize) -> Option<Self> {
+        matches!(dim, 3 | 6).then_some(Self { dim })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (Array1::zeros(self.dim), Array1::ones(self.dim))
+    }
+
+    /// Coefficients for the length of `param`.
+    fn coefficients(param: &Array1<f64>) -> Result<Coefficients, Error> {
+        match param.len() {
+            3 => Ok(A3.iter().zip(&P3).map(|(a, p)| (&a[..], &p[..])).collect()),
+            6 => Ok(A6.iter().zip(&P6).map(|(a, p)| (&a[..], &p[..])).collect()),
+            n => Err(ArgminError::InvalidParameter {
+                text: format!("Hartmann is only defined in 3 and 6 dimensions, got {n}"),
+            }
+            .into()),
+        }
+    }
+
+    /// $ \alpha_i \exp(\dots) $ for every term $i$.
+    fn terms(param: &Array1<f64>) -> Result<Vec<f64>, Error> {
+        let terms = ALPHA
+            .iter()
+            .zip(Self::coefficients(param)?)
+            .map(|(alpha, (a, p))| {
+                let exponent = param
+                    .iter()
+                    .zip(a.iter().zip(p))
+                    .map(|(x, (a, p))| a * (x - p * 1e-4).powi(2))
+                    .sum::<f64>();
+                alpha * (-exponent).exp()
+            })
+            .collect();
+        Ok(terms)
+    }
+}
+
+impl CostFunction for Hartmann {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(-Self::terms(param)?.iter().sum::<f64>())
+    }
+}
+
+impl Gradient for Hartmann {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let terms = Self::terms(param)?;
+        let coefficients = Self::coefficients(param)?;
+        let gradient = param
+            .iter()
+            .enumerate()
+            .map(|(j, x)| {
+                terms
+                    .iter()
+                    .zip(&coefficients)
+                    .map(|(t, (a, p))| 2.0 * t * a[j] * (x - p[j] * 1e-4))
+                    .sum()
+            })
+            .collect();
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_minima() {
+        let f = Hartmann::new(3).unwrap();
+        let x = array![0.114614, 0.555649, 0.852547];
+        assert!((f.cost(&x).unwrap() + 3.86278).abs() < 1e-5);
+        assert!(f.gradient(&x).unwrap().iter().all(|g| g.abs() < 1e-3));
+
+        let f = Hartmann::new(6).unwrap();
+        let x = array![0.20169, 0.150011, 0.476874, 0.275332, 0.311652, 0.6573];
+        assert!((f.cost(&x).unwrap() + 3.32237).abs() < 1e-5);
+        assert!(f.gradient(&x).unwrap().iter().all(|g| g.abs() < 1e-3));
+
+        assert!(Hartmann::new(4).is_none());
+        assert!(f.cost(&array![0.5, 0.5]).is_err());
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = Hartmann::new(6).unwrap();
+        let param = array![0.1, 0.9, 0.4, 0.3, 0.7, 0.5];
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-7;
+        for k in 0..param.len() {
+            let mut step = param.clone();
+            step[k] += h;
+            let diff = (f.cost(&step).unwrap() - f.cost(&param).unwrap()) / h;
+            assert!((diff - gradient[k]).abs() < 1e-5, "{k}");
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{fmt, str::FromStr};
+
+use ndarray::Array1;
+use rand::{distributions::Uniform, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use crate::noisy::standard_normal;
+
+/// Standard starting points, so start-point sensitivity can be studied without typing vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InitStrategy {
+    /// Rosenbrock's traditional `(-1.2, 1.0)`, repeated along every pair of dimensions.
+    #[default]
+    Classic,
+    /// All zeros.
+    Origin,
+    /// The upper corner of the bounds.
+    Corner,
+    /// Uniformly drawn within the bounds.
+    Random,
+}
+
+impl InitStrategy {
+    /// Initial param within `lower` and `upper`, which also give its dimension. Only `Random`
+    /// uses `seed`, drawing from entropy without it.
+    pub fn init_param(
+        &self,
+        lower: &Array1<f64>,
+        upper: &Array1<f64>,
+        seed: Option<u64>,
+    ) -> Array1<f64> {
+        let dim = lower.len();
+        match self {
+            InitStrategy::Classic => {
+                Array1::from_shape_fn(dim, |i| if i % 2 == 0 { -1.2 } else { 1.0 })
+            }
+            InitStrategy::Origin => Array1::zeros(dim),
+            InitStrategy::Corner => upper.clone(),
+            InitStrategy::Random => {
+                let mut rng = match seed {
+                    Some(seed) => Xoshiro256PlusPlus::seed_from_u64(seed),
+                    None => Xoshiro256PlusPlus::from_entropy(),
+                };
+                lower
+                    .iter()
+                    .zip(upper)
+                    .map(|(&l, &u)| rng.sample(Uniform::new_inclusive(l, u)))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// `init` moved by independent gaussian noise of standard deviation `sigma` along each axis,
+/// drawn from entropy without `seed`. A `sigma` of 0 returns `init` untouched, without drawing.
+pub fn jitter(init: &Array1<f64>, sigma: f64, seed: Option<u64>) -> Array1<f64> {
+    if sigma == 0.0 {
+        return init.clone();
+    }
+    let mut rng = match seed {
+        Some(seed) => Xoshiro256PlusPlus::seed_from_u64(seed),
+        None => Xoshiro256PlusPlus::from_entropy(),
+    };
+    init.mapv(|x| x + sigma * standard_normal(&mut rng))
+}
+
+impl FromStr for InitStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => Ok(InitStrategy::Classic),
+            "origin" => Ok(InitStrategy::Origin),
+            "corner" => Ok(InitStrategy::Corner),
+            "random" => Ok(InitStrategy::Random),
+            _ => Err(format!(
+                "unknown init strategy `{s}`, expected classic, origin, corner or random"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for InitStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InitStrategy::Classic => "classic",
+            InitStrategy::Origin => "origin",
+            InitStrategy::Corner => "corner",
+            InitStrategy::Random => "random",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::{minimize, Ellipsoid, EllipsoidKind, Method};
+
+    #[test]
+    fn test_init_param() {
+        let (lower, upper) = (array![-5.0, -5.0, 0.0], array![5.0, 5.0, 1.0]);
+        let init = |x: InitStrategy| x.init_param(&lower, &upper, Some(42));
+        assert_eq!(init(InitStrategy::Classic), array![-1.2, 1.0, -1.2]);
+        assert_eq!(init(InitStrategy::Origin), array![0.0, 0.0, 0.0]);
+        assert_eq!(init(InitStrategy::Corner), upper);
+        let random = init(InitStrategy::Random);
+        assert_eq!(random, init(InitStrategy::Random));
+        assert!(random
+            .iter()
+            .zip(lower.iter().zip(&upper))
+            .all(|(x, (l, u))| l <= x && x <= u));
+        for x in ["classic", "origin", "corner", "random"] {
+            assert_eq!(x.parse::<InitStrategy>().unwrap().to_string(), x);
+        }
+    }
+
+    #[test]
+    fn test_zero_jitter_reproduces_the_run() {
+        let init = array![-1.2, 1.0, 0.5];
+        let run = |init| {
+            let problem = Ellipsoid::new(EllipsoidKind::Ellipsoid, 3);
+            minimize(problem, Method::Bfgs, init, 50).unwrap()
+        };
+        let (plain, jittered) = (run(init.clone()), run(jitter(&init, 0.0, Some(7))));
+        assert_eq!(jitter(&init, 0.0, None), init);
+        assert_eq!(plain.best_cost.to_bits(), jittered.best_cost.to_bits());
+        assert_eq!(plain.best_param, jittered.best_param);
+        assert_eq!(plain.iters, jittered.iters);
+
+        let moved = jitter(&init, 0.1, Some(7));
+        assert_eq!(moved, jitter(&init, 0.1, Some(7)));
+        assert_ne!(moved, jitter(&init, 0.1, Some(8)));
+        assert!((&moved - &init).iter().all(|x| x.abs() < 1.0 && *x != 0.0));
+    }
+}
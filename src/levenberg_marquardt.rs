@@ -0,0 +1,169 @@
+use argmin::{
+    core::{
+        ArgminError, Error, IterState, Jacobian, Operator, Problem, Solver, State,
+        TerminationReason, TerminationStatus, KV,
+    },
+    kv,
+};
+use argmin_math::ArgminInv;
+use ndarray::{Array1, Array2};
+
+/// Factor applied to the damping after a rejected step, its inverse after an accepted one.
+const DAMPING_FACTOR: f64 = 10.0;
+
+/// Levenberg-Marquardt method for non-linear least squares, missing from argmin.
+///
+/// Every iteration tries the damped Gauss-Newton step
+/// $ \delta = -(J^T J + \lambda I)^{-1} J^T r $, keeping it only when it lowers the residuals.
+/// The damping $\lambda$ then shrinks towards plain Gauss-Newton, otherwise it grows towards
+/// small steepest descent steps.
+///
+/// Like argmin's Gauss-Newton solvers, the cost is the residual norm $\lVert r \rVert$.
+#[derive(Debug, Clone)]
+pub struct LevenbergMarquardt {
+    lambda: f64,
+    tol: f64,
+    /// Cost change of the last trial step.
+    last_change: f64,
+}
+
+impl LevenbergMarquardt {
+    pub fn new() -> Self {
+        Self {
+            lambda: 1e-3,
+            tol: f64::EPSILON.sqrt(),
+            last_change: f64::INFINITY,
+        }
+    }
+
+    /// Initial damping, must be positive. Defaults to `1e-3`.
+    pub fn with_lambda(mut self, lambda: f64) -> Result<Self, Error> {
+        if !(lambda > 0.0 && lambda.is_finite()) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("Levenberg-Marquardt: lambda must be positive, got {lambda}"),
+            }
+            .into());
+        }
+        self.lambda = lambda;
+        Ok(self)
+    }
+
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Stops once a trial step changes the cost by less than `tol`. Defaults to `sqrt(EPSILON)`.
+    pub fn with_tolerance(mut self, tol: f64) -> Result<Self, Error> {
+        if tol <= 0.0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "Levenberg-Marquardt: tol must be positive".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl Default for LevenbergMarquardt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> Solver<O, IterState<Array1<f64>, (), Array2<f64>, (), f64>> for LevenbergMarquardt
+where
+    O: Operator<Param = Array1<f64>, Output = Array1<f64>>
+        + Jacobian<Param = Array1<f64>, Jacobian = Array2<f64>>,
+{
+    const NAME: &'static str = "Levenberg-Marquardt";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Array1<f64>, (), Array2<f64>, (), f64>,
+    ) -> Result<(IterState<Array1<f64>, (), Array2<f64>, (), f64>, Option<KV>), Error> {
+        let param = state
+            .get_param()
+            .ok_or_else(|| ArgminError::NotInitialized {
+                text: "`LevenbergMarquardt` requires an initial parameter vector".to_string(),
+            })?
+            .clone();
+        let residuals = problem.apply(&param)?;
+        let jacobian = problem.jacobian(&param)?;
+        let mut damped = jacobian.t().dot(&jacobian);
+        damped.diag_mut().mapv_inplace(|x| x + self.lambda);
+        let step = damped.inv()?.dot(&jacobian.t().dot(&residuals));
+        let trial = &param - &step;
+
+        let cost = residuals.dot(&residuals).sqrt();
+        let trial_residuals = problem.apply(&trial)?;
+        let trial_cost = trial_residuals.dot(&trial_residuals).sqrt();
+        self.last_change = (cost - trial_cost).abs();
+        let accepted = trial_cost < cost;
+        let kv = kv!("lambda" => self.lambda; "accepted" => accepted;);
+        let state = if accepted {
+            self.lambda /= DAMPING_FACTOR;
+            state.param(trial).cost(trial_cost)
+        } else {
+            self.lambda *= DAMPING_FACTOR;
+            state.param(param).cost(cost)
+        };
+        Ok((state, Some(kv)))
+    }
+
+    fn terminate(
+        &mut self,
+        _state: &IterState<Array1<f64>, (), Array2<f64>, (), f64>,
+    ) -> TerminationStatus {
+        if self.last_change < self.tol {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{Executor, State},
+        solver::gaussnewton::GaussNewton,
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::RosenbrockResiduals;
+
+    #[test]
+    fn test_lambda() {
+        assert!(LevenbergMarquardt::new().with_lambda(1.0).is_ok());
+        assert!(LevenbergMarquardt::new().with_lambda(0.0).is_err());
+        assert!(LevenbergMarquardt::new().with_lambda(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_rosenbrock_residuals() {
+        let init = array![-1.2, 1.0];
+        let res = Executor::new(RosenbrockResiduals::default(), LevenbergMarquardt::new())
+            .configure(|state| state.param(init.clone()).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert!(
+            (param - &array![1.0, 1.0]).iter().all(|x| x.abs() < 1e-6),
+            "{param}"
+        );
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+
+        // Plain Gauss-Newton reaches the same point, LM takes a few more cautious steps.
+        let gauss_newton = Executor::new(RosenbrockResiduals::default(), GaussNewton::new())
+            .configure(|state| state.param(init).max_iters(100))
+            .run()
+            .unwrap();
+        assert!(gauss_newton.state.get_best_cost() < 1e-6);
+        assert!(res.state.get_iter() >= gauss_newton.state.get_iter());
+    }
+}
@@ -1,5 +1,129 @@
+pub mod adapters;
+pub mod annealing;
+pub mod bench;
+pub mod bohachevsky;
+pub mod bounds;
+pub mod bounds_report;
+pub mod caching;
+pub mod checkpoints;
+pub mod cli;
+pub mod compare;
+pub mod comparison;
+pub mod constrained;
+pub mod convergence;
+pub mod curve_fit;
+pub mod dimension_plot;
+pub mod ellipsoid;
+pub mod hartmann;
+pub mod init;
+pub mod levenberg_marquardt;
+#[cfg(feature = "linalg")]
+pub mod linalg_newton;
+#[cfg(feature = "log")]
+pub mod log_observer;
+pub mod methods;
+pub mod metrics;
+pub mod michalewicz;
+pub mod minimize;
+pub mod multiobjective;
+pub mod noisy;
+pub mod observers;
+pub mod optimum;
+pub mod pareto;
+pub mod particle_swarm;
+pub mod perm;
+pub mod problems;
+pub mod profile;
+pub mod qing;
+pub mod quadratic;
+pub mod repro;
+pub mod restart;
+pub mod results;
+mod rosenbrock;
 pub mod rosenbrock_ndarray;
+pub mod rosenbrock_residuals;
 pub mod rosenbrock_vec;
+pub mod run_config;
+pub mod runner;
+pub mod safeguarded_newton;
+pub mod salomon;
+pub mod shekel;
+pub mod snapshot;
+pub mod solvers;
+pub mod step_length;
+pub mod surface;
+pub mod termination;
+pub mod testfn;
+pub mod trajectory;
+pub mod whitley;
 
-pub use rosenbrock_ndarray::RosenbrockND;
+pub use bohachevsky::Bohachevsky;
+pub use constrained::{Constraint, PenaltyProblem};
+pub use ellipsoid::{Ellipsoid, EllipsoidKind};
+pub use hartmann::Hartmann;
+pub use levenberg_marquardt::LevenbergMarquardt;
+pub use methods::Method;
+pub use michalewicz::Michalewicz;
+pub use minimize::{minimize, minimize_derivative_free, minimize_without_hessian, MinimizeOutcome};
+pub use multiobjective::WeightedSum;
+pub use perm::Perm;
+pub use problems::Problem;
+pub use qing::Qing;
+pub use quadratic::Quadratic;
+pub use rosenbrock_ndarray::{RosenbrockND, RosenbrockNDBuilder};
+pub use rosenbrock_residuals::RosenbrockResiduals;
 pub use rosenbrock_vec::RosenbrockVec;
+pub use run_config::run_comparison;
+pub use runner::{run_solver, RunOptions};
+pub use salomon::Salomon;
+pub use shekel::Shekel;
+pub use testfn::TestFnProblem;
+pub use whitley::Whitley;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        adapters::{CostTransform, MaybeApprox, Negate, Rescale},
+        bounds::BoundedProblem,
+        caching::CachingProblem,
+        curve_fit::CurveFit,
+        noisy::NoisyProblem,
+        profile::Profiled,
+    };
+
+    fn _assert_send_sync<T: Clone + Send + Sync>() {}
+
+    /// `--parallel` shares the problem across threads, a field that isn't thread safe must fail
+    /// to compile here rather than at the call site.
+    #[test]
+    fn test_problems_send_sync() {
+        _assert_send_sync::<RosenbrockND>();
+        _assert_send_sync::<RosenbrockVec>();
+        _assert_send_sync::<RosenbrockResiduals>();
+        _assert_send_sync::<Bohachevsky>();
+        _assert_send_sync::<Michalewicz>();
+        _assert_send_sync::<Perm>();
+        _assert_send_sync::<Hartmann>();
+        _assert_send_sync::<Ellipsoid>();
+        _assert_send_sync::<Shekel>();
+        _assert_send_sync::<Whitley>();
+        _assert_send_sync::<Qing>();
+        _assert_send_sync::<Salomon>();
+        _assert_send_sync::<Quadratic>();
+        _assert_send_sync::<CurveFit>();
+        _assert_send_sync::<TestFnProblem>();
+        _assert_send_sync::<PenaltyProblem<RosenbrockND>>();
+        _assert_send_sync::<WeightedSum<Quadratic, Quadratic>>();
+        _assert_send_sync::<Problem>();
+        // Every wrapper used by the comparison.
+        type Objective = Profiled<
+            NoisyProblem<
+                CachingProblem<
+                    BoundedProblem<CostTransform<MaybeApprox<Negate<Rescale<Problem>>>>>,
+                >,
+            >,
+        >;
+        _assert_send_sync::<Objective>();
+    }
+}
@@ -0,0 +1,109 @@
+use argmin::core::{ArgminError, Error, Gradient, Hessian, IterState, Problem, Solver, State, KV};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{Solve, SolveC};
+
+type NewtonState = IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, f64>;
+
+/// argmin's `Newton` with the step solved by LAPACK (needs the `linalg` feature), instead of
+/// inverting the hessian.
+///
+/// Solves $ H d = \nabla f $ by a Cholesky factorization, falling back to an LU one when the
+/// hessian isn't positive definite. Like argmin's, it takes the full step scaled by `gamma`,
+/// without evaluating the cost.
+#[derive(Debug, Clone, Copy)]
+pub struct NewtonLinalg {
+    gamma: f64,
+}
+
+impl NewtonLinalg {
+    pub fn new() -> Self {
+        Self { gamma: 1.0 }
+    }
+
+    /// Step size, in `(0, 1]`.
+    pub fn with_gamma(mut self, gamma: f64) -> Result<Self, Error> {
+        if !(gamma > 0.0 && gamma <= 1.0) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`gamma` must be in (0, 1], got {gamma}"),
+            }
+            .into());
+        }
+        self.gamma = gamma;
+        Ok(self)
+    }
+}
+
+impl Default for NewtonLinalg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> Solver<O, NewtonState> for NewtonLinalg
+where
+    O: Gradient<Param = Array1<f64>, Gradient = Array1<f64>>
+        + Hessian<Param = Array1<f64>, Hessian = Array2<f64>>,
+{
+    const NAME: &'static str = "Newton method (LAPACK)";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: NewtonState,
+    ) -> Result<(NewtonState, Option<KV>), Error> {
+        let param = state
+            .get_param()
+            .ok_or_else(|| ArgminError::NotInitialized {
+                text: "`NewtonLinalg` requires an initial parameter vector".to_string(),
+            })?
+            .clone();
+        let gradient = problem.gradient(&param)?;
+        let hessian = problem.hessian(&param)?;
+        let direction = match hessian.solvec(&gradient) {
+            Ok(direction) => direction,
+            Err(_) => hessian.solve(&gradient)?,
+        };
+        let param = param - self.gamma * direction;
+        Ok((state.param(param).gradient(gradient).hessian(hessian), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{core::Executor, solver::newton::Newton};
+    use ndarray::array;
+
+    use super::*;
+    use crate::testfn::TestFnProblem;
+
+    #[test]
+    fn test_matches_newton_on_booth() {
+        let booth = TestFnProblem::booth()
+            .with_gradient(|x| {
+                vec![
+                    10.0 * x[0] + 8.0 * x[1] - 34.0,
+                    8.0 * x[0] + 10.0 * x[1] - 38.0,
+                ]
+            })
+            .with_hessian(|_| vec![10.0, 8.0, 8.0, 10.0]);
+        let init = array![-4.0, 7.5];
+        let lapack = Executor::new(booth, NewtonLinalg::new())
+            .configure(|state| state.param(init.clone()).max_iters(5))
+            .run()
+            .unwrap();
+        let newton = Executor::new(booth, Newton::<f64>::new())
+            .configure(|state| state.param(init).max_iters(5))
+            .run()
+            .unwrap();
+        let lapack = lapack.state.get_best_param().unwrap();
+        let newton = newton.state.get_best_param().unwrap();
+        assert!(
+            (lapack - newton).iter().all(|x| x.abs() < 1e-10),
+            "{lapack}"
+        );
+        assert!(
+            (lapack - array![1.0, 3.0]).iter().all(|x| x.abs() < 1e-10),
+            "{lapack}"
+        );
+    }
+}
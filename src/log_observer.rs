@@ -0,0 +1,137 @@
+use std::fmt::Write;
+
+use argmin::core::{observers::Observe, Error, State, KV};
+use log::Level;
+
+/// argmin's `SlogLogger` through the `log` facade (needs the `log` feature), so the state goes
+/// wherever the host application sends its logs, filtered by its level, e.g. `RUST_LOG` with
+/// `env_logger`.
+///
+/// Records have the `argmin` target and one line per event: the solver and its settings on
+/// init, then the iteration, costs and evaluation counts.
+#[derive(Debug, Clone, Copy)]
+pub struct LogObserver {
+    level: Level,
+}
+
+impl LogObserver {
+    /// Logs at `Level::Info`, like `SlogLogger`.
+    pub fn new() -> Self {
+        Self { level: Level::Info }
+    }
+
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+}
+
+impl Default for LogObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `, key: value` for every entry of `kv`, sorted by key so the lines are stable.
+fn format_kv(kv: &KV) -> String {
+    let mut entries = kv.kv.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|(key, _)| **key);
+    entries
+        .iter()
+        .fold(String::new(), |mut line, (key, value)| {
+            write!(line, ", {key}: {value}").unwrap();
+            line
+        })
+}
+
+impl<I: State> Observe<I> for LogObserver {
+    fn observe_init(&mut self, name: &str, kv: &KV) -> Result<(), Error> {
+        log::log!(target: "argmin", self.level, "{name}{}", format_kv(kv));
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        let mut counts = state.get_func_counts().iter().collect::<Vec<_>>();
+        counts.sort_unstable();
+        let counts = counts.iter().fold(String::new(), |mut line, (key, count)| {
+            write!(line, ", {key}: {count}").unwrap();
+            line
+        });
+        log::log!(
+            target: "argmin",
+            self.level,
+            "iter: {}, cost: {}, best_cost: {}{counts}{}",
+            state.get_iter(),
+            state.get_cost(),
+            state.get_best_cost(),
+            format_kv(kv)
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use argmin::{
+        core::{observers::ObserverMode, Executor},
+        solver::neldermead::NelderMead,
+    };
+    use log::{LevelFilter, Log, Metadata, Record};
+    use ndarray::array;
+
+    use super::*;
+    use crate::TestFnProblem;
+
+    /// Keeps the level and message of every record.
+    struct CapturingLogger(Mutex<Vec<(Level, String)>>);
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            if record.target() == "argmin" {
+                let message = record.args().to_string();
+                self.0.lock().unwrap().push((record.level(), message));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_iterations_logged() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+        let simplex = vec![array![3.0, -4.0], array![4.0, -4.0], array![3.0, -3.0]];
+        Executor::new(TestFnProblem::sphere(), NelderMead::new(simplex))
+            .configure(|state| state.max_iters(10))
+            .add_observer(
+                LogObserver::new().with_level(Level::Debug),
+                ObserverMode::Always,
+            )
+            .run()
+            .unwrap();
+        let records = LOGGER.0.lock().unwrap();
+        // The init record, then one per iteration.
+        assert_eq!(records.len(), 11);
+        assert!(records.iter().all(|(level, _)| *level == Level::Debug));
+        assert!(records[0].1.starts_with("Nelder-Mead"), "{}", records[0].1);
+        for (i, (_, message)) in records[1..].iter().enumerate() {
+            assert!(
+                message.starts_with(&format!("iter: {i}, cost: ")),
+                "{message}"
+            );
+            assert!(message.contains("cost_count: "), "{message}");
+        }
+    }
+}
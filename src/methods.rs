@@ -0,0 +1,345 @@
+use std::{fmt, str::FromStr};
+
+/// Every method compared by the binaries.
+///
+/// `Display` gives the label used in the result rows and `FromStr` parses either that label or
+/// the command line key (see [`Method::key`]), so both always come from the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Backtracking,
+    MoreThuente,
+    HagerZhang,
+    CauchyPoint,
+    Dogleg,
+    Steihaug,
+    NonlinearCg,
+    Newton,
+    NewtonCg,
+    SafeguardedNewton,
+    Bfgs,
+    Dfp,
+    Lbfgs,
+    Sr1TrustRegion,
+    GaussNewton,
+    GaussNewtonLs,
+    LevenbergMarquardt,
+    Landweber,
+    Momentum,
+    NelderMead,
+    SimulatedAnnealing,
+    ParticleSwarm,
+    DifferentialEvolution,
+}
+
+impl Method {
+    pub const ALL: [Method; 23] = [
+        Method::Backtracking,
+        Method::MoreThuente,
+        Method::HagerZhang,
+        Method::CauchyPoint,
+        Method::Dogleg,
+        Method::Steihaug,
+        Method::NonlinearCg,
+        Method::Newton,
+        Method::NewtonCg,
+        Method::SafeguardedNewton,
+        Method::Bfgs,
+        Method::Dfp,
+        Method::Lbfgs,
+        Method::Sr1TrustRegion,
+        Method::GaussNewton,
+        Method::GaussNewtonLs,
+        Method::LevenbergMarquardt,
+        Method::Landweber,
+        Method::Momentum,
+        Method::NelderMead,
+        Method::SimulatedAnnealing,
+        Method::ParticleSwarm,
+        Method::DifferentialEvolution,
+    ];
+
+    /// Human readable name, as shown in the result rows.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Method::Backtracking => "Backtracking",
+            Method::MoreThuente => "More-Thuente",
+            Method::HagerZhang => "Hager-Zhang",
+            Method::CauchyPoint => "Cauchy-Point",
+            Method::Dogleg => "Dogleg",
+            // Misspelled since the first tables, kept so older dumps still compare.
+            Method::Steihaug => "Steighaug",
+            Method::NonlinearCg => "Non-linear CG",
+            Method::Newton => "Newton",
+            Method::NewtonCg => "Newton-CG",
+            Method::SafeguardedNewton => "Safeguarded Newton",
+            Method::Bfgs => "BFGS",
+            Method::Dfp => "DFP",
+            Method::Lbfgs => "L-BFGS",
+            Method::Sr1TrustRegion => "SR1-TrustRegion",
+            Method::GaussNewton => "Gauss-Newton",
+            Method::GaussNewtonLs => "Gauss-Newton-LS",
+            Method::LevenbergMarquardt => "Levenberg-Marquardt",
+            Method::Landweber => "Landweber Iteration",
+            Method::Momentum => "Momentum",
+            Method::NelderMead => "Nelder-Mead",
+            Method::SimulatedAnnealing => "Simulated Annealing",
+            Method::ParticleSwarm => "Particle Swarm",
+            Method::DifferentialEvolution => "Differential Evolution",
+        }
+    }
+
+    /// Group the method belongs to in the result rows, empty for standalone methods.
+    pub fn family(&self) -> &'static str {
+        match self {
+            Method::Backtracking | Method::MoreThuente | Method::HagerZhang => "Linear search",
+            Method::CauchyPoint | Method::Dogleg | Method::Steihaug => "Trust region",
+            Method::NonlinearCg => "Conjugate Gradient",
+            Method::Newton | Method::NewtonCg | Method::SafeguardedNewton => "Newton methods",
+            Method::Bfgs | Method::Dfp | Method::Lbfgs | Method::Sr1TrustRegion => {
+                "Quasi-Newton methods"
+            }
+            Method::GaussNewton | Method::GaussNewtonLs | Method::LevenbergMarquardt => {
+                "Least squares"
+            }
+            Method::Landweber
+            | Method::Momentum
+            | Method::NelderMead
+            | Method::SimulatedAnnealing
+            | Method::ParticleSwarm
+            | Method::DifferentialEvolution => "",
+        }
+    }
+
+    /// One line on what to expect from the method, for `--explain`.
+    pub fn note(&self) -> &'static str {
+        match self {
+            Method::Backtracking | Method::MoreThuente | Method::HagerZhang => {
+                "steepest descent with a line search, robust but slow in narrow valleys"
+            }
+            Method::CauchyPoint => "trust region stepping along the gradient, slow but safe",
+            Method::Dogleg => "trust region mixing gradient and Newton steps, needs a hessian",
+            Method::Steihaug => "trust region solved by truncated CG, scales to larger problems",
+            Method::NonlinearCg => "conjugate directions from gradients only, cheap per iteration",
+            Method::Newton => "full Newton steps, very fast near the minimum, erratic far away",
+            Method::NewtonCg => "Newton steps solved inexactly by CG with a line search",
+            Method::SafeguardedNewton => {
+                "Newton with a line search, gradient steps where the hessian is indefinite"
+            }
+            Method::Bfgs | Method::Dfp => {
+                "builds a hessian approximation from gradients, fast once it has learned it"
+            }
+            Method::Lbfgs => "BFGS keeping only recent updates, the default for smooth problems",
+            Method::Sr1TrustRegion => {
+                "quasi-Newton inside a trust region, tolerates indefinite curvature"
+            }
+            Method::GaussNewton => {
+                "least squares via the jacobian of the residuals, no line search"
+            }
+            Method::GaussNewtonLs => "Gauss-Newton with a line search, safer far from the fit",
+            Method::LevenbergMarquardt => {
+                "Gauss-Newton damped towards gradient steps when it overshoots"
+            }
+            Method::Landweber => "fixed-size gradient steps, slow and sensitive to the step size",
+            Method::Momentum => {
+                "fixed-size gradient steps gathering speed along valleys, may overshoot the minimum"
+            }
+            Method::NelderMead => "derivative-free simplex, fine in low dimensions",
+            Method::SimulatedAnnealing => {
+                "random moves accepted by temperature, can escape local minima but converges slowly"
+            }
+            Method::ParticleSwarm => {
+                "population search over the bounds, global but costly in evaluations"
+            }
+            Method::DifferentialEvolution => {
+                "population recombined from differences of its members, global and robust"
+            }
+        }
+    }
+
+    /// Whether the method draws random numbers, so runs differ with the seed.
+    pub fn is_stochastic(&self) -> bool {
+        matches!(
+            self,
+            Method::SimulatedAnnealing | Method::ParticleSwarm | Method::DifferentialEvolution
+        )
+    }
+
+    /// Whether the method evaluates the gradient of the cost. Least-squares methods use the
+    /// jacobian of the residuals instead.
+    pub fn needs_gradient(&self) -> bool {
+        !matches!(
+            self,
+            Method::GaussNewton
+                | Method::GaussNewtonLs
+                | Method::LevenbergMarquardt
+                | Method::NelderMead
+                | Method::SimulatedAnnealing
+                | Method::ParticleSwarm
+                | Method::DifferentialEvolution
+        )
+    }
+
+    /// Whether the method minimizes residuals instead of a cost, see [`crate::Problem::residuals`].
+    pub fn needs_residuals(&self) -> bool {
+        matches!(
+            self,
+            Method::GaussNewton | Method::GaussNewtonLs | Method::LevenbergMarquardt
+        )
+    }
+
+    /// Whether the method moves through `Anneal`, see [`crate::problems::Derivatives`].
+    pub fn needs_anneal(&self) -> bool {
+        matches!(self, Method::SimulatedAnnealing)
+    }
+
+    /// Whether the method evaluates the hessian of the cost.
+    pub fn needs_hessian(&self) -> bool {
+        match self {
+            Method::CauchyPoint
+            | Method::Dogleg
+            | Method::Steihaug
+            | Method::Newton
+            | Method::NewtonCg
+            | Method::SafeguardedNewton => true,
+            // Takes its initial approximation from the problem.
+            Method::Sr1TrustRegion => true,
+            _ => false,
+        }
+    }
+
+    /// Rough number of evaluations per iteration, counting every evaluation of the cost,
+    /// gradient, hessian, residuals or jacobian as one:
+    /// - line search methods take about two trial points, each with a cost and a gradient;
+    /// - trust region methods evaluate the cost, gradient and hessian once, SR1 without the
+    ///   hessian;
+    /// - Newton evaluates the gradient and hessian, plus about two trial costs when safeguarded,
+    ///   Gauss-Newton the residuals and jacobian,
+    ///   Landweber only the gradient, momentum the gradient and the cost of its step;
+    /// - Levenberg-Marquardt evaluates the residuals and jacobian plus the residuals of its
+    ///   trial step;
+    /// - Nelder-Mead reflects and then usually expands or contracts, ignoring the rare shrinks;
+    /// - simulated annealing evaluates one move, particle swarm and differential evolution every
+    ///   member of their `population`.
+    pub fn evals_per_iter(&self, population: usize) -> u64 {
+        match self {
+            Method::Backtracking
+            | Method::MoreThuente
+            | Method::HagerZhang
+            | Method::NonlinearCg
+            | Method::NewtonCg
+            | Method::SafeguardedNewton
+            | Method::Bfgs
+            | Method::Dfp
+            | Method::Lbfgs
+            | Method::GaussNewtonLs => 4,
+            Method::CauchyPoint
+            | Method::Dogleg
+            | Method::Steihaug
+            | Method::LevenbergMarquardt => 3,
+            Method::Sr1TrustRegion | Method::Newton | Method::GaussNewton => 2,
+            Method::NelderMead | Method::Momentum => 2,
+            Method::Landweber | Method::SimulatedAnnealing => 1,
+            Method::ParticleSwarm | Method::DifferentialEvolution => population as u64,
+        }
+    }
+
+    /// Iteration cap spending about `budget` evaluations, see [`Method::evals_per_iter`]. At
+    /// least one iteration is always run.
+    pub fn iterations_for_budget(&self, budget: u64, population: usize) -> u64 {
+        (budget / self.evals_per_iter(population).max(1)).max(1)
+    }
+
+    /// Short lowercase name used to select the method from the command line, e.g. `l-bfgs` for
+    /// "L-BFGS".
+    pub fn key(&self) -> String {
+        self.label().to_lowercase().replace(' ', "-")
+    }
+
+    /// Label with the settings of the run, e.g. "Simulated Annealing (boltzmann)". The extra
+    /// details are dropped when parsing it back.
+    pub fn label_with(&self, details: &str) -> String {
+        format!("{} ({details})", self.label())
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    /// Accepts the key or the label, with or without the details of [`Method::label_with`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = match s.split_once(" (") {
+            Some((name, _)) => name,
+            None => s,
+        };
+        let name = name.trim().to_lowercase().replace(' ', "-");
+        Method::ALL
+            .into_iter()
+            .find(|x| x.key() == name)
+            .ok_or_else(|| {
+                let keys = Method::ALL.map(|x| x.key());
+                format!("unknown method `{s}`, expected one of: {}", keys.join(", "))
+            })
+    }
+}
+
+/// Methods listed one per line, by key or label, e.g. the file of `--methods-from-file`. Blank
+/// lines and `#` comments are skipped, the order is kept.
+pub fn parse_method_list(text: &str) -> Result<Vec<Method>, String> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.split('#').next().unwrap_or_default().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(number, line)| line.parse().map_err(|e| format!("line {number}: {e}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for method in Method::ALL {
+            assert_eq!(Method::from_str(&method.to_string()), Ok(method));
+            assert_eq!(method.key().parse(), Ok(method));
+            assert_eq!(method.label_with("some, details").parse(), Ok(method));
+            assert!(!method.note().is_empty());
+        }
+        assert_eq!("l-bfgs".parse(), Ok(Method::Lbfgs));
+        assert_eq!("Landweber Iteration".parse(), Ok(Method::Landweber));
+        assert!("lbfgs".parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn test_method_list_file() {
+        let methods =
+            parse_method_list("# Quasi-Newton first\nl-bfgs\n\nBFGS  # label\nnelder-mead\n");
+        assert_eq!(
+            methods,
+            Ok(vec![Method::Lbfgs, Method::Bfgs, Method::NelderMead])
+        );
+        let e = parse_method_list("bfgs\nlbfgs\n").unwrap_err();
+        assert!(
+            e.starts_with("line 2: unknown method `lbfgs`, expected one of: "),
+            "{e}"
+        );
+        assert_eq!(parse_method_list("# nothing\n"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_iterations_for_budget() {
+        assert_eq!(Method::ParticleSwarm.iterations_for_budget(10_000, 500), 20);
+        assert_eq!(Method::ParticleSwarm.iterations_for_budget(100, 500), 1);
+        assert_eq!(Method::Lbfgs.iterations_for_budget(10_000, 500), 2_500);
+        assert_eq!(
+            Method::SimulatedAnnealing.iterations_for_budget(10_000, 500),
+            10_000
+        );
+    }
+}
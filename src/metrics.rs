@@ -0,0 +1,209 @@
+use std::{fmt, str::FromStr};
+
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::results::{self, format_float, DEFAULT_PRECISION};
+
+/// Weights of the best cost, time and evaluation ranks in the composite score of
+/// [`MetricsRow`], e.g. `2,1,1` from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MetricsWeights {
+    pub cost: f64,
+    pub time: f64,
+    pub evals: f64,
+}
+
+impl Default for MetricsWeights {
+    fn default() -> Self {
+        Self {
+            cost: 1.0,
+            time: 1.0,
+            evals: 1.0,
+        }
+    }
+}
+
+impl MetricsWeights {
+    /// The scoring formula, printed above the leaderboard so the score can be interpreted.
+    pub fn formula(&self) -> String {
+        format!(
+            "score = ({} * cost_rank + {} * time_rank + {} * evals_rank) / {}, each rank scaled \
+             to [0, 1] (0 = best), failed rows ranked last, lower is better",
+            self.cost,
+            self.time,
+            self.evals,
+            self.cost + self.time + self.evals
+        )
+    }
+}
+
+impl FromStr for MetricsWeights {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let weights = s
+            .split(',')
+            .map(|x| x.trim().parse::<f64>().map_err(|e| format!("`{x}`: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [cost, time, evals] = weights[..] else {
+            return Err(format!(
+                "expected 3 weights (cost, time, evals), got {}",
+                weights.len()
+            ));
+        };
+        if weights.iter().any(|&x| !(x >= 0.0 && x.is_finite())) || cost + time + evals == 0.0 {
+            return Err(format!(
+                "weights must be non-negative and not all zero, got `{s}`"
+            ));
+        }
+        Ok(Self { cost, time, evals })
+    }
+}
+
+impl fmt::Display for MetricsWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.cost, self.time, self.evals)
+    }
+}
+
+/// One solver on the leaderboard of `--metrics`, ranked by a weighted mean of its ranks in best
+/// cost, time and evaluations, see [`MetricsWeights::formula`].
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+pub struct MetricsRow {
+    pub rank: usize,
+    pub family: String,
+    pub method: String,
+    #[tabled(display_with = "display_score")]
+    pub score: f64,
+    pub cost_rank: usize,
+    pub time_rank: usize,
+    pub evals_rank: usize,
+}
+
+impl MetricsRow {
+    /// Leaderboard of `results`, best first. Larger best costs rank first when `maximize`.
+    ///
+    /// Ties share the better rank. Failed, diverged and skipped rows, and rows missing a time or
+    /// an evaluation count, rank last.
+    pub fn leaderboard(
+        results: &[results::Result],
+        weights: &MetricsWeights,
+        maximize: bool,
+    ) -> Vec<Self> {
+        let sign = if maximize { -1.0 } else { 1.0 };
+        let cost_ranks = ranks(results, |x| Some(sign * x.best_cost));
+        let time_ranks = ranks(results, |x| x.duration.map(|d| d.as_secs_f64()));
+        let evals_ranks = ranks(results, |x| x.evaluations.map(|n| n as f64));
+        // Rank 1 scales to 0, the last rank to 1.
+        let scale = |rank: usize| match results.len() {
+            0 | 1 => 0.0,
+            n => (rank - 1) as f64 / (n - 1) as f64,
+        };
+        let total = weights.cost + weights.time + weights.evals;
+        let mut rows = results
+            .iter()
+            .enumerate()
+            .map(|(i, x)| Self {
+                rank: 0,
+                family: x.family.clone(),
+                method: x.method.clone(),
+                score: (weights.cost * scale(cost_ranks[i])
+                    + weights.time * scale(time_ranks[i])
+                    + weights.evals * scale(evals_ranks[i]))
+                    / total,
+                cost_rank: cost_ranks[i],
+                time_rank: time_ranks[i],
+                evals_rank: evals_ranks[i],
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.score.total_cmp(&b.score));
+        for i in 0..rows.len() {
+            rows[i].rank = match i {
+                0 => 1,
+                _ if rows[i].score == rows[i - 1].score => rows[i - 1].rank,
+                _ => i + 1,
+            };
+        }
+        rows
+    }
+}
+
+/// Rank of every result by `key`, 1 being the smallest. Failed rows and rows without a key
+/// come last.
+fn ranks(results: &[results::Result], key: impl Fn(&results::Result) -> Option<f64>) -> Vec<usize> {
+    let keys = results
+        .iter()
+        .map(|x| key(x).filter(|_| !x.is_failure()))
+        .collect::<Vec<_>>();
+    keys.iter()
+        .map(|key| match key {
+            Some(key) => 1 + keys.iter().flatten().filter(|x| *x < key).count(),
+            None => results.len(),
+        })
+        .collect()
+}
+
+fn display_score(score: &f64) -> String {
+    format_float(*score, DEFAULT_PRECISION)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::results::Result;
+
+    fn row(method: &str, cost: f64, millis: u64, evals: u64) -> Result {
+        Result::new(
+            "",
+            method,
+            cost,
+            Some(Duration::from_millis(millis)),
+            10,
+            None,
+        )
+        .with_evaluations(Some(evals))
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let results = [
+            row("Cheap", 1.0, 10, 100),
+            row("Accurate", 1e-9, 100, 1000),
+            row("Middle", 1e-3, 50, 500),
+            Result::failed("", "Failed", &"no hessian"),
+        ];
+        let rows = MetricsRow::leaderboard(&results, &MetricsWeights::default(), false);
+        let methods = rows.iter().map(|x| x.method.as_str()).collect::<Vec<_>>();
+        // Cheap scores (2/3 + 0 + 0) / 3, Middle 1/3 and Accurate (0 + 2/3 + 2/3) / 3.
+        assert_eq!(methods, ["Cheap", "Middle", "Accurate", "Failed"]);
+        assert!(
+            (rows[0].score - 2.0 / 9.0).abs() < 1e-12,
+            "{}",
+            rows[0].score
+        );
+        let failed = &rows[3];
+        assert_eq!(
+            (failed.cost_rank, failed.time_rank, failed.evals_rank),
+            (4, 4, 4)
+        );
+        assert_eq!(failed.score, 1.0);
+
+        let weights = "1,0,0".parse::<MetricsWeights>().unwrap();
+        let rows = MetricsRow::leaderboard(&results, &weights, false);
+        assert_eq!(rows[0].method, "Accurate");
+        let rows = MetricsRow::leaderboard(&results, &weights, true);
+        assert_eq!(rows[0].method, "Cheap");
+
+        let tied = [row("A", 1.0, 10, 100), row("B", 1.0, 10, 100)];
+        let rows = MetricsRow::leaderboard(&tied, &MetricsWeights::default(), false);
+        assert_eq!(rows.iter().map(|x| x.rank).collect::<Vec<_>>(), [1, 1]);
+
+        assert!("1,1".parse::<MetricsWeights>().is_err());
+        assert!("0,0,0".parse::<MetricsWeights>().is_err());
+        assert!("1,-1,1".parse::<MetricsWeights>().is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    f64::consts::PI,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use argmin::{core::CostFunction, solver::simulatedannealing::Anneal};
+use ndarray::Array1;
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+use crate::annealing::{bounded_anneal, DEFAULT_ANNEAL_STEP};
+
+/// The Michalewicz function is defined as:
+/// $ f(x) = -\sum_{i=1}^{n} \sin(x_i) \sin^{2m}(i x_i^2 / \pi) $
+///
+/// It is evaluated on $[0, \pi]^n$. The steepness `m` controls how narrow the valleys are, the
+/// larger it is the harder it gets to find the global minimum (about $-1.8013$ in 2-D for
+/// `m = 10`).
+#[derive(Clone)]
+pub struct Michalewicz {
+    m: f64,
+    lower_bound: Array1<f64>,
+    upper_bound: Array1<f64>,
+    /// Largest `anneal` move as a fraction of the bound width, see
+    /// [`DEFAULT_ANNEAL_STEP`].
+    anneal_step: f64,
+    /// Random number generator. We use a `Arc<Mutex<_>>` here because `ArgminOperator` requires
+    /// `self` to be passed as an immutable reference. This gives us thread safe interior
+    /// mutability.
+    rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+}
+
+/// Leaves out the state of the random number generator, so equal problems print the same.
+impl fmt::Debug for Michalewicz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Michalewicz")
+            .field("m", &self.m)
+            .field("lower_bound", &self.lower_bound)
+            .field("upper_bound", &self.upper_bound)
+            .field("anneal_step", &self.anneal_step)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Michalewicz {
+    pub fn new(m: f64, dim: usize) -> Self {
+        Self {
+            m,
+            lower_bound: Array1::zeros(dim),
+            upper_bound: Array1::from_elem(dim, PI),
+            anneal_step: DEFAULT_ANNEAL_STEP,
+            rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::from_entropy())),
+        }
+    }
+
+    /// Same function with a different steepness.
+    pub fn with_m(mut self, m: f64) -> Self {
+        self.m = m;
+        self
+    }
+
+    /// Reseeds the random number generator used by `anneal`, making runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(Xoshiro256PlusPlus::seed_from_u64(seed)));
+        self
+    }
+
+    /// Scales the `anneal` moves along each axis to `step` times the width of its bounds.
+    pub fn with_anneal_step(mut self, step: f64) -> Self {
+        self.anneal_step = step;
+        self
+    }
+
+    pub fn m(&self) -> f64 {
+        self.m
+    }
+
+    pub fn dim(&self) -> usize {
+        self.lower_bound.len()
+    }
+
+    pub fn lower_bound(&self) -> &Array1<f64> {
+        &self.lower_bound
+    }
+
+    pub fn upper_bound(&self) -> &Array1<f64> {
+        &self.upper_bound
+    }
+}
+
+impl Default for Michalewicz {
+    fn default() -> Self {
+        Self::new(10.0, 2)
+    }
+}
+
+impl CostFunction for Michalewicz {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let cost = param
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let u = (i + 1) as f64 * x.powi(2) / PI;
+                // `sin^2m` written as `(sin^2)^m` so non-integer `m` stays well defined.
+                x.sin() * u.sin().powi(2).powf(self.m)
+            })
+            .sum::<f64>();
+        Ok(-cost)
+    }
+}
+
+impl Anneal for Michalewicz {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+    type Float = f64;
+
+    fn anneal(
+        &self,
+        param: &Self::Param,
+        temp: Self::Float,
+    ) -> Result<Self::Output, argmin::core::Error> {
+        let mut rng = self.rng.lock().unwrap();
+        Ok(bounded_anneal(
+            param,
+            temp,
+            &mut *rng,
+            (&self.lower_bound, &self.upper_bound),
+            self.anneal_step,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_known_minimum() {
+        let f = Michalewicz::default();
+        let cost = f.cost(&array![2.20290552, PI / 2.0]).unwrap();
+        assert!((cost - -1.8013).abs() < 1e-4, "{cost}");
+        // Coarse grid search never goes below the known minimum.
+        let n = 200;
+        for i in 0..=n {
+            for j in 0..=n {
+                let param = array![PI * i as f64 / n as f64, PI * j as f64 / n as f64];
+                assert!(f.cost(&param).unwrap() > -1.8014);
+            }
+        }
+    }
+
+    #[test]
+    fn test_anneal_within_bounds() {
+        let f = Michalewicz::new(10.0, 3).with_seed(42);
+        let mut param = array![0.05, 1.5, 3.1];
+        for _ in 0..100 {
+            param = f.anneal(&param, 5.0).unwrap();
+            assert!(param.iter().all(|&x| (0.0..=PI).contains(&x)), "{param}");
+        }
+    }
+}
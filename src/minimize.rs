@@ -0,0 +1,359 @@
+use argmin::{
+    core::{
+        ArgminError, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, Hessian,
+        SerializeAlias, Solver, State, TerminationReason,
+    },
+    solver::{
+        conjugategradient::{beta::PolakRibiere, NonlinearConjugateGradient},
+        gradientdescent::SteepestDescent,
+        landweber::Landweber,
+        linesearch::{
+            condition::ArmijoCondition, BacktrackingLineSearch, HagerZhangLineSearch,
+            MoreThuenteLineSearch,
+        },
+        neldermead::NelderMead,
+        newton::{Newton, NewtonCG},
+        quasinewton::{SR1TrustRegion, BFGS, DFP, LBFGS},
+        trustregion::{CauchyPoint, Dogleg, Steihaug, TrustRegion},
+    },
+};
+use ndarray::{Array1, Array2};
+
+use crate::{
+    observers::GradientNorm, safeguarded_newton::SafeguardedNewton, solvers::momentum::Momentum,
+    Method,
+};
+
+/// Outcome of [`minimize`].
+#[derive(Debug, Clone)]
+pub struct MinimizeOutcome {
+    pub best_param: Array1<f64>,
+    pub best_cost: f64,
+    pub iters: u64,
+    /// `None` when the solver stopped without giving a reason.
+    pub termination_reason: Option<TerminationReason>,
+}
+
+/// Minimizes `problem` from `init` with `method`, running at most `max_iters` iterations with
+/// the default solver settings of the comparison, without any logging. Nelder-Mead starts from
+/// `init` plus a unit step along every axis.
+///
+/// Methods needing more than a cost, gradient and hessian aren't supported and return an
+/// error: the least-squares ones (residuals), simulated annealing (an anneal move) and particle
+/// swarm (bounds). Methods not evaluating the cost (e.g. Newton) get the cost of their best
+/// param evaluated at the end. Problems without a hessian or a gradient can use
+/// [`minimize_without_hessian`] and [`minimize_derivative_free`].
+///
+/// ```
+/// use argmin_exploring::{minimize, Ellipsoid, EllipsoidKind, Method};
+/// use ndarray::array;
+///
+/// // Sphere: an ellipsoid with every axis scaled alike.
+/// let sphere = Ellipsoid::new(EllipsoidKind::Ellipsoid, 3).with_condition(1.0);
+/// let outcome = minimize(sphere, Method::Lbfgs, array![1.0, -2.0, 3.0], 100).unwrap();
+/// assert!(outcome.best_cost < 1e-12);
+/// assert!(outcome.best_param.iter().all(|x| x.abs() < 1e-6));
+/// ```
+pub fn minimize<P>(
+    problem: P,
+    method: Method,
+    init: Array1<f64>,
+    max_iters: u64,
+) -> Result<MinimizeOutcome, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>
+        + Hessian<Param = Array1<f64>, Hessian = Array2<f64>>,
+{
+    solve(problem, method, init, max_iters, Outcome)
+}
+
+/// Same as [`minimize`] for a problem without a hessian, the methods needing one return an
+/// error.
+pub fn minimize_without_hessian<P>(
+    problem: P,
+    method: Method,
+    init: Array1<f64>,
+    max_iters: u64,
+) -> Result<MinimizeOutcome, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+{
+    solve_without_hessian(problem, method, init, max_iters, Outcome)
+}
+
+/// Same as [`minimize`] for a problem with only a cost, the methods needing a derivative return
+/// an error.
+pub fn minimize_derivative_free<P>(
+    problem: P,
+    method: Method,
+    init: Array1<f64>,
+    max_iters: u64,
+) -> Result<MinimizeOutcome, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    solve_derivative_free(problem, method, init, max_iters, Outcome)
+}
+
+/// Runs the solver [`solve`] picked for a method, e.g. into a [`MinimizeOutcome`].
+pub(crate) trait Runner<P> {
+    type Output;
+
+    fn run<S, I>(
+        self,
+        problem: P,
+        solver: S,
+        configure: impl FnOnce(I) -> I,
+    ) -> Result<Self::Output, Error>
+    where
+        S: Solver<P, I>,
+        I: State<Param = Array1<f64>, Float = f64>
+            + GradientNorm
+            + SerializeAlias
+            + DeserializeOwnedAlias;
+}
+
+/// Solver of `method` with the default settings of the comparison, handed to `runner`. See
+/// [`minimize`] for the methods it supports. Handles the methods needing a hessian, the others
+/// go through [`solve_without_hessian`].
+pub(crate) fn solve<P, R>(
+    problem: P,
+    method: Method,
+    init: Array1<f64>,
+    max_iters: u64,
+    runner: R,
+) -> Result<R::Output, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>
+        + Hessian<Param = Array1<f64>, Hessian = Array2<f64>>,
+    R: Runner<P>,
+{
+    match method {
+        Method::CauchyPoint => runner.run(problem, TrustRegion::new(CauchyPoint::new()), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        Method::Dogleg => runner.run(problem, TrustRegion::new(Dogleg::new()), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        Method::Steihaug => runner.run(problem, TrustRegion::new(Steihaug::new()), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        Method::Newton => runner.run(problem, Newton::new(), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        Method::NewtonCg => runner.run(
+            problem,
+            NewtonCG::new(MoreThuenteLineSearch::new()),
+            |state| state.param(init).max_iters(max_iters),
+        ),
+        Method::SafeguardedNewton => runner.run(problem, SafeguardedNewton::new(), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        Method::Sr1TrustRegion => {
+            runner.run(problem, SR1TrustRegion::new(Steihaug::new()), |state| {
+                state.param(init).max_iters(max_iters)
+            })
+        }
+        _ => solve_without_hessian(problem, method, init, max_iters, runner),
+    }
+}
+
+/// The methods of [`solve`] needing at most a gradient, the others go through
+/// [`solve_derivative_free`].
+fn solve_without_hessian<P, R>(
+    problem: P,
+    method: Method,
+    init: Array1<f64>,
+    max_iters: u64,
+    runner: R,
+) -> Result<R::Output, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+    R: Runner<P>,
+{
+    let dim = init.len();
+    match method {
+        Method::Backtracking => {
+            let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(0.0001)?);
+            runner.run(problem, SteepestDescent::new(linesearch), |state| {
+                state.param(init).max_iters(max_iters)
+            })
+        }
+        Method::MoreThuente => {
+            let linesearch = MoreThuenteLineSearch::new();
+            runner.run(problem, SteepestDescent::new(linesearch), |state| {
+                state.param(init).max_iters(max_iters)
+            })
+        }
+        Method::HagerZhang => {
+            let linesearch = HagerZhangLineSearch::new();
+            runner.run(problem, SteepestDescent::new(linesearch), |state| {
+                state.param(init).max_iters(max_iters)
+            })
+        }
+        Method::NonlinearCg => {
+            let solver =
+                NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), PolakRibiere::new())
+                    .restart_iters(10)
+                    .restart_orthogonality(0.1);
+            runner.run(problem, solver, |state| {
+                state.param(init).max_iters(max_iters)
+            })
+        }
+        Method::Bfgs => runner.run(problem, BFGS::new(MoreThuenteLineSearch::new()), |state| {
+            state
+                .param(init)
+                .inv_hessian(Array2::eye(dim))
+                .max_iters(max_iters)
+        }),
+        Method::Dfp => runner.run(problem, DFP::new(MoreThuenteLineSearch::new()), |state| {
+            state
+                .param(init)
+                .inv_hessian(Array2::eye(dim))
+                .max_iters(max_iters)
+        }),
+        Method::Lbfgs => runner.run(
+            problem,
+            LBFGS::new(MoreThuenteLineSearch::new(), 5),
+            |state| state.param(init).max_iters(max_iters),
+        ),
+        Method::Landweber => runner.run(problem, Landweber::new(0.001), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        Method::Momentum => runner.run(problem, Momentum::new(), |state| {
+            state.param(init).max_iters(max_iters)
+        }),
+        _ => solve_derivative_free(problem, method, init, max_iters, runner),
+    }
+}
+
+/// The methods of [`solve`] only evaluating the cost.
+fn solve_derivative_free<P, R>(
+    problem: P,
+    method: Method,
+    init: Array1<f64>,
+    max_iters: u64,
+    runner: R,
+) -> Result<R::Output, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>,
+    R: Runner<P>,
+{
+    let missing = if method.needs_hessian() {
+        "a hessian"
+    } else if method.needs_gradient() {
+        "a gradient"
+    } else {
+        match method {
+            Method::NelderMead => {
+                return runner.run(problem, NelderMead::new(simplex(&init)), |state| {
+                    state.param(init).max_iters(max_iters)
+                })
+            }
+            _ => "more than a cost, gradient and hessian",
+        }
+    };
+    Err(ArgminError::NotImplemented {
+        text: format!("`minimize` doesn't support {method} on this problem, it needs {missing}"),
+    }
+    .into())
+}
+
+/// `init` plus a unit step along every axis.
+fn simplex(init: &Array1<f64>) -> Vec<Array1<f64>> {
+    let mut simplex = vec![init.clone()];
+    for i in 0..init.len() {
+        let mut vertex = init.clone();
+        vertex[i] += 1.0;
+        simplex.push(vertex);
+    }
+    simplex
+}
+
+/// Runs into a [`MinimizeOutcome`], without any observer.
+struct Outcome;
+
+impl<P> Runner<P> for Outcome
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    type Output = MinimizeOutcome;
+
+    fn run<S, I>(
+        self,
+        problem: P,
+        solver: S,
+        configure: impl FnOnce(I) -> I,
+    ) -> Result<MinimizeOutcome, Error>
+    where
+        S: Solver<P, I>,
+        I: State<Param = Array1<f64>, Float = f64>
+            + GradientNorm
+            + SerializeAlias
+            + DeserializeOwnedAlias,
+    {
+        let mut res = Executor::new(problem, solver).configure(configure).run()?;
+        let state = res.state();
+        let best_param =
+            state
+                .get_best_param()
+                .cloned()
+                .ok_or_else(|| ArgminError::NotInitialized {
+                    text: "the solver returned no best param".to_string(),
+                })?;
+        let iters = state.get_iter();
+        let termination_reason = state.get_termination_reason().cloned();
+        let best_cost = match state.get_best_cost() {
+            cost if cost.is_finite() => cost,
+            _ => res.problem.cost(&best_param)?,
+        };
+        Ok(MinimizeOutcome {
+            best_param,
+            best_cost,
+            iters,
+            termination_reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::{RosenbrockND, TestFnProblem};
+
+    #[test]
+    fn test_minimize() {
+        let init = array![-1.2, 1.0];
+        let outcome = minimize(RosenbrockND::default(), Method::Newton, init.clone(), 20).unwrap();
+        // Newton never evaluates the cost, it is computed at the end.
+        assert!(outcome.best_cost < 1e-12, "{}", outcome.best_cost);
+        assert_eq!(outcome.iters, 20);
+        assert_eq!(
+            outcome.termination_reason,
+            Some(TerminationReason::MaxItersReached)
+        );
+
+        let outcome = minimize(RosenbrockND::default(), Method::NelderMead, init.clone(), 5);
+        assert_eq!(outcome.unwrap().iters, 5);
+        assert!(minimize(
+            RosenbrockND::default(),
+            Method::ParticleSwarm,
+            init.clone(),
+            5
+        )
+        .is_err());
+
+        // Sphere with only a cost.
+        let cost_only = TestFnProblem::new("sphere", |x| x.iter().map(|x| x * x).sum());
+        let outcome = minimize_derivative_free(cost_only, Method::NelderMead, init, 200);
+        assert!(outcome.unwrap().best_cost < 1e-6);
+        let e = minimize_derivative_free(cost_only, Method::Lbfgs, array![1.0, 1.0], 5);
+        assert!(e.unwrap_err().to_string().contains("it needs a gradient"));
+    }
+}
@@ -0,0 +1,100 @@
+use argmin::core::{ArgminError, CostFunction, Error, Gradient};
+use ndarray::Array1;
+
+/// Scalarizes two objectives into $ w f_1(x) + (1 - w) f_2(x) $, the simplest way to trace the
+/// trade-off between them with single-objective solvers.
+///
+/// Sweeping `w` from 1 to 0 moves the minimizer from the one of `first` to the one of `second`,
+/// but only reaches the convex part of the Pareto front.
+#[derive(Debug, Clone)]
+pub struct WeightedSum<A, B> {
+    first: A,
+    second: B,
+    w: f64,
+}
+
+impl<A, B> WeightedSum<A, B> {
+    /// Weight of `first`, in `[0, 1]`, `second` gets `1 - w`.
+    pub fn new(first: A, second: B, w: f64) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&w) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`w` must be in [0, 1], got {w}"),
+            }
+            .into());
+        }
+        Ok(Self { first, second, w })
+    }
+
+    pub fn w(&self) -> f64 {
+        self.w
+    }
+
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A, B> CostFunction for WeightedSum<A, B>
+where
+    A: CostFunction<Param = Array1<f64>, Output = f64>,
+    B: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.w * self.first.cost(param)? + (1.0 - self.w) * self.second.cost(param)?)
+    }
+}
+
+impl<A, B> Gradient for WeightedSum<A, B>
+where
+    A: Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+    B: Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+{
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self.w * self.first.gradient(param)? + (1.0 - self.w) * self.second.gradient(param)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{Executor, State},
+        solver::{linesearch::MoreThuenteLineSearch, quasinewton::LBFGS},
+    };
+    use ndarray::{array, Array2};
+
+    use super::*;
+    use crate::Quadratic;
+
+    /// Sphere centered at `center`, up to a constant.
+    fn sphere(center: Array1<f64>) -> Quadratic {
+        Quadratic::new(Array2::eye(center.len()), center)
+    }
+
+    #[test]
+    fn test_extreme_weights_recover_each_optimum() {
+        let (a, b) = (array![1.0, 2.0], array![-3.0, 0.5]);
+        for (w, expected) in [(1.0, &a), (0.0, &b), (0.5, &array![-1.0, 1.25])] {
+            let problem = WeightedSum::new(sphere(a.clone()), sphere(b.clone()), w).unwrap();
+            let res = Executor::new(problem, LBFGS::new(MoreThuenteLineSearch::new(), 5))
+                .configure(|state| state.param(array![0.0, 0.0]).max_iters(50))
+                .run()
+                .unwrap();
+            let best = res.state.get_best_param().unwrap();
+            assert!(
+                (best - expected).iter().all(|x| x.abs() < 1e-8),
+                "{w}: {best}"
+            );
+        }
+        assert!(WeightedSum::new(sphere(a.clone()), sphere(b), 1.5).is_err());
+    }
+}
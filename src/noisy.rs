@@ -0,0 +1,264 @@
+use std::{
+    f64::consts::{PI, SQRT_2},
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use argmin::{
+    core::{CostFunction, Gradient, Hessian, Jacobian, Operator},
+    solver::simulatedannealing::Anneal,
+};
+use rand::Rng;
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+/// Distribution of the noise added by [`NoisyProblem`], always scaled to the requested standard
+/// deviation so the distributions only differ in their shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseDist {
+    #[default]
+    Gaussian,
+    /// Bounded, within $\pm \sqrt{3}$ standard deviations.
+    Uniform,
+    /// Heavy-tailed, occasionally far larger than the standard deviation.
+    Laplace,
+}
+
+impl NoiseDist {
+    /// Sample with zero mean and unit variance.
+    fn sample(&self, rng: &mut impl Rng) -> f64 {
+        match self {
+            NoiseDist::Gaussian => standard_normal(rng),
+            NoiseDist::Uniform => 3f64.sqrt() * (2.0 * rng.gen::<f64>() - 1.0),
+            NoiseDist::Laplace => {
+                // Exponential magnitude with a random sign, `1 - gen` in (0, 1] keeps the
+                // logarithm finite.
+                let magnitude = -(1.0 - rng.gen::<f64>()).ln() / SQRT_2;
+                if rng.gen::<bool>() {
+                    magnitude
+                } else {
+                    -magnitude
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for NoiseDist {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gaussian" => Ok(NoiseDist::Gaussian),
+            "uniform" => Ok(NoiseDist::Uniform),
+            "laplace" => Ok(NoiseDist::Laplace),
+            _ => Err(format!(
+                "unknown noise distribution `{s}`, expected gaussian, uniform or laplace"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NoiseDist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NoiseDist::Gaussian => "gaussian",
+            NoiseDist::Uniform => "uniform",
+            NoiseDist::Laplace => "laplace",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Adds noise with standard deviation `stddev` to the cost of the inner problem, gaussian unless
+/// set with [`NoisyProblem::with_dist`].
+///
+/// Only the cost is noisy: gradient, hessian, residuals and anneal are forwarded untouched, so
+/// gradient based methods see a clean gradient but a noisy cost (e.g. in their line searches).
+#[derive(Debug, Clone)]
+pub struct NoisyProblem<P> {
+    inner: P,
+    stddev: f64,
+    dist: NoiseDist,
+    /// Random number generator. We use a `Arc<Mutex<_>>` here because `CostFunction` requires
+    /// `self` to be passed as an immutable reference.
+    rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+}
+
+impl<P> NoisyProblem<P> {
+    pub fn new(inner: P, stddev: f64) -> Self {
+        Self {
+            inner,
+            stddev,
+            dist: NoiseDist::default(),
+            rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::from_entropy())),
+        }
+    }
+
+    /// Reseeds the noise, making runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(Xoshiro256PlusPlus::seed_from_u64(seed)));
+        self
+    }
+
+    pub fn with_dist(mut self, dist: NoiseDist) -> Self {
+        self.dist = dist;
+        self
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn stddev(&self) -> f64 {
+        self.stddev
+    }
+
+    pub fn dist(&self) -> NoiseDist {
+        self.dist
+    }
+
+    fn noise(&self) -> f64 {
+        if self.stddev == 0.0 {
+            return 0.0;
+        }
+        let mut rng = self.rng.lock().unwrap();
+        self.stddev * self.dist.sample(&mut *rng)
+    }
+}
+
+/// Standard normal sample using the Box-Muller transform.
+pub(crate) fn standard_normal(rng: &mut impl Rng) -> f64 {
+    // `u1` in (0, 1] so the logarithm stays finite.
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+impl<P> CostFunction for NoisyProblem<P>
+where
+    P: CostFunction<Output = f64>,
+{
+    type Param = P::Param;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        Ok(self.inner.cost(param)? + self.noise())
+    }
+}
+
+impl<P: Gradient> Gradient for NoisyProblem<P> {
+    type Param = P::Param;
+    type Gradient = P::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        self.inner.gradient(param)
+    }
+}
+
+impl<P: Hessian> Hessian for NoisyProblem<P> {
+    type Param = P::Param;
+    type Hessian = P::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, argmin::core::Error> {
+        self.inner.hessian(param)
+    }
+}
+
+impl<P: Operator> Operator for NoisyProblem<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        self.inner.apply(param)
+    }
+}
+
+impl<P: Jacobian> Jacobian for NoisyProblem<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, argmin::core::Error> {
+        self.inner.jacobian(param)
+    }
+}
+
+impl<P: Anneal> Anneal for NoisyProblem<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(
+        &self,
+        param: &Self::Param,
+        temp: Self::Float,
+    ) -> Result<Self::Output, argmin::core::Error> {
+        self.inner.anneal(param, temp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    #[test]
+    fn test_zero_stddev() {
+        let inner = RosenbrockND::default();
+        let f = NoisyProblem::new(inner.clone(), 0.0);
+        for param in [array![10.2, -20.0], array![1.0, 1.0], array![-1.2, 1.0]] {
+            assert_eq!(f.cost(&param).unwrap(), inner.cost(&param).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_noise() {
+        let param = array![1.0, 1.0];
+        let costs = |seed| {
+            let f = NoisyProblem::new(RosenbrockND::default(), 0.5).with_seed(seed);
+            (0..2000)
+                .map(|_| f.cost(&param).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let x = costs(42);
+        assert_eq!(x, costs(42));
+        assert_ne!(x, costs(7));
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        let var = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / x.len() as f64;
+        assert!(mean.abs() < 0.05, "{mean}");
+        assert!((var.sqrt() - 0.5).abs() < 0.05, "{}", var.sqrt());
+    }
+
+    #[test]
+    fn test_distributions() {
+        let param = array![1.0, 1.0];
+        for (dist, kurtosis) in [
+            (NoiseDist::Gaussian, 3.0),
+            (NoiseDist::Uniform, 1.8),
+            (NoiseDist::Laplace, 6.0),
+        ] {
+            let f = NoisyProblem::new(RosenbrockND::default(), 0.5)
+                .with_dist(dist)
+                .with_seed(42);
+            let x = (0..20_000)
+                .map(|_| f.cost(&param).unwrap())
+                .collect::<Vec<_>>();
+            let mean = x.iter().sum::<f64>() / x.len() as f64;
+            let moment = |k| x.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / x.len() as f64;
+            assert!(mean.abs() < 0.02, "{dist}: {mean}");
+            assert!(
+                (moment(2).sqrt() - 0.5).abs() < 0.02,
+                "{dist}: {}",
+                moment(2)
+            );
+            let estimate = moment(4) / moment(2).powi(2);
+            assert!((estimate - kurtosis).abs() < 0.5, "{dist}: {estimate}");
+            assert_eq!(dist.to_string().parse(), Ok(dist));
+        }
+        let bound = 0.5 * 3f64.sqrt();
+        let f = NoisyProblem::new(RosenbrockND::default(), 0.5).with_dist(NoiseDist::Uniform);
+        assert!((0..1000).all(|_| f.cost(&param).unwrap().abs() <= bound));
+    }
+}
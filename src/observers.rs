@@ -0,0 +1,613 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use argmin::core::{observers::Observe, Error, IterState, KvValue, PopulationState, State, KV};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+use crate::{results::csv_field, runner::ToParamVec, trajectory::Trajectory};
+
+/// Records the best cost after every iteration.
+///
+/// `Executor::add_observer` takes ownership of the observer, so the history lives behind an
+/// `Arc<Mutex<_>>`: keep a clone around to read it once the run is over.
+#[derive(Debug, Clone, Default)]
+pub struct CostHistoryObserver {
+    best_costs: Arc<Mutex<Vec<f64>>>,
+}
+
+impl CostHistoryObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Best cost after each iteration, the first value is after iteration 1.
+    pub fn best_costs(&self) -> Vec<f64> {
+        self.best_costs.lock().unwrap().clone()
+    }
+}
+
+impl<I: State<Float = f64>> Observe<I> for CostHistoryObserver {
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.best_costs.lock().unwrap().push(state.get_best_cost());
+        Ok(())
+    }
+}
+
+/// Records the param and cost after every iteration, for `--save-best-trajectory`. Shares its
+/// records between clones like [`CostHistoryObserver`].
+#[derive(Debug, Clone, Default)]
+pub struct TrajectoryObserver {
+    trajectory: Arc<Mutex<Trajectory>>,
+}
+
+impl TrajectoryObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trajectory(&self) -> Trajectory {
+        self.trajectory.lock().unwrap().clone()
+    }
+}
+
+impl<I> Observe<I> for TrajectoryObserver
+where
+    I: State<Float = f64>,
+    I::Param: ToParamVec,
+{
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        let Some(param) = state.get_param() else {
+            return Ok(());
+        };
+        let mut trajectory = self.trajectory.lock().unwrap();
+        trajectory.params.push(param.to_param_vec());
+        trajectory.costs.push(state.get_cost());
+        Ok(())
+    }
+}
+
+/// Moves accepted and rejected by simulated annealing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AcceptanceCounts {
+    pub accepted: u64,
+    pub rejected: u64,
+}
+
+impl AcceptanceCounts {
+    /// Fraction of accepted moves, `None` if no move was recorded.
+    pub fn rate(&self) -> Option<f64> {
+        let total = self.accepted + self.rejected;
+        (total > 0).then(|| self.accepted as f64 / total as f64)
+    }
+}
+
+/// Counts the moves accepted by simulated annealing, read from the `acc` entry of its iteration
+/// log. A healthy anneal starts accepting most moves and then cools down, a start that is too
+/// cold rejects almost everything.
+///
+/// Other solvers don't log `acc`, so their counts stay at zero. Shares its counts between clones
+/// like [`CostHistoryObserver`].
+#[derive(Debug, Clone, Default)]
+pub struct AcceptanceObserver {
+    counts: Arc<Mutex<AcceptanceCounts>>,
+}
+
+impl AcceptanceObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn counts(&self) -> AcceptanceCounts {
+        *self.counts.lock().unwrap()
+    }
+}
+
+impl<I: State> Observe<I> for AcceptanceObserver {
+    fn observe_iter(&mut self, _state: &I, kv: &KV) -> Result<(), Error> {
+        let accepted = match kv.get("acc").and_then(KvValue::get_bool) {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let mut counts = self.counts.lock().unwrap();
+        if accepted {
+            counts.accepted += 1;
+        } else {
+            counts.rejected += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Counts the steps where [`SafeguardedNewton`](crate::safeguarded_newton::SafeguardedNewton)
+/// fell back to steepest descent, read from the `fallback` entry of its iteration log.
+///
+/// The count stays `None` for solvers that don't log `fallback`. Shares its count between clones
+/// like [`CostHistoryObserver`].
+#[derive(Debug, Clone, Default)]
+pub struct FallbackObserver {
+    count: Arc<Mutex<Option<u64>>>,
+}
+
+impl FallbackObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> Option<u64> {
+        *self.count.lock().unwrap()
+    }
+}
+
+impl<I: State> Observe<I> for FallbackObserver {
+    fn observe_iter(&mut self, _state: &I, kv: &KV) -> Result<(), Error> {
+        let fallback = match kv.get("fallback").and_then(KvValue::get_bool) {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        *self.count.lock().unwrap().get_or_insert(0) += fallback as u64;
+        Ok(())
+    }
+}
+
+/// Restarts done by a [`RestartingSolver`](crate::restart::RestartingSolver) and the attempt
+/// that found the best cost, read from the `attempt` entry of its iteration log.
+///
+/// Both stay `None` for solvers that don't log `attempt`. Shares them between clones like
+/// [`CostHistoryObserver`].
+#[derive(Debug, Clone, Default)]
+pub struct RestartObserver {
+    attempts: Arc<Mutex<Option<(u64, u64)>>>,
+}
+
+impl RestartObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn restarts(&self) -> Option<u64> {
+        self.attempts.lock().unwrap().map(|(restarts, _)| restarts)
+    }
+
+    pub fn best_attempt(&self) -> Option<u64> {
+        self.attempts.lock().unwrap().map(|(_, best)| best)
+    }
+}
+
+impl<I: State> Observe<I> for RestartObserver {
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        let attempt = match kv.get("attempt").and_then(KvValue::get_uint) {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let mut attempts = self.attempts.lock().unwrap();
+        let (restarts, best) = attempts.get_or_insert((0, 0));
+        *restarts = attempt;
+        if state.is_best() {
+            *best = attempt;
+        }
+        Ok(())
+    }
+}
+
+/// Spread of the wall-clock time taken by the iterations of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IterTimes {
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    /// Mean time of the last quarter of the iterations over the one of the first quarter, `None`
+    /// with fewer than 8 iterations. Well above 1 when iterations get slower as the solver goes,
+    /// e.g. with an internal state growing every iteration.
+    pub growth: Option<f64>,
+}
+
+impl IterTimes {
+    /// `None` without any iteration.
+    pub fn new(durations: &[Duration]) -> Option<Self> {
+        let mean = |x: &[Duration]| x.iter().sum::<Duration>() / x.len() as u32;
+        let quarter = durations.len() / 4;
+        let growth = (durations.len() >= 8).then(|| {
+            let first = mean(&durations[..quarter]).as_secs_f64();
+            let last = mean(&durations[durations.len() - quarter..]).as_secs_f64();
+            last / first
+        });
+        Some(Self {
+            min: *durations.iter().min()?,
+            mean: mean(durations),
+            max: *durations.iter().max()?,
+            growth,
+        })
+    }
+
+    /// Whether the last iterations took at least twice as long as the first ones.
+    pub fn is_growing(&self) -> bool {
+        self.growth.is_some_and(|x| x >= 2.0)
+    }
+}
+
+impl fmt::Display for IterTimes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({:?}-{:?})", self.mean, self.min, self.max)?;
+        match self.growth {
+            Some(growth) if self.is_growing() => write!(f, ", growing x{growth:.1}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct IterClock {
+    last: Option<Instant>,
+    durations: Vec<Duration>,
+}
+
+/// Records the wall-clock time between successive iterations, the first one counted from the
+/// initialization of the solver. Shares its records between clones like [`CostHistoryObserver`].
+#[derive(Debug, Clone, Default)]
+pub struct TimePerIterObserver {
+    clock: Arc<Mutex<IterClock>>,
+}
+
+impl TimePerIterObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time taken by each iteration.
+    pub fn durations(&self) -> Vec<Duration> {
+        self.clock.lock().unwrap().durations.clone()
+    }
+
+    pub fn times(&self) -> Option<IterTimes> {
+        IterTimes::new(&self.clock.lock().unwrap().durations)
+    }
+}
+
+impl<I: State> Observe<I> for TimePerIterObserver {
+    fn observe_init(&mut self, _name: &str, _kv: &KV) -> Result<(), Error> {
+        self.clock.lock().unwrap().last = Some(Instant::now());
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, _state: &I, _kv: &KV) -> Result<(), Error> {
+        let now = Instant::now();
+        let mut clock = self.clock.lock().unwrap();
+        if let Some(last) = clock.last {
+            clock.durations.push(now - last);
+        }
+        clock.last = Some(now);
+        Ok(())
+    }
+}
+
+/// States that may carry a gradient, so observers can log its norm.
+pub trait GradientNorm {
+    /// L2 norm of the current gradient, `None` for gradient-free solvers.
+    fn gradient_norm(&self) -> Option<f64>;
+}
+
+impl<P, J, H> GradientNorm for IterState<P, Array1<f64>, J, H, f64> {
+    fn gradient_norm(&self) -> Option<f64> {
+        self.grad.as_ref().map(|g| g.dot(g).sqrt())
+    }
+}
+
+impl<P, J, H> GradientNorm for IterState<P, Vec<f64>, J, H, f64> {
+    fn gradient_norm(&self) -> Option<f64> {
+        self.grad
+            .as_ref()
+            .map(|g| g.iter().map(|x| x.powi(2)).sum::<f64>().sqrt())
+    }
+}
+
+impl<P, J, H> GradientNorm for IterState<P, (), J, H, f64> {
+    fn gradient_norm(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl<P> GradientNorm for PopulationState<P, f64> {
+    fn gradient_norm(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// Writes a `method,iter,cost,best_cost,gradient_norm` row after every iteration, into a single
+/// CSV shared by all the runs of a comparison.
+///
+/// Clones share the same writer, [`CsvHistoryObserver::for_method`] gives one tagged with the
+/// method of a run. Every row is written at once under a lock, so runs on different threads
+/// never interleave within a row. `iter` is the number of iterations done so far, the gradient
+/// norm is left empty when the solver doesn't keep the gradient in its state (gradient-free
+/// solvers, but also e.g. steepest descent).
+#[derive(Clone)]
+pub struct CsvHistoryObserver {
+    method: String,
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl CsvHistoryObserver {
+    pub const HEADER: &'static str = "method,iter,cost,best_cost,gradient_norm";
+
+    /// Writes the header to `writer`.
+    pub fn new(writer: impl Write + Send + 'static) -> io::Result<Self> {
+        let mut writer: Box<dyn Write + Send> = Box::new(writer);
+        writeln!(writer, "{}", Self::HEADER)?;
+        Ok(Self {
+            method: String::new(),
+            writer: Arc::new(Mutex::new(writer)),
+        })
+    }
+
+    /// Creates (or truncates) the file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Observer writing to the same file, tagging its rows with `method`.
+    pub fn for_method(&self, method: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            writer: self.writer.clone(),
+        }
+    }
+
+    /// Flushes the rows buffered so far, call it once all the runs are over.
+    pub fn flush(&self) -> io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+impl fmt::Debug for CsvHistoryObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsvHistoryObserver")
+            .field("method", &self.method)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I: State<Float = f64> + GradientNorm> Observe<I> for CsvHistoryObserver {
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        let gradient_norm = state
+            .gradient_norm()
+            .map(|x| x.to_string())
+            .unwrap_or_default();
+        let row = format!(
+            "{},{},{},{},{gradient_norm}\n",
+            csv_field(&self.method),
+            state.get_iter() + 1,
+            state.get_cost(),
+            state.get_best_cost(),
+        );
+        self.writer.lock().unwrap().write_all(row.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Streams a `{"method", "iter", "cost", "best_cost"}` JSON object per line (NDJSON) after every
+/// iteration, so a dashboard can plot the convergence while the runs go.
+///
+/// Shares its writer between clones like [`CsvHistoryObserver`], and flushes after every line
+/// so nothing waits in a buffer. Non-finite costs are written as `null`.
+#[derive(Clone)]
+pub struct NdjsonObserver {
+    method: String,
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl NdjsonObserver {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            method: String::new(),
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+
+    /// Observer writing to the same stream, tagging its lines with `method`.
+    pub fn for_method(&self, method: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for NdjsonObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NdjsonObserver")
+            .field("method", &self.method)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I: State<Float = f64>> Observe<I> for NdjsonObserver {
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        let line = serde_json::json!({
+            "method": self.method,
+            "iter": state.get_iter() + 1,
+            "cost": state.get_cost(),
+            "best_cost": state.get_best_cost(),
+        });
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{line}")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Where the runs log their state every few iterations, `--observer` on the command line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Logger {
+    /// argmin's `SlogLogger`, on the terminal.
+    #[default]
+    Term,
+    /// [`LogObserver`](crate::log_observer::LogObserver), through the `log` facade.
+    #[cfg(feature = "log")]
+    Log,
+}
+
+impl FromStr for Logger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "term" => Ok(Logger::Term),
+            #[cfg(feature = "log")]
+            "log" => Ok(Logger::Log),
+            #[cfg(not(feature = "log"))]
+            "log" => Err("the `log` observer needs the `log` feature".to_string()),
+            _ => Err(format!("unknown observer `{s}`, expected term or log")),
+        }
+    }
+}
+
+/// Number of iterations needed for the best cost to drop below `threshold`, `None` if it never
+/// did.
+pub fn iters_to(best_costs: &[f64], threshold: f64) -> Option<u64> {
+    best_costs
+        .iter()
+        .position(|&x| x < threshold)
+        .map(|i| i as u64 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{observers::ObserverMode, CostFunction, Executor},
+        solver::neldermead::NelderMead,
+    };
+    use argmin_testfunctions::sphere;
+    use ndarray::{array, Array1};
+
+    use super::*;
+    use crate::{annealing::AnnealingConfig, results::COST_THRESHOLDS, RosenbrockND};
+
+    struct Sphere;
+
+    impl CostFunction for Sphere {
+        type Param = Array1<f64>;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(sphere(&param.to_vec()))
+        }
+    }
+
+    #[test]
+    fn test_iters_to() {
+        let best_costs = [10.0, 0.5, 0.05, 1e-4, 1e-4];
+        assert_eq!(iters_to(&best_costs, 1.0), Some(2));
+        assert_eq!(iters_to(&best_costs, 1e-3), Some(4));
+        assert_eq!(iters_to(&best_costs, 1e-6), None);
+    }
+
+    #[test]
+    fn test_acceptance_rate() {
+        let rate = |init_temp| {
+            let acceptance = AcceptanceObserver::new();
+            let solver = AnnealingConfig {
+                init_temp,
+                ..Default::default()
+            }
+            .build(Some(42))
+            .unwrap();
+            // Starting at the minimum every move is worse, only a hot anneal accepts them.
+            Executor::new(RosenbrockND::default().with_seed(7), solver)
+                .configure(|state| state.param(array![1.0, 1.0]).max_iters(200))
+                .add_observer(acceptance.clone(), ObserverMode::Always)
+                .run()
+                .unwrap();
+            let counts = acceptance.counts();
+            assert_eq!(counts.accepted + counts.rejected, 200);
+            counts.rate().unwrap()
+        };
+        let hot = rate(1e3);
+        let cold = rate(1e-6);
+        assert!(hot > 0.2, "{hot}");
+        assert!(cold < 0.05, "{cold}");
+        assert_eq!(AcceptanceCounts::default().rate(), None);
+    }
+
+    #[test]
+    fn test_time_per_iter() {
+        let times = TimePerIterObserver::new();
+        let simplex = vec![array![3.0, -4.0], array![4.0, -4.0], array![3.0, -3.0]];
+        let res = Executor::new(Sphere, NelderMead::new(simplex))
+            .configure(|state| state.max_iters(40))
+            .add_observer(times.clone(), ObserverMode::Always)
+            .run()
+            .unwrap();
+        assert_eq!(times.durations().len() as u64, res.state.get_iter());
+        let stats = times.times().unwrap();
+        assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+        assert!(stats.growth.is_some());
+
+        let growing = (1..=8).map(Duration::from_millis).collect::<Vec<_>>();
+        assert!(IterTimes::new(&growing).unwrap().is_growing());
+        assert_eq!(IterTimes::new(&[]), None);
+    }
+
+    #[test]
+    fn test_sphere_thresholds() {
+        let history = CostHistoryObserver::new();
+        let simplex = vec![array![3.0, -4.0], array![4.0, -4.0], array![3.0, -3.0]];
+        let res = Executor::new(Sphere, NelderMead::new(simplex))
+            .configure(|state| state.max_iters(200))
+            .add_observer(history.clone(), ObserverMode::Always)
+            .run()
+            .unwrap();
+        let best_costs = history.best_costs();
+        assert_eq!(best_costs.len() as u64, res.state.get_iter());
+        let iters = COST_THRESHOLDS.map(|x| iters_to(&best_costs, x).unwrap());
+        assert!(iters.windows(2).all(|x| x[0] < x[1]), "{iters:?}");
+    }
+
+    /// Buffer readable after the observer took ownership of its writer.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_ndjson_stream() {
+        let buffer = SharedBuffer::default();
+        let ndjson = NdjsonObserver::new(buffer.clone()).for_method("Nelder-Mead");
+        let simplex = vec![array![3.0, -4.0], array![4.0, -4.0], array![3.0, -3.0]];
+        Executor::new(Sphere, NelderMead::new(simplex))
+            .configure(|state| state.max_iters(10))
+            .add_observer(ndjson, ObserverMode::Always)
+            .run()
+            .unwrap();
+        let content = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines = content
+            .lines()
+            .map(|x| serde_json::from_str::<serde_json::Value>(x).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 10);
+        for (i, line) in lines.iter().enumerate() {
+            assert_eq!(line["method"], "Nelder-Mead");
+            assert_eq!(line["iter"], i as u64 + 1);
+            assert!(line["best_cost"].as_f64().unwrap() <= line["cost"].as_f64().unwrap());
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use ndarray::{array, Array1};
+
+use crate::{
+    bohachevsky::Bohachevsky,
+    ellipsoid::Ellipsoid,
+    perm::Perm,
+    qing::{Qing, MAX_OPTIMA_DIM},
+    results::{format_float, Result, DEFAULT_PRECISION},
+    salomon::Salomon,
+    whitley::Whitley,
+    Problem, RosenbrockND,
+};
+
+/// Problems whose global minimizers are known analytically.
+pub trait KnownOptimum {
+    /// Every global minimizer, empty when they aren't known (e.g. unusual parameters).
+    fn optima(&self) -> Vec<Array1<f64>>;
+
+    /// L2 distance from `param` to the nearest optimum, `None` when no optimum is known.
+    fn distance_to_optimum(&self, param: &[f64]) -> Option<f64> {
+        nearest_distance(&self.optima(), param)
+    }
+}
+
+fn nearest_distance(optima: &[Array1<f64>], param: &[f64]) -> Option<f64> {
+    optima
+        .iter()
+        .map(|x| {
+            x.iter()
+                .zip(param)
+                .map(|(x, y)| (x - y).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .min_by(f64::total_cmp)
+}
+
+impl KnownOptimum for RosenbrockND {
+    /// $(a, a^2)$ in two dimensions. The chained form only has a simple minimizer for `a = 1`,
+    /// at $(1, \dots, 1)$.
+    fn optima(&self) -> Vec<Array1<f64>> {
+        let a = self.a();
+        if self.dim() == 2 {
+            vec![array![a, a.powi(2)]]
+        } else if a == 1.0 {
+            vec![Array1::ones(self.dim())]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl KnownOptimum for Bohachevsky {
+    fn optima(&self) -> Vec<Array1<f64>> {
+        vec![array![0.0, 0.0]]
+    }
+}
+
+impl KnownOptimum for Perm {
+    fn optima(&self) -> Vec<Array1<f64>> {
+        vec![self.optimum()]
+    }
+}
+
+impl KnownOptimum for Ellipsoid {
+    fn optima(&self) -> Vec<Array1<f64>> {
+        vec![Array1::zeros(self.dim())]
+    }
+}
+
+impl KnownOptimum for Whitley {
+    fn optima(&self) -> Vec<Array1<f64>> {
+        vec![self.optimum()]
+    }
+}
+
+impl KnownOptimum for Qing {
+    /// One per sign pattern of the positive optimum, up to [`MAX_OPTIMA_DIM`] dimensions.
+    fn optima(&self) -> Vec<Array1<f64>> {
+        if self.dim() > MAX_OPTIMA_DIM {
+            return Vec::new();
+        }
+        let optimum = self.optimum();
+        (0..1usize << self.dim())
+            .map(|signs| {
+                let mut x = optimum.clone();
+                x.indexed_iter_mut()
+                    .filter(|(i, _)| signs >> i & 1 == 1)
+                    .for_each(|(_, x)| *x = -*x);
+                x
+            })
+            .collect()
+    }
+}
+
+impl KnownOptimum for Salomon {
+    fn optima(&self) -> Vec<Array1<f64>> {
+        vec![self.optimum()]
+    }
+}
+
+impl KnownOptimum for Problem {
+    fn optima(&self) -> Vec<Array1<f64>> {
+        match self {
+            Problem::Rosenbrock(f) => f.optima(),
+            Problem::Bohachevsky(f) => f.optima(),
+            Problem::Perm(f) => f.optima(),
+            Problem::Ellipsoid(f) => f.optima(),
+            Problem::Whitley(f) => f.optima(),
+            Problem::Qing(f) => f.optima(),
+            Problem::Salomon(f) => f.optima(),
+            // Only known numerically.
+            Problem::Michalewicz(_) | Problem::Hartmann(_) | Problem::Shekel(_) => Vec::new(),
+        }
+    }
+}
+
+/// Stops a run once the best param gets within `tol` (L2 distance) of the nearest optimum, to
+/// measure iterations-to-accuracy instead of iterations-to-cost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamTarget {
+    optima: Vec<Array1<f64>>,
+    tol: f64,
+}
+
+impl ParamTarget {
+    /// Returns `None` when the problem has no known optimum.
+    pub fn new(problem: &impl KnownOptimum, tol: f64) -> Option<Self> {
+        let optima = problem.optima();
+        (!optima.is_empty()).then_some(Self { optima, tol })
+    }
+
+    pub fn tol(&self) -> f64 {
+        self.tol
+    }
+
+    pub fn reached(&self, param: &[f64]) -> bool {
+        nearest_distance(&self.optima, param).unwrap() < self.tol
+    }
+
+    /// How close the best param of `result` got, for `--check-optimum`.
+    pub fn check(&self, result: &Result) -> OptimumCheck {
+        let distance = result
+            .best_param
+            .as_ref()
+            .and_then(|x| nearest_distance(&self.optima, x));
+        OptimumCheck {
+            method: result.method.clone(),
+            distance,
+            passed: distance.is_some_and(|x| x < self.tol),
+        }
+    }
+}
+
+/// Outcome of `--check-optimum` for one method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimumCheck {
+    pub method: String,
+    /// Distance from the best param to the nearest optimum, `None` when the run has no best
+    /// param (e.g. it failed or was skipped).
+    pub distance: Option<f64>,
+    pub passed: bool,
+}
+
+/// The precision, e.g. `{:.3}`, gives the significant digits of the distance.
+impl fmt::Display for OptimumCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed { "pass" } else { "FAIL" };
+        let digits = f.precision().unwrap_or(DEFAULT_PRECISION);
+        match self.distance {
+            Some(distance) => write!(
+                f,
+                "{status} {}: distance {}",
+                self.method,
+                format_float(distance, digits)
+            ),
+            None => write!(f, "{status} {}: no best param", self.method),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::CostFunction;
+
+    use super::*;
+
+    #[test]
+    fn test_param_target() {
+        let target = ParamTarget::new(&RosenbrockND::default(), 1e-3).unwrap();
+        assert!(target.reached(&[1.0, 1.0005]));
+        assert!(!target.reached(&[1.0, 1.01]));
+
+        // Multiple minima: the distance is to the nearest one.
+        let optima = [array![-1.0, 0.0], array![1.0, 0.0]];
+        let distance = nearest_distance(&optima, &[0.9, 0.0]).unwrap();
+        assert!((distance - 0.1).abs() < 1e-12);
+        assert!(ParamTarget::new(
+            &RosenbrockND::new(2.0, 100.0, Array1::zeros(3), Array1::ones(3)),
+            1e-3
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_check() {
+        let target = ParamTarget::new(&RosenbrockND::default(), 1e-4).unwrap();
+        let result = |param: Option<Vec<f64>>| {
+            Result::new("", "Newton", 0.0, None, 1, None).with_best_param(param)
+        };
+        let check = target.check(&result(Some(vec![1.0, 1.00005])));
+        assert!(check.passed);
+        assert!((check.distance.unwrap() - 5e-5).abs() < 1e-12);
+        assert!(!target.check(&result(Some(vec![1.0, 1.001]))).passed);
+        let check = target.check(&result(None));
+        assert_eq!((check.distance, check.passed), (None, false));
+        assert_eq!(check.to_string(), "FAIL Newton: no best param");
+    }
+
+    #[test]
+    fn test_optima() {
+        for name in Problem::NAMES {
+            let problem = name.parse::<Problem>().unwrap();
+            for optimum in problem.optima() {
+                assert!(problem.cost(&optimum).unwrap().abs() < 1e-15, "{name}");
+            }
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::results::{format_float, Result, DEFAULT_PRECISION};
+
+/// One method on the cost-time plane of `--pareto`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParetoPoint {
+    pub method: String,
+    /// Run time in seconds, the average over `--repeat`.
+    pub time: f64,
+    pub best_cost: f64,
+    /// No other method is at least as fast and as good, and better at one of them.
+    pub frontier: bool,
+}
+
+/// Best cost against run time of every method, with the non-dominated ones marked: the best
+/// accuracy reachable within each time. Rendered as an SVG scatter or as text.
+///
+/// Failed, diverged and skipped methods have no meaningful point, they are listed apart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParetoPlot {
+    pub points: Vec<ParetoPoint>,
+    /// Methods left off the plot, with their status.
+    pub excluded: Vec<(String, String)>,
+    /// Significant digits of the rendered costs.
+    pub digits: usize,
+}
+
+impl ParetoPlot {
+    /// Larger best costs are better when `maximize`.
+    pub fn new(results: &[Result], maximize: bool) -> Self {
+        let mut points = Vec::new();
+        let mut excluded = Vec::new();
+        for result in results {
+            match (result.is_failure(), result.duration) {
+                (false, Some(duration)) => points.push(ParetoPoint {
+                    method: result.method.clone(),
+                    time: duration.as_secs_f64(),
+                    best_cost: result.best_cost,
+                    frontier: false,
+                }),
+                (false, None) => excluded.push((result.method.clone(), "no time".to_string())),
+                (true, _) => excluded.push((result.method.clone(), result.status.to_string())),
+            }
+        }
+        let sign = if maximize { -1.0 } else { 1.0 };
+        let dominates = |a: &ParetoPoint, b: &ParetoPoint| {
+            let (ca, cb) = (sign * a.best_cost, sign * b.best_cost);
+            a.time <= b.time && ca <= cb && (a.time < b.time || ca < cb)
+        };
+        let frontier = points
+            .iter()
+            .map(|p| !points.iter().any(|q| dominates(q, p)))
+            .collect::<Vec<_>>();
+        for (point, frontier) in points.iter_mut().zip(frontier) {
+            point.frontier = frontier;
+        }
+        Self {
+            points,
+            excluded,
+            digits: DEFAULT_PRECISION,
+        }
+    }
+
+    /// Renders the costs with `digits` significant digits instead of [`DEFAULT_PRECISION`].
+    pub fn with_precision(mut self, digits: usize) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Time on a log scale, best cost too unless some cost is negative.
+    fn scales(&self) -> (Scale, Scale) {
+        let times = self.points.iter().map(|x| x.time);
+        let costs = self.points.iter().map(|x| x.best_cost);
+        let log_cost = self.points.iter().all(|x| x.best_cost >= 0.0);
+        (Scale::new(times, true), Scale::new(costs, log_cost))
+    }
+
+    /// Frontier points from the fastest to the slowest.
+    fn frontier(&self) -> Vec<&ParetoPoint> {
+        let mut frontier = self
+            .points
+            .iter()
+            .filter(|x| x.frontier)
+            .collect::<Vec<_>>();
+        frontier.sort_by(|a, b| a.time.total_cmp(&b.time));
+        frontier
+    }
+
+    /// Standalone SVG document: every method as a labelled dot, the frontier in red joined by
+    /// a staircase, the excluded methods listed in the corner.
+    pub fn render_svg(&self, title: &str) -> String {
+        const WIDTH: f64 = 760.0;
+        const HEIGHT: f64 = 480.0;
+        let (left, right, top, bottom) = (90.0, 30.0, 40.0, 60.0);
+        let (time, cost) = self.scales();
+        let x = |t: f64| left + time.fraction(t) * (WIDTH - left - right);
+        let y = |c: f64| HEIGHT - bottom - cost.fraction(c) * (HEIGHT - top - bottom);
+
+        let mut svg = String::new();
+        let mut line = |s: String| {
+            svg.push_str(&s);
+            svg.push('\n');
+        };
+        line(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="11">"#
+        ));
+        line(format!(
+            r#"<rect width="{WIDTH}" height="{HEIGHT}" fill="white"/>"#
+        ));
+        line(format!(
+            r#"<text x="{}" y="22" text-anchor="middle" font-size="14">{}</text>"#,
+            WIDTH / 2.0,
+            escape(title)
+        ));
+        let (x0, x1, y0, y1) = (left, WIDTH - right, top, HEIGHT - bottom);
+        line(format!(
+            r#"<path d="M{x0},{y0} V{y1} H{x1}" fill="none" stroke="black"/>"#
+        ));
+        for t in time.ticks() {
+            line(format!(
+                r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+                x(t),
+                y1 + 16.0,
+                format_time(t)
+            ));
+        }
+        for c in cost.ticks() {
+            line(format!(
+                r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                x0 - 6.0,
+                y(c) + 4.0,
+                format_float(c, self.digits)
+            ));
+        }
+        line(format!(
+            r#"<text x="{}" y="{}" text-anchor="middle">time (log scale)</text>"#,
+            (x0 + x1) / 2.0,
+            HEIGHT - 20.0
+        ));
+        line(format!(
+            r#"<text x="20" y="{}" text-anchor="middle" transform="rotate(-90 20 {})">best cost{}</text>"#,
+            (y0 + y1) / 2.0,
+            (y0 + y1) / 2.0,
+            if cost.log { " (log scale)" } else { "" }
+        ));
+
+        let frontier = self.frontier();
+        if frontier.len() > 1 {
+            let mut path = format!("M{},{}", x(frontier[0].time), y(frontier[0].best_cost));
+            for point in &frontier[1..] {
+                write!(path, " H{} V{}", x(point.time), y(point.best_cost)).unwrap();
+            }
+            line(format!(
+                r##"<path d="{path}" fill="none" stroke="#d62728" stroke-dasharray="4 3"/>"##
+            ));
+        }
+        for point in &self.points {
+            let color = if point.frontier { "#d62728" } else { "#7f7f7f" };
+            let (px, py) = (x(point.time), y(point.best_cost));
+            line(format!(
+                r#"<circle cx="{px}" cy="{py}" r="4" fill="{color}"><title>{}: {} in {}</title></circle>"#,
+                escape(&point.method),
+                format_float(point.best_cost, self.digits),
+                format_time(point.time)
+            ));
+            line(format!(
+                r#"<text x="{}" y="{}" fill="{color}">{}</text>"#,
+                px + 6.0,
+                py - 6.0,
+                escape(&point.method)
+            ));
+        }
+        for (i, (method, status)) in self.excluded.iter().enumerate() {
+            line(format!(
+                r##"<text x="{}" y="{}" text-anchor="end" fill="#7f7f7f">{} ({status})</text>"##,
+                x1,
+                y0 + 14.0 * (i + 1) as f64,
+                escape(method)
+            ));
+        }
+        line("</svg>".to_string());
+        svg
+    }
+
+    /// Text scatter of `width x height` cells: every method is drawn as its key from the legend
+    /// below the plot, where `*` marks the frontier.
+    pub fn render_ascii(&self, width: usize, height: usize) -> String {
+        const KEYS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let (time, cost) = self.scales();
+        let mut grid = vec![vec![' '; width]; height];
+        let cell = |fraction: f64, n: usize| (fraction * (n - 1) as f64).round() as usize;
+        let key = |i: usize| KEYS.get(i).map_or('?', |&x| x as char);
+        for (i, point) in self.points.iter().enumerate() {
+            let col = cell(time.fraction(point.time), width);
+            let row = height - 1 - cell(cost.fraction(point.best_cost), height);
+            grid[row][col] = key(i);
+        }
+
+        let (top, bottom) = (
+            format_float(cost.max(), self.digits),
+            format_float(cost.min(), self.digits),
+        );
+        let margin = top.len().max(bottom.len());
+        let mut out = String::new();
+        for (i, row) in grid.iter().enumerate() {
+            let label = match i {
+                0 => top.as_str(),
+                _ if i == height - 1 => bottom.as_str(),
+                _ => "",
+            };
+            let row = row.iter().collect::<String>();
+            writeln!(out, "{label:>margin$} |{}", row.trim_end()).unwrap();
+        }
+        writeln!(out, "{:>margin$} +{}", "", "-".repeat(width)).unwrap();
+        let (fastest, slowest) = (format_time(time.min()), format_time(time.max()));
+        let gap = (width + 1)
+            .saturating_sub(fastest.len() + slowest.len())
+            .max(1);
+        writeln!(
+            out,
+            "{:>margin$}  {fastest}{}{slowest}",
+            "",
+            " ".repeat(gap)
+        )
+        .unwrap();
+        let scale = if cost.log { "log" } else { "linear" };
+        writeln!(out, "Best cost ({scale} scale) against time (log scale):").unwrap();
+        for (i, point) in self.points.iter().enumerate() {
+            let mark = if point.frontier { '*' } else { ' ' };
+            writeln!(
+                out,
+                "  {} {mark} {}: {} in {}",
+                key(i),
+                point.method,
+                format_float(point.best_cost, self.digits),
+                format_time(point.time)
+            )
+            .unwrap();
+        }
+        for (method, status) in &self.excluded {
+            writeln!(out, "  off the plot: {method} ({status})").unwrap();
+        }
+        out
+    }
+}
+
+/// Maps values of one axis to `[0, 1]`, on a log scale when `log`. Zeros can't be drawn on a
+/// log scale, they sit a decade below the smallest positive value.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Scale {
+    pub(crate) log: bool,
+    floor: f64,
+    /// Range of the transformed values.
+    lo: f64,
+    hi: f64,
+}
+
+impl Scale {
+    pub(crate) fn new(values: impl Iterator<Item = f64> + Clone, log: bool) -> Self {
+        let positive = values.clone().filter(|&x| x > 0.0);
+        let smallest = positive.fold(f64::INFINITY, f64::min);
+        let has_zero = values.clone().any(|x| x <= 0.0);
+        let log = log && smallest.is_finite();
+        let floor = if has_zero { smallest / 10.0 } else { smallest };
+        let mut scale = Self {
+            log,
+            floor,
+            lo: 0.0,
+            hi: 0.0,
+        };
+        let transformed = values.map(|x| scale.transform(x));
+        let lo = transformed.clone().fold(f64::INFINITY, f64::min);
+        let hi = transformed.fold(f64::NEG_INFINITY, f64::max);
+        (scale.lo, scale.hi) = match (lo, hi) {
+            (lo, _) if !lo.is_finite() => (0.0, 1.0),
+            (lo, hi) if lo == hi => (lo - 0.5, hi + 0.5),
+            range => range,
+        };
+        scale
+    }
+
+    fn transform(&self, x: f64) -> f64 {
+        match self.log {
+            true => x.max(self.floor).log10(),
+            false => x,
+        }
+    }
+
+    fn untransform(&self, x: f64) -> f64 {
+        match self.log {
+            true => 10f64.powf(x),
+            false => x,
+        }
+    }
+
+    pub(crate) fn fraction(&self, x: f64) -> f64 {
+        (self.transform(x) - self.lo) / (self.hi - self.lo)
+    }
+
+    fn min(&self) -> f64 {
+        self.untransform(self.lo)
+    }
+
+    fn max(&self) -> f64 {
+        self.untransform(self.hi)
+    }
+
+    /// Every decade within the range on a log scale, at least both ends, otherwise 5 evenly
+    /// spaced values.
+    pub(crate) fn ticks(&self) -> Vec<f64> {
+        let decades = (self.lo.ceil() as i32..=self.hi.floor() as i32)
+            .map(|k| 10f64.powi(k))
+            .collect::<Vec<_>>();
+        match self.log && decades.len() >= 2 {
+            true => decades,
+            false => (0..5)
+                .map(|i| self.untransform(self.lo + (self.hi - self.lo) * i as f64 / 4.0))
+                .collect(),
+        }
+    }
+}
+
+fn format_time(secs: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(secs))
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(method: &str, cost: f64, millis: u64) -> Result {
+        Result::new(
+            "",
+            method,
+            cost,
+            Some(Duration::from_millis(millis)),
+            10,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_frontier() {
+        let results = [
+            row("Fast", 1.0, 1),
+            row("Slow and rough", 2.0, 50),
+            row("Accurate", 1e-9, 100),
+            row("Middle", 1e-3, 10),
+            row("Exact", 0.0, 200),
+            Result::failed("", "Failed", &"no hessian"),
+        ];
+        let plot = ParetoPlot::new(&results, false);
+        let frontier = plot
+            .frontier()
+            .iter()
+            .map(|x| x.method.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(frontier, ["Fast", "Middle", "Accurate", "Exact"]);
+        assert_eq!(
+            plot.excluded,
+            [("Failed".to_string(), "failed".to_string())]
+        );
+        // The slowest method is the best one when maximizing.
+        let plot = ParetoPlot::new(&results[..4], true);
+        let frontier = plot.frontier();
+        assert_eq!(frontier.len(), 2);
+        assert_eq!(frontier[1].method, "Slow and rough");
+    }
+
+    #[test]
+    fn test_render() {
+        let results = [
+            row("Fast", 1.0, 1),
+            row("Accurate & slow", 1e-9, 100),
+            row("Exact", 0.0, 200),
+            Result::failed("", "Failed", &"no hessian"),
+        ];
+        let plot = ParetoPlot::new(&results, false);
+        let svg = plot.render_svg("rosenbrock");
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains("Accurate &amp; slow"));
+        assert!(svg.contains("Failed (failed)"));
+        assert!(svg.contains("(log scale)</text>"));
+
+        let ascii = plot.render_ascii(40, 10);
+        let lines = ascii.lines().collect::<Vec<_>>();
+        // The fast and rough method top left, the exact one at the bottom of the log scale.
+        assert!(lines[0].ends_with("|a"), "{ascii}");
+        assert!(lines[9].ends_with('c'), "{ascii}");
+        assert!(ascii.contains("  a * Fast: 1 in 1.00ms"), "{ascii}");
+        assert!(ascii.contains("off the plot: Failed (failed)"));
+    }
+}
@@ -0,0 +1,173 @@
+use argmin::{
+    core::{ArgminError, CostFunction, Error, PopulationState, Problem, Solver, KV},
+    solver::particleswarm::Particle,
+};
+use ndarray::Array1;
+use rand::Rng;
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+/// Particle swarm optimization drawing its random numbers from a seedable generator.
+///
+/// Same canonical algorithm and default weights as argmin's `ParticleSwarm`, which draws its
+/// initial particles and every velocity update from `thread_rng` without any way to seed it, so
+/// its runs can't be reproduced. Passing a seeded initial population to it isn't enough, the
+/// updates would still differ.
+///
+/// The state only holds the best particle, the swarm itself is kept by the solver.
+#[derive(Debug, Clone)]
+pub struct SeededParticleSwarm {
+    bounds: (Array1<f64>, Array1<f64>),
+    num_particles: usize,
+    weight_inertia: f64,
+    weight_cognitive: f64,
+    weight_social: f64,
+    rng: Xoshiro256PlusPlus,
+    swarm: Vec<SwarmParticle>,
+}
+
+#[derive(Debug, Clone)]
+struct SwarmParticle {
+    position: Array1<f64>,
+    velocity: Array1<f64>,
+    best_position: Array1<f64>,
+    best_cost: f64,
+}
+
+impl SeededParticleSwarm {
+    pub fn new(bounds: (Array1<f64>, Array1<f64>), num_particles: usize) -> Self {
+        Self {
+            bounds,
+            num_particles,
+            weight_inertia: 1.0 / (2.0 * 2f64.ln()),
+            weight_cognitive: 0.5 + 2f64.ln(),
+            weight_social: 0.5 + 2f64.ln(),
+            rng: Xoshiro256PlusPlus::from_entropy(),
+            swarm: Vec::new(),
+        }
+    }
+
+    /// Reseeds the particles and their moves, making runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        self
+    }
+
+    /// Uniform sample between `0` and each coordinate of `to`.
+    fn towards(&mut self, to: &Array1<f64>) -> Array1<f64> {
+        to.mapv(|x| x * self.rng.gen::<f64>())
+    }
+
+    /// Uniform sample between `-delta` and `delta`.
+    fn symmetric(&mut self, delta: &Array1<f64>) -> Array1<f64> {
+        delta.mapv(|x| x * (2.0 * self.rng.gen::<f64>() - 1.0))
+    }
+
+    fn best(&self) -> Particle<Array1<f64>, f64> {
+        let best = self
+            .swarm
+            .iter()
+            .min_by(|a, b| a.best_cost.total_cmp(&b.best_cost))
+            .unwrap();
+        Particle::new(
+            best.best_position.clone(),
+            best.best_cost,
+            Array1::zeros(best.position.len()),
+        )
+    }
+}
+
+type SwarmState = PopulationState<Particle<Array1<f64>, f64>, f64>;
+
+impl<O> Solver<O, SwarmState> for SeededParticleSwarm
+where
+    O: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    const NAME: &'static str = "Particle Swarm Optimization (seeded)";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: SwarmState,
+    ) -> Result<(SwarmState, Option<KV>), Error> {
+        if self.num_particles == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "`SeededParticleSwarm` requires at least one particle".to_string(),
+            }
+            .into());
+        }
+        let (lower, upper) = self.bounds.clone();
+        let delta = &upper - &lower;
+        self.swarm = (0..self.num_particles)
+            .map(|_| {
+                let position = &lower + &self.towards(&delta);
+                let velocity = self.symmetric(&delta);
+                let cost = problem.cost(&position)?;
+                Ok(SwarmParticle {
+                    best_position: position.clone(),
+                    position,
+                    velocity,
+                    best_cost: cost,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let best = self.best();
+        Ok((state.cost(best.cost).individual(best), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: SwarmState,
+    ) -> Result<(SwarmState, Option<KV>), Error> {
+        let global = self.best().position;
+        let (lower, upper) = self.bounds.clone();
+        let mut swarm = std::mem::take(&mut self.swarm);
+        for p in &mut swarm {
+            let pull_to_optimum = self.towards(&(&p.best_position - &p.position));
+            let pull_to_global = self.towards(&(&global - &p.position));
+            p.velocity = self.weight_inertia * &p.velocity
+                + self.weight_cognitive * pull_to_optimum
+                + self.weight_social * pull_to_global;
+            let mut position = &p.position + &p.velocity;
+            position.zip_mut_with(&lower, |x, l| *x = x.max(*l));
+            position.zip_mut_with(&upper, |x, u| *x = x.min(*u));
+            p.position = position;
+            let cost = problem.cost(&p.position)?;
+            if cost < p.best_cost {
+                p.best_position = p.position.clone();
+                p.best_cost = cost;
+            }
+        }
+        self.swarm = swarm;
+        let best = self.best();
+        Ok((state.cost(best.cost).individual(best), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::{Executor, State};
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    fn run(seed: u64) -> (f64, Vec<f64>) {
+        let problem = RosenbrockND::default();
+        let bounds = (problem.lower_bound().clone(), problem.upper_bound().clone());
+        let solver = SeededParticleSwarm::new(bounds, 40).with_seed(seed);
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.max_iters(50))
+            .run()
+            .unwrap();
+        let best = res.state.get_best_param().unwrap();
+        (res.state.get_best_cost(), best.position.to_vec())
+    }
+
+    #[test]
+    fn test_seeded_runs_match() {
+        let (cost, param) = run(42);
+        assert_eq!((cost, param.clone()), run(42));
+        assert_ne!(param, run(7).1);
+        assert!(cost < 1e-2, "{cost}");
+    }
+}
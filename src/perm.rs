@@ -0,0 +1,142 @@
+use argmin::core::{CostFunction, Gradient};
+use ndarray::Array1;
+
+/// The Perm function (also known as Perm 0, d, β) is defined as:
+/// $ f(x) = \sum_{i=1}^{d} \left( \sum_{j=1}^{d} (j + \beta)(x_j^i - j^{-i}) \right)^2 $
+///
+/// It is evaluated on $[-d, d]^d$ with its global minimum $f = 0$ at $(1, 1/2, \dots, 1/d)$. The
+/// smaller `beta` is, the worse the conditioning around the minimum gets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Perm {
+    beta: f64,
+    dim: usize,
+}
+
+impl Perm {
+    pub fn new(beta: f64, dim: usize) -> Self {
+        Self { beta, dim }
+    }
+
+    /// Same function with a different `beta`.
+    pub fn with_beta(mut self, beta: f64) -> Self {
+        self.beta = beta;
+        self
+    }
+
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        let d = self.dim as f64;
+        (
+            Array1::from_elem(self.dim, -d),
+            Array1::from_elem(self.dim, d),
+        )
+    }
+
+    /// The global minimizer $(1, 1/2, \dots, 1/d)$.
+    pub fn optimum(&self) -> Array1<f64> {
+        Array1::from_shape_fn(self.dim, |j| 1.0 / (j + 1) as f64)
+    }
+
+    /// Inner sums $\sum_j (j + \beta)(x_j^i - j^{-i})$ for every $i$.
+    fn inner_sums(&self, param: &Array1<f64>) -> Vec<f64> {
+        (1..=param.len() as i32)
+            .map(|i| {
+                param
+                    .iter()
+                    .enumerate()
+                    .map(|(j, x)| {
+                        let j = (j + 1) as f64;
+                        (j + self.beta) * (x.powi(i) - (1.0 / j).powi(i))
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+impl Default for Perm {
+    fn default() -> Self {
+        Self::new(10.0, 2)
+    }
+}
+
+impl CostFunction for Perm {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        Ok(self.inner_sums(param).iter().map(|s| s.powi(2)).sum())
+    }
+}
+
+impl Gradient for Perm {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        let sums = self.inner_sums(param);
+        let gradient = param
+            .iter()
+            .enumerate()
+            .map(|(k, x)| {
+                let weight = (k + 1) as f64 + self.beta;
+                sums.iter()
+                    .enumerate()
+                    .map(|(i, s)| {
+                        let i = (i + 1) as i32;
+                        2.0 * s * weight * i as f64 * x.powi(i - 1)
+                    })
+                    .sum()
+            })
+            .collect();
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_optimum() {
+        for dim in 1..=6 {
+            for beta in [0.5, 10.0] {
+                let f = Perm::new(beta, dim);
+                let optimum = f.optimum();
+                assert!(f.cost(&optimum).unwrap().abs() < 1e-15, "{dim} {beta}");
+                assert!(f
+                    .gradient(&optimum)
+                    .unwrap()
+                    .iter()
+                    .all(|g| g.abs() < 1e-12));
+            }
+        }
+        assert_eq!(Perm::new(10.0, 3).optimum(), array![1.0, 0.5, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = Perm::new(0.5, 3);
+        let param = array![0.3, -1.2, 2.0];
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-6;
+        for k in 0..param.len() {
+            let mut step = param.clone();
+            step[k] += h;
+            let diff = (f.cost(&step).unwrap() - f.cost(&param).unwrap()) / h;
+            assert!(
+                (diff - gradient[k]).abs() < 1e-3 * gradient[k].abs().max(1.0),
+                "{k}"
+            );
+        }
+    }
+}
@@ -0,0 +1,573 @@
+use std::{fmt, str::FromStr};
+
+use argmin::{
+    core::{ArgminError, CostFunction, Error, Gradient, Hessian, Jacobian, Operator},
+    solver::simulatedannealing::Anneal,
+};
+use ndarray::{Array1, Array2};
+
+use crate::{
+    bohachevsky::{Bohachevsky, BohachevskyVariant},
+    bounds::BoundedProblem,
+    caching::CachingProblem,
+    ellipsoid::{Ellipsoid, EllipsoidKind},
+    hartmann::Hartmann,
+    michalewicz::Michalewicz,
+    noisy::NoisyProblem,
+    perm::Perm,
+    profile::Profiled,
+    qing::Qing,
+    rosenbrock_residuals::RosenbrockResiduals,
+    salomon::Salomon,
+    shekel::Shekel,
+    whitley::Whitley,
+    RosenbrockND,
+};
+
+/// Test problems available to the solver comparison, selected by name with `--problem`.
+///
+/// Methods a problem can't provide (e.g. the hessian of a gradient-only function) return an
+/// error, so the corresponding solvers fail instead of silently using something else.
+/// [`SolverRun`] checks [`Derivatives`] first, skipping them altogether.
+///
+/// Like every problem of the crate it is `Clone + Send + Sync`, `--parallel` shares it across
+/// threads.
+///
+/// [`SolverRun`]: crate::runner::SolverRun
+#[derive(Debug, Clone)]
+pub enum Problem {
+    Rosenbrock(RosenbrockND),
+    Bohachevsky(Bohachevsky),
+    Michalewicz(Michalewicz),
+    Perm(Perm),
+    Hartmann(Hartmann),
+    Ellipsoid(Ellipsoid),
+    Shekel(Shekel),
+    Whitley(Whitley),
+    Qing(Qing),
+    Salomon(Salomon),
+}
+
+impl Problem {
+    pub const NAMES: [&'static str; 15] = [
+        "rosenbrock",
+        "bohachevsky-1",
+        "bohachevsky-2",
+        "bohachevsky-3",
+        "michalewicz",
+        "perm",
+        "hartmann-3",
+        "hartmann-6",
+        "ellipsoid",
+        "cigar",
+        "discus",
+        "shekel",
+        "whitley",
+        "qing",
+        "salomon",
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Problem::Rosenbrock(_) => "rosenbrock",
+            Problem::Bohachevsky(f) => match f.variant() {
+                BohachevskyVariant::One => "bohachevsky-1",
+                BohachevskyVariant::Two => "bohachevsky-2",
+                BohachevskyVariant::Three => "bohachevsky-3",
+            },
+            Problem::Michalewicz(_) => "michalewicz",
+            Problem::Perm(_) => "perm",
+            Problem::Hartmann(f) => match f.dim() {
+                3 => "hartmann-3",
+                _ => "hartmann-6",
+            },
+            Problem::Ellipsoid(f) => match f.kind() {
+                EllipsoidKind::Ellipsoid => "ellipsoid",
+                EllipsoidKind::Cigar => "cigar",
+                EllipsoidKind::Discus => "discus",
+            },
+            Problem::Shekel(_) => "shekel",
+            Problem::Whitley(_) => "whitley",
+            Problem::Qing(_) => "qing",
+            Problem::Salomon(_) => "salomon",
+        }
+    }
+
+    /// Short explanation of the problem for `--explain`: its shape, where the optimum is and
+    /// what makes it hard.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Problem::Rosenbrock(_) => {
+                "Unimodal (multimodal from 4-D on, with a single extra local minimum) with its \
+                 global minimum f = 0 at (1, ..., 1). The minimum sits in a long, narrow, curved \
+                 valley: finding the valley is easy, following it is slow for steepest descent \
+                 while Newton-type methods walk along it in a few dozen steps."
+            }
+            Problem::Bohachevsky(_) => {
+                "A bowl with cosine ripples on top, mildly multimodal, with its global minimum \
+                 f = 0 at (0, 0). Gradient methods started far away descend the bowl quickly but \
+                 may stop in a ripple near the bottom, global methods do well."
+            }
+            Problem::Michalewicz(_) => {
+                "Highly multimodal with d! local minima and steep, narrow valleys controlled by \
+                 `m`, its global minimum is only known numerically (about -1.8013 in 2-D). The \
+                 plateaus between valleys give no gradient information, a hard test for every \
+                 method. No gradient is provided."
+            }
+            Problem::Perm(_) => {
+                "Unimodal but badly conditioned near its global minimum f = 0 at \
+                 (1, 1/2, ..., 1/d), the more so the smaller `beta` and the larger d. Costs \
+                 span many orders of magnitude over the box, line searches often struggle."
+            }
+            Problem::Hartmann(_) => {
+                "Multimodal on the unit cube, with 4 local minima and its global minimum only \
+                 known numerically (-3.86278 in 3-D, -3.32237 in 6-D). Local methods find the \
+                 basin they start in, the global one depends on the starting point."
+            }
+            Problem::Ellipsoid(f) => match f.kind() {
+                EllipsoidKind::Ellipsoid => {
+                    "Convex quadratic with its minimum f = 0 at the origin, axis scales growing \
+                     geometrically up to the condition number (1e6 by default). Newton solves it \
+                     in one step, first-order methods slow down with the conditioning."
+                }
+                EllipsoidKind::Cigar => {
+                    "Convex quadratic with its minimum f = 0 at the origin, one flat axis and \
+                     every other one steeper by the condition number (1e6 by default): a long \
+                     thin valley along the first axis, slow for steepest descent."
+                }
+                EllipsoidKind::Discus => {
+                    "Convex quadratic with its minimum f = 0 at the origin, one axis steeper than \
+                     every other one by the condition number (1e6 by default). Steepest descent \
+                     zig-zags across the steep axis, second-order methods don't care."
+                }
+            },
+            Problem::Shekel(_) => {
+                "Multimodal on [0, 10]^4 with `m` local minima (10 by default), each a sharp \
+                 well around flat ground, the deepest near (4, 4, 4, 4) and only known \
+                 numerically (-10.5364 for m = 10). Local methods stop in the nearest well or \
+                 stall on the plateau, global ones have to find the right well."
+            }
+            Problem::Whitley(_) => {
+                "Extremely rugged and non-separable, a Griewank-style cosine over every pairwise \
+                 Rosenbrock term, with its global minimum f = 0 at (1, ..., 1). The Rosenbrock \
+                 valley is full of ripples, each a local minimum, so gradient methods stop \
+                 almost where they start: an anchor for global methods."
+            }
+            Problem::Qing(_) => {
+                "Separable, with a global minimum f = 0 wherever x_i = +-sqrt(i): 2^d of them, \
+                 one per orthant. Every method finds a global minimum, which one depends on the \
+                 signs of the starting point. `--check-optimum` lists them up to 16 dimensions."
+            }
+            Problem::Salomon(_) => {
+                "Radially symmetric, with its global minimum f = 0 at the origin inside rings of \
+                 local minima at about every integer radius, each 0.1 above the one inside it. \
+                 Gradient methods stop on the ring they start next to, annealing has to hop \
+                 inwards one ring at a time."
+            }
+        }
+    }
+
+    /// Box in which the problem is usually evaluated, as `(lower, upper)`.
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        match self {
+            Problem::Rosenbrock(f) => (f.lower_bound().clone(), f.upper_bound().clone()),
+            Problem::Bohachevsky(f) => f.bounds(),
+            Problem::Michalewicz(f) => (f.lower_bound().clone(), f.upper_bound().clone()),
+            Problem::Perm(f) => f.bounds(),
+            Problem::Hartmann(f) => f.bounds(),
+            Problem::Ellipsoid(f) => f.bounds(),
+            Problem::Shekel(f) => f.bounds(),
+            Problem::Whitley(f) => f.bounds(),
+            Problem::Qing(f) => f.bounds(),
+            Problem::Salomon(f) => (f.lower_bound().clone(), f.upper_bound().clone()),
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        match self {
+            Problem::Rosenbrock(f) => f.dim(),
+            Problem::Bohachevsky(_) => 2,
+            Problem::Michalewicz(f) => f.dim(),
+            Problem::Perm(f) => f.dim(),
+            Problem::Hartmann(f) => f.dim(),
+            Problem::Ellipsoid(f) => f.dim(),
+            Problem::Shekel(f) => f.dim(),
+            Problem::Whitley(f) => f.dim(),
+            Problem::Qing(f) => f.dim(),
+            Problem::Salomon(f) => f.dim(),
+        }
+    }
+
+    /// Same problem in `dim` dimensions, fails for problems with a fixed dimension.
+    pub fn with_dim(self, dim: usize) -> Result<Self, String> {
+        match self {
+            Problem::Rosenbrock(_) if dim >= 2 => {
+                Ok(Problem::Rosenbrock(RosenbrockND::with_dim(dim)))
+            }
+            Problem::Michalewicz(f) if dim >= 1 => {
+                Ok(Problem::Michalewicz(Michalewicz::new(f.m(), dim)))
+            }
+            Problem::Perm(f) if dim >= 1 => Ok(Problem::Perm(Perm::new(f.beta(), dim))),
+            Problem::Whitley(_) if dim >= 1 => Ok(Problem::Whitley(Whitley::new(dim))),
+            Problem::Qing(_) if dim >= 1 => Ok(Problem::Qing(Qing::new(dim))),
+            Problem::Salomon(_) if dim >= 1 => Ok(Problem::Salomon(Salomon::new(dim))),
+            Problem::Hartmann(_) if Hartmann::new(dim).is_some() => {
+                Ok(Problem::Hartmann(Hartmann::new(dim).unwrap()))
+            }
+            Problem::Ellipsoid(f) if dim >= 2 => Ok(Problem::Ellipsoid(
+                Ellipsoid::new(f.kind(), dim).with_condition(f.condition()),
+            )),
+            problem if problem.dim() == dim => Ok(problem),
+            problem => Err(format!(
+                "`{problem}` is only defined in {} dimensions, got `--dim {dim}`",
+                problem.dim()
+            )),
+        }
+    }
+
+    /// Seeds the random number generator of problems with a random `anneal` move.
+    pub fn with_seed(self, seed: u64) -> Self {
+        match self {
+            Problem::Rosenbrock(f) => Problem::Rosenbrock(f.with_seed(seed)),
+            Problem::Michalewicz(f) => Problem::Michalewicz(f.with_seed(seed)),
+            Problem::Salomon(f) => Problem::Salomon(f.with_seed(seed)),
+            problem => problem,
+        }
+    }
+
+    /// Scales the `anneal` moves of the problems having one, see
+    /// [`DEFAULT_ANNEAL_STEP`](crate::annealing::DEFAULT_ANNEAL_STEP).
+    pub fn with_anneal_step(self, step: f64) -> Self {
+        match self {
+            Problem::Rosenbrock(f) => Problem::Rosenbrock(f.with_anneal_step(step)),
+            Problem::Michalewicz(f) => Problem::Michalewicz(f.with_anneal_step(step)),
+            Problem::Salomon(f) => Problem::Salomon(f.with_anneal_step(step)),
+            problem => problem,
+        }
+    }
+
+    /// Least-squares form of the problem, for Gauss-Newton style solvers.
+    pub fn residuals(&self) -> Option<RosenbrockResiduals> {
+        match self {
+            Problem::Rosenbrock(f) => Some(f.into()),
+            Problem::Bohachevsky(_)
+            | Problem::Michalewicz(_)
+            | Problem::Perm(_)
+            | Problem::Hartmann(_)
+            | Problem::Ellipsoid(_)
+            | Problem::Shekel(_)
+            | Problem::Whitley(_)
+            | Problem::Qing(_)
+            | Problem::Salomon(_) => None,
+        }
+    }
+
+    fn not_implemented(&self, what: &str) -> Error {
+        ArgminError::NotImplemented {
+            text: format!("`{}` does not provide {what}", self.name()),
+        }
+        .into()
+    }
+}
+
+/// Derivatives a problem provides, so methods needing others (see [`Method::needs_hessian`])
+/// can be skipped instead of failing on their first evaluation.
+///
+/// [`Method::needs_hessian`]: crate::Method::needs_hessian
+pub trait Derivatives {
+    fn has_gradient(&self) -> bool;
+
+    fn has_hessian(&self) -> bool;
+
+    /// Whether `Anneal` gives a move, which simulated annealing needs.
+    fn has_anneal(&self) -> bool;
+}
+
+impl Derivatives for RosenbrockND {
+    fn has_gradient(&self) -> bool {
+        true
+    }
+
+    fn has_hessian(&self) -> bool {
+        true
+    }
+
+    fn has_anneal(&self) -> bool {
+        true
+    }
+}
+
+impl Derivatives for Problem {
+    fn has_gradient(&self) -> bool {
+        match self {
+            Problem::Rosenbrock(_)
+            | Problem::Bohachevsky(_)
+            | Problem::Perm(_)
+            | Problem::Hartmann(_)
+            | Problem::Ellipsoid(_)
+            | Problem::Shekel(_)
+            | Problem::Whitley(_)
+            | Problem::Qing(_)
+            | Problem::Salomon(_) => true,
+            Problem::Michalewicz(_) => false,
+        }
+    }
+
+    fn has_hessian(&self) -> bool {
+        match self {
+            Problem::Rosenbrock(_) | Problem::Ellipsoid(_) => true,
+            Problem::Bohachevsky(_)
+            | Problem::Michalewicz(_)
+            | Problem::Perm(_)
+            | Problem::Hartmann(_)
+            | Problem::Shekel(_)
+            | Problem::Whitley(_)
+            | Problem::Qing(_)
+            | Problem::Salomon(_) => false,
+        }
+    }
+
+    fn has_anneal(&self) -> bool {
+        match self {
+            Problem::Rosenbrock(_) | Problem::Michalewicz(_) | Problem::Salomon(_) => true,
+            Problem::Bohachevsky(_)
+            | Problem::Perm(_)
+            | Problem::Hartmann(_)
+            | Problem::Ellipsoid(_)
+            | Problem::Shekel(_)
+            | Problem::Whitley(_)
+            | Problem::Qing(_) => false,
+        }
+    }
+}
+
+impl<P: Derivatives> Derivatives for BoundedProblem<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner().has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner().has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner().has_anneal()
+    }
+}
+
+impl<P: Derivatives> Derivatives for CachingProblem<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner().has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner().has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner().has_anneal()
+    }
+}
+
+impl<P: Derivatives> Derivatives for NoisyProblem<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner().has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner().has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner().has_anneal()
+    }
+}
+
+impl<P: Derivatives> Derivatives for Profiled<P> {
+    fn has_gradient(&self) -> bool {
+        self.inner().has_gradient()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.inner().has_hessian()
+    }
+
+    fn has_anneal(&self) -> bool {
+        self.inner().has_anneal()
+    }
+}
+
+impl FromStr for Problem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rosenbrock" => Ok(Problem::Rosenbrock(RosenbrockND::default())),
+            "bohachevsky-1" => Ok(Problem::Bohachevsky(Bohachevsky::new(
+                BohachevskyVariant::One,
+            ))),
+            "bohachevsky-2" => Ok(Problem::Bohachevsky(Bohachevsky::new(
+                BohachevskyVariant::Two,
+            ))),
+            "bohachevsky-3" => Ok(Problem::Bohachevsky(Bohachevsky::new(
+                BohachevskyVariant::Three,
+            ))),
+            "michalewicz" => Ok(Problem::Michalewicz(Michalewicz::default())),
+            "perm" => Ok(Problem::Perm(Perm::default())),
+            "hartmann-3" => Ok(Problem::Hartmann(Hartmann::new(3).unwrap())),
+            "hartmann-6" => Ok(Problem::Hartmann(Hartmann::new(6).unwrap())),
+            "ellipsoid" => Ok(Problem::Ellipsoid(Ellipsoid::new(
+                EllipsoidKind::Ellipsoid,
+                2,
+            ))),
+            "cigar" => Ok(Problem::Ellipsoid(Ellipsoid::new(EllipsoidKind::Cigar, 2))),
+            "discus" => Ok(Problem::Ellipsoid(Ellipsoid::new(EllipsoidKind::Discus, 2))),
+            "shekel" => Ok(Problem::Shekel(Shekel::default())),
+            "whitley" => Ok(Problem::Whitley(Whitley::default())),
+            "qing" => Ok(Problem::Qing(Qing::default())),
+            "salomon" => Ok(Problem::Salomon(Salomon::default())),
+            _ => Err(format!(
+                "unknown problem `{s}`, expected one of: {}",
+                Problem::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl CostFunction for Problem {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        match self {
+            Problem::Rosenbrock(f) => f.cost(param),
+            Problem::Bohachevsky(f) => f.cost(param),
+            Problem::Michalewicz(f) => f.cost(param),
+            Problem::Perm(f) => f.cost(param),
+            Problem::Hartmann(f) => f.cost(param),
+            Problem::Ellipsoid(f) => f.cost(param),
+            Problem::Shekel(f) => f.cost(param),
+            Problem::Whitley(f) => f.cost(param),
+            Problem::Qing(f) => f.cost(param),
+            Problem::Salomon(f) => f.cost(param),
+        }
+    }
+}
+
+impl Gradient for Problem {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        match self {
+            Problem::Rosenbrock(f) => f.gradient(param),
+            Problem::Bohachevsky(f) => f.gradient(param),
+            Problem::Perm(f) => f.gradient(param),
+            Problem::Hartmann(f) => f.gradient(param),
+            Problem::Ellipsoid(f) => f.gradient(param),
+            Problem::Shekel(f) => f.gradient(param),
+            Problem::Whitley(f) => f.gradient(param),
+            Problem::Qing(f) => f.gradient(param),
+            Problem::Salomon(f) => f.gradient(param),
+            Problem::Michalewicz(_) => Err(self.not_implemented("a gradient")),
+        }
+    }
+}
+
+impl Hessian for Problem {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        match self {
+            Problem::Rosenbrock(f) => f.hessian(param),
+            Problem::Ellipsoid(f) => f.hessian(param),
+            Problem::Bohachevsky(_)
+            | Problem::Michalewicz(_)
+            | Problem::Perm(_)
+            | Problem::Hartmann(_)
+            | Problem::Shekel(_)
+            | Problem::Whitley(_)
+            | Problem::Qing(_)
+            | Problem::Salomon(_) => Err(self.not_implemented("a hessian")),
+        }
+    }
+}
+
+impl Operator for Problem {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        match self.residuals() {
+            Some(f) => f.apply(param),
+            None => Err(self.not_implemented("residuals")),
+        }
+    }
+}
+
+impl Jacobian for Problem {
+    type Param = Array1<f64>;
+    type Jacobian = Array2<f64>;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        match self.residuals() {
+            Some(f) => f.jacobian(param),
+            None => Err(self.not_implemented("a jacobian")),
+        }
+    }
+}
+
+impl Anneal for Problem {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+    type Float = f64;
+
+    fn anneal(&self, param: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+        match self {
+            Problem::Rosenbrock(f) => f.anneal(param, temp),
+            Problem::Michalewicz(f) => f.anneal(param, temp),
+            Problem::Salomon(f) => f.anneal(param, temp),
+            Problem::Bohachevsky(_)
+            | Problem::Perm(_)
+            | Problem::Hartmann(_)
+            | Problem::Ellipsoid(_)
+            | Problem::Shekel(_)
+            | Problem::Whitley(_)
+            | Problem::Qing(_) => Err(self.not_implemented("an anneal move")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        for name in Problem::NAMES {
+            let problem = name.parse::<Problem>().unwrap();
+            assert_eq!(problem.to_string(), name);
+            let (lower, upper) = problem.bounds();
+            assert_eq!(lower.len(), problem.dim());
+            assert_eq!(upper.len(), problem.dim());
+            assert!(!problem.description().is_empty());
+        }
+        assert!("sphere-42".parse::<Problem>().is_err());
+    }
+
+    #[test]
+    fn test_with_dim() {
+        let problem = "rosenbrock".parse::<Problem>().unwrap();
+        assert_eq!(problem.with_dim(5).unwrap().dim(), 5);
+        let problem = "bohachevsky-1".parse::<Problem>().unwrap();
+        assert!(problem.clone().with_dim(2).is_ok());
+        assert!(problem.with_dim(3).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use argmin::{
+    core::{CostFunction, Gradient, Hessian, Jacobian, Operator},
+    solver::simulatedannealing::Anneal,
+};
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::results;
+
+/// Time spent evaluating each part of a problem, accumulated over the runs using it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    pub cost: Duration,
+    pub gradient: Duration,
+    pub hessian: Duration,
+    /// Residuals and their jacobian, evaluated by least-squares methods.
+    pub residuals: Duration,
+    pub anneal: Duration,
+    /// Total time of the runs, evaluations included.
+    pub total: Duration,
+    pub runs: u32,
+}
+
+impl Profile {
+    /// Records a finished run lasting `duration`.
+    pub fn add_run(&mut self, duration: Duration) {
+        self.total += duration;
+        self.runs += 1;
+    }
+
+    /// Time spent evaluating the problem.
+    pub fn evals(&self) -> Duration {
+        self.cost + self.gradient + self.hessian + self.residuals + self.anneal
+    }
+
+    /// Time spent in the solver itself (and argmin around it).
+    pub fn overhead(&self) -> Duration {
+        self.total.saturating_sub(self.evals())
+    }
+}
+
+/// Wraps a problem to time every evaluation into a shared [`Profile`], forwarding untouched
+/// without one.
+#[derive(Debug, Clone)]
+pub struct Profiled<P> {
+    inner: P,
+    profile: Option<Arc<Mutex<Profile>>>,
+}
+
+impl<P> Profiled<P> {
+    pub fn new(inner: P, profile: Option<Arc<Mutex<Profile>>>) -> Self {
+        Self { inner, profile }
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Runs `eval`, adding its time to the `part` of the profile.
+    fn timed<T>(&self, part: fn(&mut Profile) -> &mut Duration, eval: impl FnOnce() -> T) -> T {
+        let Some(profile) = &self.profile else {
+            return eval();
+        };
+        let start = Instant::now();
+        let out = eval();
+        *part(&mut profile.lock().unwrap()) += start.elapsed();
+        out
+    }
+}
+
+impl<P: CostFunction> CostFunction for Profiled<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        self.timed(|x| &mut x.cost, || self.inner.cost(param))
+    }
+}
+
+impl<P: Gradient> Gradient for Profiled<P> {
+    type Param = P::Param;
+    type Gradient = P::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        self.timed(|x| &mut x.gradient, || self.inner.gradient(param))
+    }
+}
+
+impl<P: Hessian> Hessian for Profiled<P> {
+    type Param = P::Param;
+    type Hessian = P::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, argmin::core::Error> {
+        self.timed(|x| &mut x.hessian, || self.inner.hessian(param))
+    }
+}
+
+impl<P: Operator> Operator for Profiled<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        self.timed(|x| &mut x.residuals, || self.inner.apply(param))
+    }
+}
+
+impl<P: Jacobian> Jacobian for Profiled<P> {
+    type Param = P::Param;
+    type Jacobian = P::Jacobian;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, argmin::core::Error> {
+        self.timed(|x| &mut x.residuals, || self.inner.jacobian(param))
+    }
+}
+
+impl<P: Anneal> Anneal for Profiled<P> {
+    type Param = P::Param;
+    type Output = P::Output;
+    type Float = P::Float;
+
+    fn anneal(
+        &self,
+        param: &Self::Param,
+        temp: Self::Float,
+    ) -> Result<Self::Output, argmin::core::Error> {
+        self.timed(|x| &mut x.anneal, || self.inner.anneal(param, temp))
+    }
+}
+
+/// Where the time of a run went, averaged over its repeats.
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+#[tabled(rename_all = "Pascal")]
+pub struct ProfileRow {
+    pub family: String,
+    pub method: String,
+    #[tabled(display_with = "display_duration")]
+    pub total: Duration,
+    #[tabled(display_with = "display_duration")]
+    pub cost: Duration,
+    #[tabled(display_with = "display_duration")]
+    pub gradient: Duration,
+    #[tabled(display_with = "display_duration")]
+    pub hessian: Duration,
+    #[tabled(display_with = "display_duration")]
+    pub residuals: Duration,
+    #[tabled(display_with = "display_duration")]
+    pub anneal: Duration,
+    #[tabled(display_with = "display_duration")]
+    pub overhead: Duration,
+    /// Fraction of the total time spent evaluating the problem.
+    #[tabled(display_with = "display_share")]
+    pub eval_share: Option<f64>,
+}
+
+impl ProfileRow {
+    pub fn new(result: &results::Result, profile: &Profile) -> Self {
+        let runs = profile.runs.max(1);
+        let total = profile.total / runs;
+        let evals = profile.evals() / runs;
+        Self {
+            family: result.family.clone(),
+            method: result.method.clone(),
+            total,
+            cost: profile.cost / runs,
+            gradient: profile.gradient / runs,
+            hessian: profile.hessian / runs,
+            residuals: profile.residuals / runs,
+            anneal: profile.anneal / runs,
+            overhead: profile.overhead() / runs,
+            eval_share: (!total.is_zero())
+                .then(|| (evals.as_secs_f64() / total.as_secs_f64()).min(1.0)),
+        }
+    }
+}
+
+fn display_duration(duration: &Duration) -> String {
+    match duration.is_zero() {
+        true => "-".to_string(),
+        false => format!("{duration:?}"),
+    }
+}
+
+fn display_share(share: &Option<f64>) -> String {
+    match share {
+        Some(x) => format!("{:.1}%", 100.0 * x),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::Executor,
+        solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch},
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    #[test]
+    fn test_profile() {
+        let profile = Arc::new(Mutex::new(Profile::default()));
+        let problem = Profiled::new(RosenbrockND::default(), Some(profile.clone()));
+        let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+        let start = Instant::now();
+        Executor::new(problem, solver)
+            .configure(|state| state.param(array![-1.2, 1.0]).max_iters(20))
+            .run()
+            .unwrap();
+        profile.lock().unwrap().add_run(start.elapsed());
+
+        let profile = profile.lock().unwrap().clone();
+        assert!(!profile.cost.is_zero() && !profile.gradient.is_zero());
+        assert!(profile.hessian.is_zero() && profile.anneal.is_zero());
+        assert!(profile.evals() <= profile.total);
+        let row = ProfileRow::new(&results::Result::new("", "x", 0.0, None, 0, None), &profile);
+        assert_eq!(row.overhead + profile.evals(), row.total);
+        assert!(row.eval_share.unwrap() > 0.0);
+    }
+}
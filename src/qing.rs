@@ -0,0 +1,118 @@
+use argmin::core::{CostFunction, Gradient};
+use ndarray::Array1;
+
+/// Largest dimension whose $2^d$ global minimizers are listed by its
+/// [`KnownOptimum`](crate::optimum::KnownOptimum) impl, there are too many to list above it.
+pub const MAX_OPTIMA_DIM: usize = 16;
+
+/// The Qing function $ f(x) = \sum_{i=1}^{d} (x_i^2 - i)^2 $.
+///
+/// It is evaluated on $[-500, 500]^d$ with its global minimum $f = 0$ wherever
+/// $x_i = \pm\sqrt{i}$. Separable, but every coordinate has two optimal values, so there are
+/// $2^d$ global minimizers: local methods usually end in the one of the orthant they start in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Qing {
+    dim: usize,
+}
+
+impl Qing {
+    pub fn new(dim: usize) -> Self {
+        Self { dim }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (
+            Array1::from_elem(self.dim, -500.0),
+            Array1::from_elem(self.dim, 500.0),
+        )
+    }
+
+    /// The global minimizer $(\sqrt{1}, \dots, \sqrt{d})$ with every coordinate positive.
+    pub fn optimum(&self) -> Array1<f64> {
+        Array1::from_shape_fn(self.dim, |i| ((i + 1) as f64).sqrt())
+    }
+}
+
+impl Default for Qing {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl CostFunction for Qing {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let cost = param
+            .iter()
+            .zip(1..)
+            .map(|(x, i)| (x.powi(2) - i as f64).powi(2))
+            .sum();
+        Ok(cost)
+    }
+}
+
+impl Gradient for Qing {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        let gradient = param
+            .iter()
+            .zip(1..)
+            .map(|(x, i)| 4.0 * x * (x.powi(2) - i as f64))
+            .collect();
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::optimum::KnownOptimum;
+
+    #[test]
+    fn test_optimum() {
+        for dim in 1..=6 {
+            let f = Qing::new(dim);
+            let optima = f.optima();
+            assert_eq!(optima.len(), 1 << dim);
+            for optimum in optima {
+                assert!(f.cost(&optimum).unwrap().abs() < 1e-12, "{optimum}");
+                assert!(f
+                    .gradient(&optimum)
+                    .unwrap()
+                    .iter()
+                    .all(|g| g.abs() < 1e-12));
+            }
+        }
+        assert_eq!(Qing::new(3).cost(&array![0.0, 0.0, 0.0]).unwrap(), 14.0);
+        assert!(Qing::new(MAX_OPTIMA_DIM + 1).optima().is_empty());
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = Qing::new(3);
+        let param = array![0.3, -1.4, 2.6];
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-7;
+        for k in 0..param.len() {
+            let (mut forward, mut backward) = (param.clone(), param.clone());
+            forward[k] += h;
+            backward[k] -= h;
+            let diff = (f.cost(&forward).unwrap() - f.cost(&backward).unwrap()) / (2.0 * h);
+            assert!(
+                (diff - gradient[k]).abs() < 1e-4 * gradient[k].abs().max(1.0),
+                "{k}: {diff} vs {}",
+                gradient[k]
+            );
+        }
+    }
+}
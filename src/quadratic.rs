@@ -0,0 +1,123 @@
+use argmin::core::{CostFunction, Error, Gradient, Hessian};
+use argmin_math::ArgminInv;
+use ndarray::{Array1, Array2};
+
+/// Convex quadratic $ f(x) = \frac{1}{2} x^T Q x - b^T x $ with a symmetric positive definite
+/// `q`, minimized at the solution of $ Q x = b $.
+///
+/// The conditioning of `q` is all that makes it hard, see [`Quadratic::with_condition`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quadratic {
+    q: Array2<f64>,
+    b: Array1<f64>,
+}
+
+impl Quadratic {
+    /// `q` has to be symmetric positive definite, it is not checked.
+    pub fn new(q: Array2<f64>, b: Array1<f64>) -> Self {
+        assert_eq!(
+            q.dim(),
+            (b.len(), b.len()),
+            "`q` must be a square matrix matching `b`"
+        );
+        Self { q, b }
+    }
+
+    /// `n`-dimensional problem with a diagonal `q` whose eigenvalues are spread geometrically from
+    /// 1 to `kappa`, so its condition number is exactly `kappa`. `b` is all ones.
+    pub fn with_condition(n: usize, kappa: f64) -> Self {
+        let eigenvalues = Array1::from_shape_fn(n, |i| match n {
+            1 => 1.0,
+            _ => kappa.powf(i as f64 / (n - 1) as f64),
+        });
+        Self::new(Array2::from_diag(&eigenvalues), Array1::ones(n))
+    }
+
+    pub fn q(&self) -> &Array2<f64> {
+        &self.q
+    }
+
+    pub fn b(&self) -> &Array1<f64> {
+        &self.b
+    }
+
+    pub fn dim(&self) -> usize {
+        self.b.len()
+    }
+
+    /// The minimizer, solution of $ Q x = b $.
+    pub fn minimizer(&self) -> Result<Array1<f64>, Error> {
+        Ok(self.q.inv()?.dot(&self.b))
+    }
+}
+
+impl CostFunction for Quadratic {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(0.5 * param.dot(&self.q.dot(param)) - self.b.dot(param))
+    }
+}
+
+impl Gradient for Quadratic {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self.q.dot(param) - &self.b)
+    }
+}
+
+impl Hessian for Quadratic {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, _param: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(self.q.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{Executor, State},
+        solver::newton::Newton,
+    };
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_with_condition() {
+        let f = Quadratic::with_condition(4, 1e3);
+        let diag = f.q().diag().to_owned();
+        assert_eq!(diag[0], 1.0);
+        assert!((diag[3] - 1e3).abs() < 1e-9);
+        assert!((diag[3] / diag[0] - 1e3).abs() < 1e-9);
+        let minimizer = f.minimizer().unwrap();
+        assert!(f
+            .gradient(&minimizer)
+            .unwrap()
+            .iter()
+            .all(|g| g.abs() < 1e-12));
+        assert_eq!(Quadratic::with_condition(1, 1e3).q(), &array![[1.0]]);
+    }
+
+    #[test]
+    fn test_newton_one_step() {
+        let q = array![[4.0, 1.0, 0.0], [1.0, 3.0, -1.0], [0.0, -1.0, 2.0]];
+        let f = Quadratic::new(q, array![1.0, -2.0, 0.5]);
+        let minimizer = f.minimizer().unwrap();
+        let res = Executor::new(f, Newton::<f64>::new())
+            .configure(|state| state.param(array![10.0, -5.0, 3.0]).max_iters(1))
+            .run()
+            .unwrap();
+        assert_eq!(res.state.get_iter(), 1);
+        let param = res.state.get_best_param().unwrap();
+        assert!(
+            (param - &minimizer).iter().all(|x| x.abs() < 1e-12),
+            "{param}"
+        );
+    }
+}
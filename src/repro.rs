@@ -0,0 +1,137 @@
+use std::fmt::{self, Write};
+
+use serde::Serialize;
+
+use crate::results::{Result, RunParams};
+
+/// Significant digits the floats of [`result_hash`] are rounded to by default.
+pub const DEFAULT_REPRO_DIGITS: usize = 6;
+
+/// What [`config_hash`] fingerprints, serialized to JSON as its canonical form.
+#[derive(Serialize)]
+struct Config<'a> {
+    problem: String,
+    params: RunParams,
+    methods: Vec<&'a str>,
+}
+
+/// Fingerprint of what produced a run: the problem with its parameters (e.g. the `a` and `b` of
+/// rosenbrock), the init, seed and iterations of `params`, and the set of `methods`.
+///
+/// Whether the seed was drawn at random doesn't count, replaying it gives the same hash.
+pub fn config_hash(problem: &impl fmt::Debug, params: &RunParams, methods: &[&str]) -> String {
+    let mut methods = methods.to_vec();
+    methods.sort_unstable();
+    let config = Config {
+        problem: format!("{problem:?}"),
+        params: RunParams {
+            seed_drawn: false,
+            ..params.clone()
+        },
+        methods,
+    };
+    let json = serde_json::to_string(&config).expect("configs are serializable");
+    format!("{:016x}", fnv1a(json.as_bytes()))
+}
+
+/// Fingerprint of the outcome of every row: its status, iterations and evaluations, and its
+/// best cost and param rounded to `digits` significant digits, so last-bit noise between
+/// platforms doesn't change it. Times are left out, and so is the order of the rows.
+pub fn result_hash(results: &[Result], digits: usize) -> String {
+    let mut rows = results
+        .iter()
+        .map(|x| {
+            let mut row = format!(
+                "{}|{:?}|{}|{:?}|{}|",
+                x.method,
+                x.status,
+                x.iterations,
+                x.evaluations,
+                round(x.best_cost, digits)
+            );
+            for value in x.best_param.iter().flatten() {
+                write!(row, "{},", round(*value, digits)).unwrap();
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+    rows.sort_unstable();
+    format!("{:016x}", fnv1a(rows.join("\n").as_bytes()))
+}
+
+/// `x` in scientific notation with `digits` significant digits, `-0` written as `0`.
+fn round(x: f64, digits: usize) -> String {
+    format!("{:.*e}", digits.saturating_sub(1), x + 0.0)
+}
+
+/// 64-bit FNV-1a, unlike `DefaultHasher` it is guaranteed to stay the same across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    fn params(seed: u64) -> RunParams {
+        RunParams {
+            init: Some(vec![-1.2, 1.0]),
+            seed: Some(seed),
+            seed_drawn: false,
+            max_iters: 100,
+            noise_stddev: None,
+            eval_budget: None,
+            maximize: false,
+        }
+    }
+
+    #[test]
+    fn test_config_hash() {
+        let problem = RosenbrockND::default();
+        let hash = config_hash(&problem, &params(1), &["BFGS", "L-BFGS"]);
+        assert_eq!(hash.len(), 16);
+        // Each problem draws its own random number generator.
+        let same = RosenbrockND::default();
+        assert_eq!(hash, config_hash(&same, &params(1), &["BFGS", "L-BFGS"]));
+        assert_eq!(hash, config_hash(&problem, &params(1), &["L-BFGS", "BFGS"]));
+        let drawn = RunParams {
+            seed_drawn: true,
+            ..params(1)
+        };
+        assert_eq!(hash, config_hash(&problem, &drawn, &["BFGS", "L-BFGS"]));
+        assert_ne!(hash, config_hash(&problem, &params(2), &["BFGS", "L-BFGS"]));
+        assert_ne!(hash, config_hash(&problem, &params(1), &["BFGS"]));
+        let other = RosenbrockND::builder().a(2.0).build().unwrap();
+        assert_ne!(hash, config_hash(&other, &params(1), &["BFGS", "L-BFGS"]));
+    }
+
+    #[test]
+    fn test_result_hash_rounds() {
+        let row = |cost: f64, millis: u64| {
+            Result::new(
+                "",
+                "BFGS",
+                cost,
+                Some(Duration::from_millis(millis)),
+                10,
+                None,
+            )
+        };
+        let hash = result_hash(&[row(1.0e-9, 5)], 6);
+        assert_eq!(hash, result_hash(&[row(1.0e-9 + 1e-22, 50)], 6));
+        assert_ne!(hash, result_hash(&[row(1.1e-9, 5)], 6));
+        assert_eq!(
+            result_hash(&[row(1.0e-9, 5)], 1),
+            result_hash(&[row(1.1e-9, 5)], 1)
+        );
+        assert_eq!(
+            result_hash(&[row(0.0, 5)], 6),
+            result_hash(&[row(-0.0, 5)], 6)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use argmin::core::{
+    ArgminError, CostFunction, Error, IterState, Problem, Solver, State, TerminationStatus, KV,
+};
+use ndarray::Array1;
+use rand::Rng;
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+/// Wraps a local solver to restart it from a perturbed point whenever it stops on its own above
+/// the target cost, at most `restarts` times: a simple basin hopping letting e.g. BFGS escape the
+/// local minima of a mildly multimodal function. The state keeps the best param across attempts.
+///
+/// Every restart starts from the best param so far moved uniformly by up to `radius` along each
+/// axis, with a fresh copy of the solver and the initial inverse hessian, if any. It counts as an
+/// iteration, so `max_iters` caps all the attempts together.
+///
+/// Unless `restarts` is 0, the iteration log has an `attempt` entry, 0 before the first restart,
+/// read by [`RestartObserver`](crate::observers::RestartObserver).
+#[derive(Debug, Clone)]
+pub struct RestartingSolver<S, H> {
+    template: S,
+    /// Inverse hessian of the initial state, quasi-Newton methods start every attempt from it.
+    inv_hessian: Option<H>,
+    solver: S,
+    restarts: u64,
+    radius: f64,
+    target: f64,
+    attempt: u64,
+    /// The solver stopped, the next iteration restarts it.
+    pending: bool,
+    rng: Xoshiro256PlusPlus,
+}
+
+impl<S: Clone, H> RestartingSolver<S, H> {
+    /// Up to `restarts` restarts within a radius of 1, drawn from entropy and whatever the cost.
+    pub fn new(solver: S, restarts: u64) -> Self {
+        Self {
+            template: solver.clone(),
+            inv_hessian: None,
+            solver,
+            restarts,
+            radius: 1.0,
+            target: f64::NEG_INFINITY,
+            attempt: 0,
+            pending: false,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+
+    /// Largest move along each axis from the best param, must be positive.
+    pub fn with_radius(mut self, radius: f64) -> Result<Self, Error> {
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`radius` must be positive, got {radius}"),
+            }
+            .into());
+        }
+        self.radius = radius;
+        Ok(self)
+    }
+
+    /// Best cost at which the solver isn't restarted anymore.
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        self
+    }
+
+    pub fn restarts(&self) -> u64 {
+        self.restarts
+    }
+
+    fn log(&self, kv: Option<KV>) -> Option<KV> {
+        if self.restarts == 0 {
+            return kv;
+        }
+        let attempt = argmin::kv!("attempt" => self.attempt;);
+        Some(kv.unwrap_or_default().merge(attempt))
+    }
+}
+
+impl<O, S, G, J, H> Solver<O, IterState<Array1<f64>, G, J, H, f64>> for RestartingSolver<S, H>
+where
+    O: CostFunction<Param = Array1<f64>, Output = f64>,
+    S: Solver<O, IterState<Array1<f64>, G, J, H, f64>> + Clone,
+    H: Clone,
+{
+    const NAME: &'static str = S::NAME;
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Array1<f64>, G, J, H, f64>,
+    ) -> Result<(IterState<Array1<f64>, G, J, H, f64>, Option<KV>), Error> {
+        self.solver = self.template.clone();
+        self.attempt = 0;
+        self.pending = false;
+        self.inv_hessian = state.get_inv_hessian().cloned();
+        self.solver.init(problem, state)
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<Array1<f64>, G, J, H, f64>,
+    ) -> Result<(IterState<Array1<f64>, G, J, H, f64>, Option<KV>), Error> {
+        if !self.pending {
+            let (state, kv) = self.solver.next_iter(problem, state)?;
+            return Ok((state, self.log(kv)));
+        }
+        self.pending = false;
+        self.attempt += 1;
+        let base = match (state.get_best_param(), state.get_param()) {
+            (Some(best), Some(_)) if state.get_best_cost() < state.get_cost() => best,
+            (_, Some(param)) => param,
+            (_, None) => {
+                return Err(ArgminError::NotInitialized {
+                    text: "`RestartingSolver` requires an initial parameter vector".to_string(),
+                }
+                .into())
+            }
+        };
+        let param = base.mapv(|x| x + self.rng.gen_range(-self.radius..=self.radius));
+        state.take_gradient();
+        state.take_hessian();
+        state.take_jacobian();
+        if let Some(inv_hessian) = &self.inv_hessian {
+            state = state.inv_hessian(inv_hessian.clone());
+        }
+        self.solver = self.template.clone();
+        // Left to the solver like the executor does, setting it here would make the cost look
+        // unchanged to the termination checks comparing it to the previous one.
+        let state = state.param(param).cost(f64::INFINITY);
+        let (state, kv) = self.solver.init(problem, state)?;
+        Ok((state, self.log(kv)))
+    }
+
+    fn terminate(&mut self, state: &IterState<Array1<f64>, G, J, H, f64>) -> TerminationStatus {
+        let status = self.solver.terminate(state);
+        if status.terminated()
+            && self.attempt < self.restarts
+            && state.get_best_cost() > self.target
+        {
+            self.pending = true;
+            return TerminationStatus::NotTerminated;
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use argmin::{
+        core::Executor,
+        solver::{linesearch::MoreThuenteLineSearch, quasinewton::BFGS},
+    };
+    use ndarray::{array, Array2};
+
+    use super::*;
+    use crate::TestFnProblem;
+
+    #[test]
+    fn test_restarts_escape_local_minima() {
+        let rastrigin = TestFnProblem::rastrigin().with_gradient(|x: &[f64]| {
+            x.iter()
+                .map(|x| 2.0 * x + 20.0 * PI * (2.0 * PI * x).sin())
+                .collect()
+        });
+        let bfgs = || BFGS::new(MoreThuenteLineSearch::new());
+        // Next to the local minimum around (3, 3), far from the global one at the origin.
+        let init = array![3.2, 2.9];
+        let run = |solver: RestartingSolver<BFGS<_, f64>, Array2<f64>>| {
+            Executor::new(rastrigin, solver)
+                .configure(|state| {
+                    state
+                        .param(init.clone())
+                        .inv_hessian(Array2::eye(2))
+                        .max_iters(2_000)
+                })
+                .run()
+                .unwrap()
+                .state
+                .get_best_cost()
+        };
+        let single = run(RestartingSolver::new(bfgs(), 0));
+        assert!(single > 17.0, "{single}");
+        for seed in 0..5 {
+            let restarted = RestartingSolver::new(bfgs(), 50)
+                .with_radius(1.5)
+                .unwrap()
+                .with_seed(seed);
+            let restarted = run(restarted);
+            assert!(restarted < 2.0, "{seed}: {restarted}");
+        }
+        let invalid = RestartingSolver::<_, Array2<f64>>::new(bfgs(), 1).with_radius(0.0);
+        assert!(invalid.is_err());
+    }
+}
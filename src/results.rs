@@ -0,0 +1,1369 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use argmin::core::TerminationReason;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tabled::{builder::Builder, Style, Table, Tabled};
+
+use crate::{
+    convergence::ConvergenceRate,
+    observers::{iters_to, IterTimes},
+    run_config::RunConfig,
+    step_length::StepStats,
+    trajectory::Trajectory,
+    Method,
+};
+
+/// Version of the JSON output layout, bump it whenever the serialized fields change.
+pub const SCHEMA_VERSION: u32 = 22;
+
+/// Significant digits of the rendered costs, distances and params unless the renderer is given
+/// others, e.g. by [`Format::render_columns`]. 17 digits show every `f64` exactly.
+pub const DEFAULT_PRECISION: usize = 6;
+
+/// Renders `x` rounded to `digits` significant digits, in scientific notation when very large or
+/// small, e.g. `0.172532` or `3.03913e-26`.
+pub fn format_float(x: f64, digits: usize) -> String {
+    if !x.is_finite() || x == 0.0 {
+        return x.to_string();
+    }
+    let rounded = format!("{x:.*e}", digits.max(1) - 1)
+        .parse::<f64>()
+        .expect("formatted floats parse back");
+    match rounded.abs() {
+        abs if (1e-4..1e6).contains(&abs) => rounded.to_string(),
+        _ => format!("{rounded:e}"),
+    }
+}
+
+/// Renders a param with [`format_float`], e.g. `[1, 0.999998]`.
+pub fn format_param(param: &[f64], digits: usize) -> String {
+    let values = param
+        .iter()
+        .map(|&x| format_float(x, digits))
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
+/// Outcome of a run, normalizing the termination reasons of the solvers and the errors, so
+/// scripts can tell whether it succeeded without parsing `termination_reason`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// Stopped on its own: converged, reached its target or gave up on a plateau.
+    Converged,
+    /// Ran out of iterations.
+    MaxIters,
+    /// Never started as the time budget of the sweep ran out.
+    Timeout,
+    /// Returned an error, diverged or was interrupted.
+    Failed,
+    /// Never started, e.g. as the problem lacks a derivative the method needs.
+    Skipped,
+    /// Returned without a termination reason. Also the status of the rows of dumps written
+    /// before it was recorded, which can't be trusted to have converged.
+    #[default]
+    Unknown,
+}
+
+impl RunStatus {
+    /// Status of a run that returned, `diverged` when its best cost isn't finite.
+    pub fn new(termination_reason: Option<&TerminationReason>, diverged: bool) -> Self {
+        match termination_reason {
+            _ if diverged => RunStatus::Failed,
+            Some(TerminationReason::MaxItersReached) => RunStatus::MaxIters,
+            Some(
+                TerminationReason::SolverConverged
+                | TerminationReason::TargetCostReached
+                | TerminationReason::SolverExit(_),
+            ) => RunStatus::Converged,
+            Some(TerminationReason::KeyboardInterrupt) => RunStatus::Failed,
+            None => RunStatus::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for RunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RunStatus::Converged => "converged",
+            RunStatus::MaxIters => "max_iters",
+            RunStatus::Timeout => "timeout",
+            RunStatus::Failed => "failed",
+            RunStatus::Skipped => "skipped",
+            RunStatus::Unknown => "unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Summary of a single solver run, one row of the comparison table.
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize, Deserialize)]
+#[tabled(rename_all = "Pascal")]
+pub struct Result {
+    pub family: String,
+    pub method: String,
+    #[tabled(display_with("display_cost", args))]
+    #[serde(with = "non_finite")]
+    pub best_cost: f64,
+    pub time: String,
+    pub iterations: u64,
+    /// Iteration cap derived from `--eval-budget`, `None` when every method shares `max_iters`.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub iteration_cap: Option<u64>,
+    /// Evaluations of the cost, gradient, hessian, residuals and jacobian, summed as argmin
+    /// counts them. `None` without a run.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub evaluations: Option<u64>,
+    pub termination_reason: String,
+    #[serde(default)]
+    pub status: RunStatus,
+    /// Reruns of a stochastic method with `--retry-on-failure`, `None` when it doesn't apply.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub retries: Option<u64>,
+    /// Independent runs of a stochastic method with `--repeat-best-of`, the row being the best of
+    /// them. `None` when it doesn't apply.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub best_of: Option<u64>,
+    /// Seed of the best run with `--repeat-best-of`, passing it to `--seed` reproduces the row.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub best_of_seed: Option<u64>,
+    /// Fraction of moves accepted by simulated annealing, `None` for the other methods.
+    #[tabled(display_with = "display_rate")]
+    #[serde(default)]
+    pub acceptance_rate: Option<f64>,
+    /// Steps where the safeguarded Newton fell back to steepest descent, `None` for the other
+    /// methods.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub fallback_steps: Option<u64>,
+    /// Line search the row was rerun with after a line-search failure with `--retry-linesearch`.
+    #[tabled(display_with = "display_name")]
+    #[serde(default)]
+    pub linesearch_fallback: Option<String>,
+    /// Restarts of a local solver with `--restarts`, `None` when it doesn't apply.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub restarts: Option<u64>,
+    /// Attempt that found the best cost with `--restarts`, 0 for the run before any restart.
+    #[tabled(display_with = "display_iters")]
+    #[serde(default)]
+    pub best_attempt: Option<u64>,
+    /// Fraction of the cost evaluations answered from the cache with `--cache`.
+    #[tabled(display_with = "display_rate")]
+    #[serde(default)]
+    pub cache_hit_rate: Option<f64>,
+    /// Step lengths accepted by the line search, `None` for the methods without one.
+    #[tabled(display_with = "display_steps")]
+    #[serde(default)]
+    pub step_length: Option<StepStats>,
+    /// Empirical order of convergence to the known optimum, only with `--convergence-rate`.
+    #[tabled(display_with = "display_convergence")]
+    #[serde(default)]
+    pub convergence_rate: Option<ConvergenceRate>,
+    /// Time per iteration, only recorded with `--time-per-iter`.
+    #[tabled(display_with = "display_iter_times")]
+    #[serde(default)]
+    pub time_per_iter: Option<IterTimes>,
+    /// The best cost is `inf` or `NaN`, meaning the solver blew up.
+    #[tabled(skip)]
+    pub diverged: bool,
+    /// The solver returned an error instead of a result.
+    #[tabled(skip)]
+    pub error: Option<String>,
+    /// The error was raised by a line search, see
+    /// [`is_linesearch_error`](crate::runner::is_linesearch_error).
+    #[tabled(skip)]
+    #[serde(default)]
+    pub linesearch_error: bool,
+    /// The solver was never run, e.g. because the time budget of the sweep ran out.
+    #[tabled(skip)]
+    #[serde(default)]
+    pub skipped: bool,
+    /// Best parameter found, missing in dumps written before it was recorded.
+    #[tabled(skip)]
+    #[serde(default, with = "non_finite_param")]
+    pub best_param: Option<Vec<f64>>,
+    /// Param the method started from with `--init-jitter`, in the original variables.
+    #[tabled(skip)]
+    #[serde(default)]
+    pub start: Option<Vec<f64>>,
+    /// Raw run time, `time` is its formatted version.
+    #[tabled(skip)]
+    #[serde(default)]
+    pub duration: Option<Duration>,
+    /// Best cost after every iteration, only recorded on request and never serialized.
+    #[tabled(skip)]
+    #[serde(skip)]
+    pub best_cost_history: Option<Vec<f64>>,
+    /// Param and cost after every iteration, only recorded on request and never serialized.
+    #[tabled(skip)]
+    #[serde(skip)]
+    pub trajectory: Option<Trajectory>,
+}
+
+impl Result {
+    pub fn new(
+        family: impl ToString,
+        method: impl ToString,
+        best_cost: f64,
+        time: Option<Duration>,
+        iterations: u64,
+        termination_reason: Option<&TerminationReason>,
+    ) -> Self {
+        let duration = time;
+        let time = time
+            .map(|d| format!("{d:?}"))
+            .unwrap_or_else(|| "-".to_string());
+
+        let status = RunStatus::new(termination_reason, !best_cost.is_finite());
+        let termination_reason = match termination_reason {
+            Some(x) => format!("{x}"),
+            None => "-".to_string(),
+        };
+        Self {
+            family: family.to_string(),
+            method: method.to_string(),
+            best_cost,
+            time,
+            iterations,
+            iteration_cap: None,
+            evaluations: None,
+            termination_reason,
+            status,
+            retries: None,
+            best_of: None,
+            best_of_seed: None,
+            acceptance_rate: None,
+            fallback_steps: None,
+            linesearch_fallback: None,
+            restarts: None,
+            best_attempt: None,
+            cache_hit_rate: None,
+            step_length: None,
+            convergence_rate: None,
+            time_per_iter: None,
+            diverged: !best_cost.is_finite(),
+            error: None,
+            linesearch_error: false,
+            skipped: false,
+            best_param: None,
+            start: None,
+            duration,
+            best_cost_history: None,
+            trajectory: None,
+        }
+    }
+
+    pub fn with_best_param(mut self, best_param: Option<Vec<f64>>) -> Self {
+        self.best_param = best_param;
+        self
+    }
+
+    /// Row for a solver that returned an error, the error takes the place of the termination
+    /// reason.
+    pub fn failed(family: impl ToString, method: impl ToString, error: &impl fmt::Display) -> Self {
+        Self {
+            family: family.to_string(),
+            method: method.to_string(),
+            best_cost: f64::NAN,
+            time: "-".to_string(),
+            iterations: 0,
+            iteration_cap: None,
+            evaluations: None,
+            termination_reason: format!("Error: {error}"),
+            status: RunStatus::Failed,
+            retries: None,
+            best_of: None,
+            best_of_seed: None,
+            acceptance_rate: None,
+            fallback_steps: None,
+            linesearch_fallback: None,
+            restarts: None,
+            best_attempt: None,
+            cache_hit_rate: None,
+            step_length: None,
+            convergence_rate: None,
+            time_per_iter: None,
+            diverged: false,
+            error: Some(error.to_string()),
+            linesearch_error: false,
+            skipped: false,
+            best_param: None,
+            start: None,
+            duration: None,
+            best_cost_history: None,
+            trajectory: None,
+        }
+    }
+
+    /// Row for a solver that was not run, `reason` takes the place of the termination reason.
+    pub fn skipped(family: impl ToString, method: impl ToString, reason: &str) -> Self {
+        Self {
+            termination_reason: format!("Skipped: {reason}"),
+            status: RunStatus::Skipped,
+            diverged: false,
+            skipped: true,
+            ..Self::new(family, method, f64::NAN, None, 0, None)
+        }
+    }
+
+    /// Row for a solver that was not run as the time budget of the sweep ran out.
+    pub fn timed_out(family: impl ToString, method: impl ToString) -> Self {
+        Self {
+            status: RunStatus::Timeout,
+            ..Self::skipped(family, method, "time budget exhausted")
+        }
+    }
+
+    pub fn with_best_cost_history(mut self, best_cost_history: Option<Vec<f64>>) -> Self {
+        self.best_cost_history = best_cost_history;
+        self
+    }
+
+    pub fn with_trajectory(mut self, trajectory: Option<Trajectory>) -> Self {
+        self.trajectory = trajectory;
+        self
+    }
+
+    pub fn with_iteration_cap(mut self, iteration_cap: Option<u64>) -> Self {
+        self.iteration_cap = iteration_cap;
+        self
+    }
+
+    pub fn with_evaluations(mut self, evaluations: Option<u64>) -> Self {
+        self.evaluations = evaluations;
+        self
+    }
+
+    pub fn with_retries(mut self, retries: Option<u64>) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Records the number of runs and the seed of the winning one, see [`Self::best_of`].
+    pub fn with_best_of(mut self, runs: u64, seed: Option<u64>) -> Self {
+        self.best_of = Some(runs);
+        self.best_of_seed = seed;
+        self
+    }
+
+    pub fn with_acceptance_rate(mut self, acceptance_rate: Option<f64>) -> Self {
+        self.acceptance_rate = acceptance_rate;
+        self
+    }
+
+    pub fn with_fallback_steps(mut self, fallback_steps: Option<u64>) -> Self {
+        self.fallback_steps = fallback_steps;
+        self
+    }
+
+    pub fn with_linesearch_error(mut self, linesearch_error: bool) -> Self {
+        self.linesearch_error = linesearch_error;
+        self
+    }
+
+    pub fn with_linesearch_fallback(mut self, linesearch_fallback: Option<String>) -> Self {
+        self.linesearch_fallback = linesearch_fallback;
+        self
+    }
+
+    /// Records the restarts done and the attempt that found the best cost, see
+    /// [`Self::best_attempt`].
+    pub fn with_restarts(mut self, restarts: Option<u64>, best_attempt: Option<u64>) -> Self {
+        self.restarts = restarts;
+        self.best_attempt = best_attempt;
+        self
+    }
+
+    pub fn with_start(mut self, start: Option<Vec<f64>>) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn with_cache_hit_rate(mut self, cache_hit_rate: Option<f64>) -> Self {
+        self.cache_hit_rate = cache_hit_rate;
+        self
+    }
+
+    pub fn with_step_length(mut self, step_length: Option<StepStats>) -> Self {
+        self.step_length = step_length;
+        self
+    }
+
+    pub fn with_convergence_rate(mut self, convergence_rate: Option<ConvergenceRate>) -> Self {
+        self.convergence_rate = convergence_rate;
+        self
+    }
+
+    pub fn with_time_per_iter(mut self, time_per_iter: Option<IterTimes>) -> Self {
+        self.time_per_iter = time_per_iter;
+        self
+    }
+
+    /// Replaces the run time, e.g. with an average over several runs.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.time = format!("{duration:?}");
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Whether the run failed, diverged or was skipped, i.e. its best cost is meaningless.
+    pub fn is_failure(&self) -> bool {
+        self.diverged || self.error.is_some() || self.skipped
+    }
+}
+
+/// Cost thresholds of the time-to-target report.
+pub const COST_THRESHOLDS: [f64; 4] = [1e-1, 1e-3, 1e-6, 1e-9];
+
+/// Iterations needed by a run for its best cost to drop below each of [`COST_THRESHOLDS`].
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+pub struct ThresholdRow {
+    #[tabled(rename = "Family")]
+    pub family: String,
+    #[tabled(rename = "Method")]
+    pub method: String,
+    #[tabled(rename = "iters_to_1e-1", display_with = "display_iters")]
+    #[serde(rename = "iters_to_1e-1")]
+    pub iters_to_1e_1: Option<u64>,
+    #[tabled(rename = "iters_to_1e-3", display_with = "display_iters")]
+    #[serde(rename = "iters_to_1e-3")]
+    pub iters_to_1e_3: Option<u64>,
+    #[tabled(rename = "iters_to_1e-6", display_with = "display_iters")]
+    #[serde(rename = "iters_to_1e-6")]
+    pub iters_to_1e_6: Option<u64>,
+    #[tabled(rename = "iters_to_1e-9", display_with = "display_iters")]
+    #[serde(rename = "iters_to_1e-9")]
+    pub iters_to_1e_9: Option<u64>,
+}
+
+impl ThresholdRow {
+    /// Needs the best cost history of the run, without it every threshold counts as missed.
+    pub fn new(result: &Result) -> Self {
+        let history = result.best_cost_history.as_deref().unwrap_or_default();
+        let [iters_to_1e_1, iters_to_1e_3, iters_to_1e_6, iters_to_1e_9] =
+            COST_THRESHOLDS.map(|x| iters_to(history, x));
+        Self {
+            family: result.family.clone(),
+            method: result.method.clone(),
+            iters_to_1e_1,
+            iters_to_1e_3,
+            iters_to_1e_6,
+            iters_to_1e_9,
+        }
+    }
+}
+
+/// Steepest descent with one line search under one iteration cap, for `--compare-linesearch`.
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+pub struct LineSearchRow {
+    #[tabled(rename = "LineSearch")]
+    pub line_search: String,
+    #[tabled(rename = "MaxIters")]
+    pub max_iters: u64,
+    /// Iterations until the run stopped on its own, `None` when it hit `max_iters` or failed.
+    #[tabled(rename = "ItersToConverge", display_with = "display_iters")]
+    pub iters_to_converge: Option<u64>,
+    #[tabled(rename = "BestCost", display_with = "display_stat")]
+    pub best_cost: Option<f64>,
+    #[tabled(rename = "Time")]
+    pub time: String,
+}
+
+impl LineSearchRow {
+    pub fn new(max_iters: u64, result: &Result) -> Self {
+        let converged = result.status == RunStatus::Converged;
+        Self {
+            line_search: result.method.clone(),
+            max_iters,
+            iters_to_converge: converged.then_some(result.iterations),
+            best_cost: (!result.is_failure()).then_some(result.best_cost),
+            time: result.time.clone(),
+        }
+    }
+}
+
+/// Best cost of a method over the seeds of `--seed-sweep`. Failed, diverged and skipped runs
+/// are only counted, the statistics leave them out and are `None` when every run failed.
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+pub struct SweepRow {
+    #[tabled(rename = "Family")]
+    pub family: String,
+    #[tabled(rename = "Method")]
+    pub method: String,
+    #[tabled(rename = "Runs")]
+    pub runs: usize,
+    #[tabled(rename = "Failed")]
+    pub failed: usize,
+    #[tabled(rename = "Mean", display_with = "display_stat")]
+    pub mean: Option<f64>,
+    #[tabled(rename = "Std", display_with = "display_stat")]
+    pub std: Option<f64>,
+    #[tabled(rename = "Min", display_with = "display_stat")]
+    pub min: Option<f64>,
+    #[tabled(rename = "Median", display_with = "display_stat")]
+    pub median: Option<f64>,
+    #[tabled(rename = "Max", display_with = "display_stat")]
+    pub max: Option<f64>,
+}
+
+impl SweepRow {
+    /// Summarizes the runs of a single method, named after the first one.
+    pub fn new(results: &[Result]) -> Self {
+        let mut costs = results
+            .iter()
+            .filter(|x| !x.is_failure())
+            .map(|x| x.best_cost)
+            .collect::<Vec<_>>();
+        costs.sort_by(f64::total_cmp);
+        let n = costs.len();
+        let (min, max) = (costs.first().copied(), costs.last().copied());
+        // Shifted by the minimum, identical costs give exactly their value and a zero std.
+        let mean = min.map(|min| min + costs.iter().map(|x| x - min).sum::<f64>() / n as f64);
+        let std = mean.map(|mean| {
+            let variance = costs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+            variance.sqrt()
+        });
+        let median = (n > 0).then(|| match n % 2 {
+            1 => costs[n / 2],
+            _ => (costs[n / 2 - 1] + costs[n / 2]) / 2.0,
+        });
+        Self {
+            family: results
+                .first()
+                .map(|x| x.family.clone())
+                .unwrap_or_default(),
+            method: results
+                .first()
+                .map(|x| x.method.clone())
+                .unwrap_or_default(),
+            runs: results.len(),
+            failed: results.len() - n,
+            mean,
+            std,
+            min,
+            median,
+            max,
+        }
+    }
+}
+
+/// Below this magnitude the baseline cost is considered zero and [`relative_to`] falls back to
+/// differences, ratios to it would be meaningless.
+pub const ZERO_BASELINE: f64 = 1e-12;
+
+/// Best cost of a run compared to the one of a baseline method.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Relative {
+    /// `cost / baseline`, below 1 is better than the baseline.
+    Ratio(f64),
+    /// `cost - baseline`, used when the baseline cost is (near) zero.
+    Difference(f64),
+}
+
+impl fmt::Display for Relative {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Relative::Ratio(x) if (1e-2..1e5).contains(&x) || x == 0.0 => write!(f, "{x:.3}x"),
+            Relative::Ratio(x) => write!(f, "{x:.2e}x"),
+            Relative::Difference(x) => write!(f, "{x:+.2e}"),
+        }
+    }
+}
+
+/// A result with its best cost relative to a baseline method, see [`relative_to`].
+#[derive(Debug, Clone, PartialEq, Tabled, Serialize)]
+pub struct RelativeRow {
+    #[tabled(inline)]
+    #[serde(flatten)]
+    pub result: Result,
+    /// `None` for failed, diverged or skipped runs.
+    #[tabled(rename = "Relative", display_with = "display_relative")]
+    pub relative: Option<Relative>,
+}
+
+/// Compares the best cost of every row to the one of `baseline`, which has to be one of the
+/// rows and must not have failed.
+pub fn relative_to(
+    results: Vec<Result>,
+    baseline: Method,
+) -> std::result::Result<Vec<RelativeRow>, String> {
+    let base = results
+        .iter()
+        .find(|x| x.method.parse() == Ok(baseline))
+        .ok_or_else(|| format!("the baseline `{}` was not run", baseline.key()))?;
+    if base.is_failure() {
+        return Err(format!(
+            "the baseline `{}` has no best cost ({})",
+            baseline.key(),
+            display_cost(base)
+        ));
+    }
+    let base = base.best_cost;
+    let rows = results
+        .into_iter()
+        .map(|result| {
+            let relative = (!result.is_failure()).then(|| {
+                if base.abs() < ZERO_BASELINE {
+                    Relative::Difference(result.best_cost - base)
+                } else {
+                    Relative::Ratio(result.best_cost / base)
+                }
+            });
+            RelativeRow { result, relative }
+        })
+        .collect();
+    Ok(rows)
+}
+
+fn display_relative(relative: &Option<Relative>) -> String {
+    match relative {
+        Some(x) => x.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn display_stat(stat: &Option<f64>) -> String {
+    match stat {
+        Some(x) => format_float(*x, DEFAULT_PRECISION),
+        None => "-".to_string(),
+    }
+}
+
+fn display_iters(iters: &Option<u64>) -> String {
+    match iters {
+        Some(x) => x.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn display_name(name: &Option<String>) -> String {
+    match name {
+        Some(x) => x.clone(),
+        None => "-".to_string(),
+    }
+}
+
+fn display_rate(rate: &Option<f64>) -> String {
+    match rate {
+        Some(x) => format!("{:.1}%", 100.0 * x),
+        None => "-".to_string(),
+    }
+}
+
+fn display_steps(steps: &Option<StepStats>) -> String {
+    display_with_digits(steps, DEFAULT_PRECISION)
+}
+
+fn display_convergence(rate: &Option<ConvergenceRate>) -> String {
+    display_with_digits(rate, DEFAULT_PRECISION)
+}
+
+/// Renders a value whose `Display` takes the significant digits as its precision.
+fn display_with_digits<T: fmt::Display>(value: &Option<T>, digits: usize) -> String {
+    match value {
+        Some(x) => format!("{x:.digits$}"),
+        None => "-".to_string(),
+    }
+}
+
+fn display_iter_times(times: &Option<IterTimes>) -> String {
+    match times {
+        Some(x) => x.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn display_cost(result: &Result) -> String {
+    display_cost_digits(result, DEFAULT_PRECISION)
+}
+
+fn display_cost_digits(result: &Result, digits: usize) -> String {
+    if result.error.is_some() {
+        "failed".to_string()
+    } else if result.skipped {
+        "skipped".to_string()
+    } else if result.diverged {
+        "diverged".to_string()
+    } else {
+        format_float(result.best_cost, digits)
+    }
+}
+
+/// JSON has no representation for `inf`/`NaN`, they are written as `null` and read back as `NaN`.
+mod non_finite {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        x: &f64,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        Some(*x).filter(|x| x.is_finite()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NAN))
+    }
+}
+
+/// Same as `non_finite` for every coordinate of the best param.
+mod non_finite_param {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        x: &Option<Vec<f64>>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        x.as_ref()
+            .map(|x| {
+                x.iter()
+                    .map(|x| Some(*x).filter(|x| x.is_finite()))
+                    .collect::<Vec<_>>()
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Option<Vec<f64>>, D::Error> {
+        let x = Option::<Vec<Option<f64>>>::deserialize(deserializer)?;
+        Ok(x.map(|x| x.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect()))
+    }
+}
+
+/// Settings a run was made with, stored next to the results so the output is self-describing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunParams {
+    /// Initial parameter, `None` when it is not shared by all the rows.
+    pub init: Option<Vec<f64>>,
+    pub seed: Option<u64>,
+    /// `seed` was drawn at random as none was given, passing it to `--seed` replays the run.
+    #[serde(default)]
+    pub seed_drawn: bool,
+    pub max_iters: u64,
+    /// Standard deviation of the noise added to the cost, if any.
+    #[serde(default)]
+    pub noise_stddev: Option<f64>,
+    /// Evaluations every method was given instead of `max_iters`, see
+    /// [`Method::iterations_for_budget`].
+    #[serde(default)]
+    pub eval_budget: Option<u64>,
+    /// The problem was maximized, best costs are the largest values found.
+    #[serde(default)]
+    pub maximize: bool,
+}
+
+/// Full output of a run: the result rows plus what produced them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report<T> {
+    pub schema: u32,
+    pub problem: String,
+    pub params: RunParams,
+    pub results: Vec<T>,
+}
+
+impl<T> Report<T> {
+    pub fn new(problem: impl ToString, params: RunParams, results: Vec<T>) -> Self {
+        Self {
+            schema: SCHEMA_VERSION,
+            problem: problem.to_string(),
+            params,
+            results,
+        }
+    }
+}
+
+/// Outcome of [`run_comparison`](crate::run_comparison): one row per method of `config`, in its
+/// order, for code consuming the results without parsing the printed tables.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub rows: Vec<Result>,
+    pub config: RunConfig,
+}
+
+impl Summary {
+    /// The rows with the settings they were run with, rendered by [`Format::render`] like the
+    /// output of the binaries.
+    pub fn report(&self) -> Report<Result> {
+        Report::new(
+            &self.config.problem,
+            self.config.params.clone(),
+            self.rows.clone(),
+        )
+    }
+}
+
+/// Sorts by best cost, best first: the lowest cost, or the largest value with `maximize`.
+/// Diverged and failed rows are always ranked last.
+pub fn sort_by_cost(results: &mut [Result], maximize: bool) {
+    results.sort_by(|x, y| {
+        x.is_failure().cmp(&y.is_failure()).then_with(|| {
+            let order = x.best_cost.total_cmp(&y.best_cost);
+            match maximize {
+                true => order.reverse(),
+                false => order,
+            }
+        })
+    });
+}
+
+/// Output format used to render result rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    #[default]
+    Table,
+    Csv,
+    Json,
+    JsonPretty,
+    Markdown,
+}
+
+impl Format {
+    /// Renders the result rows, the JSON formats also include the report metadata.
+    pub fn render<T: Tabled + Serialize>(&self, report: &Report<T>) -> String {
+        match self {
+            Format::Table => Table::new(&report.results)
+                .with(Style::modern())
+                .to_string(),
+            Format::Csv => to_csv(&report.results),
+            Format::Json => serde_json::to_string(report).expect("reports are serializable"),
+            Format::JsonPretty => {
+                serde_json::to_string_pretty(report).expect("reports are serializable")
+            }
+            Format::Markdown => Table::new(&report.results)
+                .with(Style::markdown())
+                .to_string(),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "json-pretty" => Ok(Format::JsonPretty),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format `{s}`, expected table, csv, json, json-pretty or markdown"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Table => "table",
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::JsonPretty => "json-pretty",
+            Format::Markdown => "markdown",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Renders one column of a result row.
+type Accessor = fn(&Result, usize) -> String;
+
+/// Columns of the result rows that can be picked with `--columns`, as `(name, header,
+/// accessor)`, in their default order. Only the first [`DEFAULT_COLUMNS`] are rendered by
+/// default, the others must be picked.
+const COLUMNS: [(&str, &str, Accessor); 23] = [
+    ("family", "Family", |x, _| x.family.clone()),
+    ("method", "Method", |x, _| x.method.clone()),
+    ("best_cost", "BestCost", display_cost_digits),
+    ("time", "Time", |x, _| x.time.clone()),
+    ("iters", "Iterations", |x, _| x.iterations.to_string()),
+    ("iteration_cap", "IterationCap", |x, _| {
+        display_iters(&x.iteration_cap)
+    }),
+    ("evaluations", "Evaluations", |x, _| {
+        display_iters(&x.evaluations)
+    }),
+    ("termination_reason", "TerminationReason", |x, _| {
+        x.termination_reason.clone()
+    }),
+    ("status", "Status", |x, _| x.status.to_string()),
+    ("retries", "Retries", |x, _| display_iters(&x.retries)),
+    ("best_of", "BestOf", |x, _| display_iters(&x.best_of)),
+    ("best_of_seed", "BestOfSeed", |x, _| {
+        display_iters(&x.best_of_seed)
+    }),
+    ("acceptance_rate", "AcceptanceRate", |x, _| {
+        display_rate(&x.acceptance_rate)
+    }),
+    ("fallback_steps", "FallbackSteps", |x, _| {
+        display_iters(&x.fallback_steps)
+    }),
+    ("linesearch_fallback", "LinesearchFallback", |x, _| {
+        display_name(&x.linesearch_fallback)
+    }),
+    ("restarts", "Restarts", |x, _| display_iters(&x.restarts)),
+    ("best_attempt", "BestAttempt", |x, _| {
+        display_iters(&x.best_attempt)
+    }),
+    ("cache_hit_rate", "CacheHitRate", |x, _| {
+        display_rate(&x.cache_hit_rate)
+    }),
+    ("step_length", "StepLength", |x, digits| {
+        display_with_digits(&x.step_length, digits)
+    }),
+    ("convergence_rate", "ConvergenceRate", |x, digits| {
+        display_with_digits(&x.convergence_rate, digits)
+    }),
+    ("time_per_iter", "TimePerIter", |x, _| {
+        display_iter_times(&x.time_per_iter)
+    }),
+    ("best_param", "BestParam", |x, digits| match &x.best_param {
+        Some(param) => format_param(param, digits),
+        None => "-".to_string(),
+    }),
+    ("start", "Start", |x, digits| match &x.start {
+        Some(param) => format_param(param, digits),
+        None => "-".to_string(),
+    }),
+];
+
+/// Columns rendered without `--columns`, the fields of [`Result`] shown by its `Tabled` derive.
+const DEFAULT_COLUMNS: usize = 21;
+
+/// Selection and order of the columns rendered for result rows, e.g.
+/// `family,method,best_cost,iters`. The JSON formats always keep every field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns(Vec<usize>);
+
+impl Columns {
+    fn headers(&self) -> Vec<String> {
+        self.0.iter().map(|&i| COLUMNS[i].1.to_string()).collect()
+    }
+
+    fn fields(&self, result: &Result, digits: usize) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|&i| (COLUMNS[i].2)(result, digits))
+            .collect()
+    }
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self((0..DEFAULT_COLUMNS).collect())
+    }
+}
+
+impl FromStr for Columns {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let columns = s
+            .split(',')
+            .map(|name| {
+                let name = name.trim();
+                COLUMNS
+                    .iter()
+                    .position(|(x, _, _)| *x == name)
+                    .ok_or_else(|| {
+                        let names = COLUMNS.map(|(x, _, _)| x);
+                        format!(
+                            "unknown column `{name}`, expected any of: {}",
+                            names.join(", ")
+                        )
+                    })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Self(columns))
+    }
+}
+
+impl Format {
+    /// Same as [`Format::render`] keeping only the given `columns` of the rows, with the costs,
+    /// params and other floats rounded to `digits` significant digits. The JSON formats keep the
+    /// exact values.
+    pub fn render_columns(
+        &self,
+        report: &Report<Result>,
+        columns: &Columns,
+        digits: usize,
+    ) -> String {
+        let rows = report.results.iter().map(|x| columns.fields(x, digits));
+        match self {
+            Format::Json | Format::JsonPretty => self.render(report),
+            Format::Csv => {
+                let line = |fields: Vec<String>| {
+                    let fields = fields.iter().map(|x| csv_field(x)).collect::<Vec<_>>();
+                    fields.join(",") + "\n"
+                };
+                std::iter::once(columns.headers())
+                    .chain(rows)
+                    .map(line)
+                    .collect()
+            }
+            Format::Table | Format::Markdown => {
+                let mut builder = Builder::new();
+                builder.set_columns(columns.headers());
+                for row in rows {
+                    builder.add_record(row);
+                }
+                let mut table = builder.build();
+                match self {
+                    Format::Markdown => table.with(Style::markdown()).to_string(),
+                    _ => table.with(Style::modern()).to_string(),
+                }
+            }
+        }
+    }
+}
+
+fn to_csv<T: Tabled>(rows: &[T]) -> String {
+    let line = |fields: Vec<std::borrow::Cow<'_, str>>| {
+        fields
+            .iter()
+            .map(|x| csv_field(x))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let mut out = line(T::headers());
+    out.push('\n');
+    for row in rows {
+        out.push_str(&line(row.fields()));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quotes a field when it contains a separator, a quote or a newline.
+pub(crate) fn csv_field(x: &str) -> String {
+    if x.contains([',', '"', '\n']) {
+        format!("\"{}\"", x.replace('"', "\"\""))
+    } else {
+        x.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_csv() {
+        let rows = vec![
+            Result::new("Linear search", "Backtracking", 0.5, None, 10, None),
+            Result::new("", "Simulated Annealing", 2.0, None, 10, None)
+                .with_acceptance_rate(Some(0.4567)),
+            Result::new(
+                "",
+                "Nelder-Mead",
+                1.0,
+                None,
+                3,
+                Some(&TerminationReason::SolverExit("a, b".to_string())),
+            ),
+        ];
+        let csv = Format::Csv.render(&Report::new("rosenbrock", RunParams::default(), rows));
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Family,Method,BestCost,Time,Iterations,IterationCap,Evaluations,TerminationReason,Status,Retries,BestOf,BestOfSeed,AcceptanceRate,FallbackSteps,LinesearchFallback,Restarts,BestAttempt,CacheHitRate,StepLength,ConvergenceRate,TimePerIter"
+        );
+        assert_eq!(
+            lines[1],
+            "Linear search,Backtracking,0.5,-,10,-,-,-,unknown,-,-,-,-,-,-,-,-,-,-,-,-"
+        );
+        assert_eq!(
+            lines[2],
+            ",Simulated Annealing,2,-,10,-,-,-,unknown,-,-,-,45.7%,-,-,-,-,-,-,-,-"
+        );
+        assert_eq!(
+            lines[3],
+            ",Nelder-Mead,1,-,3,-,-,\"a, b\",converged,-,-,-,-,-,-,-,-,-,-,-,-"
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let rows = vec![
+            Result::new("Linear search", "Backtracking", 0.5, None, 10, None),
+            Result::failed("", "Nelder-Mead", &"a, b"),
+        ];
+        let report = Report::new("rosenbrock", RunParams::default(), rows);
+        for format in [Format::Table, Format::Csv, Format::Markdown] {
+            assert_eq!(
+                format.render_columns(&report, &Columns::default(), DEFAULT_PRECISION),
+                format.render(&report)
+            );
+        }
+
+        let columns = "method, best_cost,iters".parse().unwrap();
+        let csv = Format::Csv.render_columns(&report, &columns, DEFAULT_PRECISION);
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "Method,BestCost,Iterations",
+                "Backtracking,0.5,10",
+                "Nelder-Mead,failed,0"
+            ]
+        );
+        assert!("method,cost".parse::<Columns>().is_err());
+
+        let rows = vec![Result::new("", "BFGS", 0.123456789, None, 10, None)
+            .with_best_param(Some(vec![1.0, 0.99999912345]))];
+        let report = Report::new("rosenbrock", RunParams::default(), rows);
+        let columns = "best_cost,best_param".parse().unwrap();
+        assert_eq!(
+            Format::Csv
+                .render_columns(&report, &columns, DEFAULT_PRECISION)
+                .lines()
+                .nth(1),
+            Some("0.123457,\"[1, 0.999999]\"")
+        );
+        assert_eq!(
+            Format::Csv
+                .render_columns(&report, &columns, 3)
+                .lines()
+                .nth(1),
+            Some("0.123,\"[1, 1]\"")
+        );
+    }
+
+    #[test]
+    fn test_format_float() {
+        assert_eq!(format_float(0.17253159887233474, 6), "0.172532");
+        assert_eq!(format_float(3.0391310107898667e-26, 6), "3.03913e-26");
+        assert_eq!(format_float(1234567.0, 3), "1.23e6");
+        assert_eq!(format_float(0.5, 6), "0.5");
+        assert_eq!(format_float(-2.0, 1), "-2");
+        assert_eq!(format_float(0.1 + 0.2, 17), "0.30000000000000004");
+        assert_eq!(format_float(f64::NAN, 6), "NaN");
+    }
+
+    #[test]
+    fn test_run_status() {
+        let status = |reason: TerminationReason| RunStatus::new(Some(&reason), false);
+        assert_eq!(
+            status(TerminationReason::SolverConverged),
+            RunStatus::Converged
+        );
+        assert_eq!(
+            status(TerminationReason::TargetCostReached),
+            RunStatus::Converged
+        );
+        assert_eq!(
+            status(TerminationReason::SolverExit("Stalled".to_string())),
+            RunStatus::Converged
+        );
+        assert_eq!(
+            status(TerminationReason::MaxItersReached),
+            RunStatus::MaxIters
+        );
+        assert_eq!(
+            status(TerminationReason::KeyboardInterrupt),
+            RunStatus::Failed
+        );
+        assert_eq!(
+            RunStatus::new(Some(&TerminationReason::SolverConverged), true),
+            RunStatus::Failed
+        );
+        assert_eq!(RunStatus::new(None, false), RunStatus::Unknown);
+
+        let converged = Some(&TerminationReason::SolverConverged);
+        let rows = [
+            Result::new("", "Newton", f64::NAN, None, 3, converged),
+            Result::failed("", "Newton", &"no hessian"),
+            Result::skipped("", "Newton", "no hessian"),
+            Result::timed_out("", "Newton"),
+        ];
+        let statuses = rows.iter().map(|x| x.status).collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                RunStatus::Failed,
+                RunStatus::Failed,
+                RunStatus::Skipped,
+                RunStatus::Timeout
+            ]
+        );
+        let json = serde_json::to_string(&rows[3]).unwrap();
+        assert!(json.contains(r#""status":"timeout""#), "{json}");
+    }
+
+    #[test]
+    fn test_diverged() {
+        let mut rows = vec![
+            Result::new("", "Nan", f64::NAN, None, 1, None),
+            Result::new("", "Inf", f64::INFINITY, None, 1, None),
+            Result::new("", "Worse", 2.0, None, 1, None),
+            Result::new("", "Better", -1.0, None, 1, None),
+            Result::failed("", "Failed", &"no hessian"),
+            Result::skipped("", "Skipped", "time budget exhausted"),
+        ];
+        assert!(rows[0].diverged && rows[1].diverged && !rows[2].diverged);
+        sort_by_cost(&mut rows, true);
+        assert_eq!(rows[0].method, "Worse");
+        sort_by_cost(&mut rows, false);
+        let methods = rows.iter().map(|x| x.method.as_str()).collect::<Vec<_>>();
+        assert_eq!(methods[..2], ["Better", "Worse"]);
+        let csv = Format::Csv.render(&Report::new("rosenbrock", RunParams::default(), rows));
+        assert_eq!(
+            csv.lines().nth(3),
+            Some(",Inf,diverged,-,1,-,-,-,failed,-,-,-,-,-,-,-,-,-,-,-,-")
+        );
+        assert_eq!(
+            csv.lines().nth(5),
+            Some(",Failed,failed,-,0,-,-,Error: no hessian,failed,-,-,-,-,-,-,-,-,-,-,-,-")
+        );
+        assert_eq!(
+            csv.lines().nth(6),
+            Some(
+                ",Skipped,skipped,-,0,-,-,Skipped: time budget exhausted,skipped,-,-,-,-,-,-,-,-,-,-,-,-"
+            )
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let params = RunParams {
+            init: Some(vec![10.2, -20.0]),
+            seed: Some(42),
+            seed_drawn: true,
+            max_iters: 100,
+            noise_stddev: None,
+            eval_budget: None,
+            maximize: false,
+        };
+        let rows = vec![
+            Result::new("Newton methods", "Newton", f64::INFINITY, None, 100, None)
+                .with_best_param(Some(vec![f64::NAN, 1.0])),
+            Result::new(
+                "Quasi-Newton methods",
+                "L-BFGS",
+                1e-20,
+                Some(Duration::from_millis(2)),
+                31,
+                Some(&TerminationReason::SolverConverged),
+            )
+            .with_best_param(Some(vec![1.0, 1.0])),
+        ];
+        let report = Report::new("rosenbrock", params, rows);
+        for format in [Format::Json, Format::JsonPretty] {
+            let json = format.render(&report);
+            let parsed: Report<Result> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.schema, SCHEMA_VERSION);
+            assert_eq!(parsed.params, report.params);
+            assert!(parsed.results[0].best_cost.is_nan() && parsed.results[0].diverged);
+            assert!(parsed.results[0].best_param.as_ref().unwrap()[0].is_nan());
+            assert_eq!(parsed.results[1], report.results[1]);
+        }
+        let schema = format!("\n  \"schema\": {SCHEMA_VERSION}");
+        assert!(Format::JsonPretty.render(&report).contains(&schema));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        for format in [
+            Format::Table,
+            Format::Csv,
+            Format::Json,
+            Format::JsonPretty,
+            Format::Markdown,
+        ] {
+            assert_eq!(format.to_string().parse::<Format>(), Ok(format));
+        }
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_sweep_row() {
+        let results = [4.0, 1.0, 3.0, 2.0]
+            .map(|x| Result::new("", "SA", x, None, 1, None))
+            .into_iter()
+            .chain([Result::failed("", "SA", &"boom")])
+            .collect::<Vec<_>>();
+        let row = SweepRow::new(&results);
+        assert_eq!((row.runs, row.failed), (5, 1));
+        assert_eq!(
+            (row.min, row.median, row.max),
+            (Some(1.0), Some(2.5), Some(4.0))
+        );
+        assert_eq!(row.mean, Some(2.5));
+        assert!((row.std.unwrap() - 1.25f64.sqrt()).abs() < 1e-12);
+        assert_eq!(SweepRow::new(&results[4..]).mean, None);
+    }
+
+    #[test]
+    fn test_line_search_row() {
+        let capped = Result::new(
+            "",
+            "Backtracking",
+            0.5,
+            None,
+            100,
+            Some(&TerminationReason::MaxItersReached),
+        );
+        let row = LineSearchRow::new(100, &capped);
+        assert_eq!((row.iters_to_converge, row.best_cost), (None, Some(0.5)));
+        let reached = TerminationReason::SolverExit("param target".to_string());
+        let converged = Result::new("", "Hager-Zhang", 1e-9, None, 42, Some(&reached));
+        assert_eq!(
+            LineSearchRow::new(100, &converged).iters_to_converge,
+            Some(42)
+        );
+        let failed = LineSearchRow::new(100, &Result::failed("", "More-Thuente", &"boom"));
+        assert_eq!((failed.iters_to_converge, failed.best_cost), (None, None));
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let rows = || {
+            vec![
+                Result::new("Newton methods", "Newton", 2.0, None, 5, None),
+                Result::new("", "Nelder-Mead", 6.4, None, 5, None),
+                Result::new("", "Simulated Annealing (fast)", 1.0, None, 5, None),
+                Result::failed("", "BFGS", &"no hessian"),
+            ]
+        };
+        let relative = relative_to(rows(), Method::Newton).unwrap();
+        let relative = relative.iter().map(|x| x.relative).collect::<Vec<_>>();
+        assert_eq!(
+            relative,
+            [
+                Some(Relative::Ratio(1.0)),
+                Some(Relative::Ratio(3.2)),
+                Some(Relative::Ratio(0.5)),
+                None
+            ]
+        );
+        assert_eq!(Relative::Ratio(3.2).to_string(), "3.200x");
+
+        let mut zero = rows();
+        zero[0].best_cost = 0.0;
+        let relative = relative_to(zero, Method::Newton).unwrap();
+        assert_eq!(relative[1].relative, Some(Relative::Difference(6.4)));
+        assert_eq!(relative[1].relative.unwrap().to_string(), "+6.40e0");
+
+        let csv = Format::Csv.render(&Report::new(
+            "rosenbrock",
+            RunParams::default(),
+            relative_to(rows(), Method::SimulatedAnnealing).unwrap(),
+        ));
+        assert!(csv.lines().next().unwrap().ends_with(",Relative"));
+        assert!(csv.lines().nth(2).unwrap().ends_with(",6.400x"));
+
+        let err = relative_to(rows(), Method::Lbfgs).unwrap_err();
+        assert_eq!(err, "the baseline `l-bfgs` was not run");
+        assert!(relative_to(rows(), Method::Bfgs).is_err());
+    }
+}
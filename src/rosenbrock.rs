@@ -0,0 +1,78 @@
+//! Derivatives of the chained N-dimensional rosenbrock function, shared by `RosenbrockVec` and
+//! `RosenbrockND`. `argmin_testfunctions` only provides them for two dimensions.
+use ndarray::Array2;
+
+/// Gradient of the chained rosenbrock function, each term $(x_i, x_{i+1})$ contributes to two
+/// entries.
+pub(crate) fn rosenbrock_derivative(param: &[f64], a: f64, b: f64) -> Vec<f64> {
+    let mut gradient = vec![0.0; param.len()];
+    for (i, w) in param.windows(2).enumerate() {
+        let (x, y) = (w[0], w[1]);
+        gradient[i] += -2.0 * (a - x) - 4.0 * b * x * (y - x.powi(2));
+        gradient[i + 1] += 2.0 * b * (y - x.powi(2));
+    }
+    gradient
+}
+
+/// Cost and gradient of the chained rosenbrock function in one pass, sharing $a - x_i$ and
+/// $x_{i+1} - x_i^2$ between them.
+pub(crate) fn rosenbrock_cost_and_derivative(param: &[f64], a: f64, b: f64) -> (f64, Vec<f64>) {
+    let mut cost = 0.0;
+    let mut gradient = vec![0.0; param.len()];
+    for (i, w) in param.windows(2).enumerate() {
+        let (x, y) = (w[0], w[1]);
+        let (e, d) = (a - x, y - x.powi(2));
+        cost += e.powi(2) + b * d.powi(2);
+        gradient[i] += -2.0 * e - 4.0 * b * x * d;
+        gradient[i + 1] += 2.0 * b * d;
+    }
+    (cost, gradient)
+}
+
+/// Hessian of the chained rosenbrock function, which is tridiagonal. It doesn't depend on `a`.
+pub(crate) fn rosenbrock_hessian_dense(param: &[f64], b: f64) -> Array2<f64> {
+    let n = param.len();
+    let mut hessian = Array2::zeros((n, n));
+    for (i, w) in param.windows(2).enumerate() {
+        let (x, y) = (w[0], w[1]);
+        hessian[(i, i)] += 12.0 * b * x.powi(2) - 4.0 * b * y + 2.0;
+        hessian[(i, i + 1)] += -4.0 * b * x;
+        hessian[(i + 1, i)] += -4.0 * b * x;
+        hessian[(i + 1, i + 1)] += 2.0 * b;
+    }
+    hessian
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::{Gradient, Hessian};
+    use ndarray::Array1;
+
+    use crate::{RosenbrockND, RosenbrockVec};
+
+    #[test]
+    fn test_vec_and_ndarray_agree() {
+        let params = [
+            vec![-1.2, 1.0],
+            vec![10.2, -20.0],
+            vec![0.5, 3.0, -2.0],
+            vec![1.0, 1.0, 1.0],
+        ];
+        let f_vec = RosenbrockVec::default();
+        for param in params {
+            let f_nd = RosenbrockND::with_dim(param.len());
+            let param_nd = Array1::from_vec(param.clone());
+            assert_eq!(
+                f_vec.gradient(&param).unwrap(),
+                f_nd.gradient(&param_nd).unwrap().to_vec()
+            );
+            let hessian = f_nd.hessian(&param_nd).unwrap();
+            let hessian = hessian
+                .rows()
+                .into_iter()
+                .map(|x| x.to_vec())
+                .collect::<Vec<_>>();
+            assert_eq!(f_vec.hessian(&param).unwrap(), hessian, "{param:?}");
+        }
+    }
+}
@@ -1,28 +1,68 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 use argmin::{
-    core::{CostFunction, Gradient, Hessian},
+    core::{ArgminError, CostFunction, Error, Gradient, Hessian},
     solver::simulatedannealing::Anneal,
 };
-use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative, rosenbrock_2d_hessian};
-use ndarray::{array, Array1, Array2};
-use rand::{distributions::Uniform, Rng};
+use argmin_testfunctions::rosenbrock;
+use ndarray::{array, s, Array1, Array2, Axis};
 use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    annealing::{bounded_anneal, DEFAULT_ANNEAL_STEP},
+    rosenbrock::{rosenbrock_cost_and_derivative, rosenbrock_derivative, rosenbrock_hessian_dense},
+};
 
 /// The rosenbrock function is defined as:
 /// $ f(x,y) = (a-x)^2 + b(y-x^2)^2 $
-#[derive(Debug, Clone)]
+///
+/// For more than two dimensions the chained form is used:
+/// $ f(x) = \sum_{i=1}^{n-1} (a-x_i)^2 + b(x_{i+1}-x_i^2)^2 $
+///
+/// Serializing keeps `a`, `b` and the bounds only, a deserialized problem gets a fresh random
+/// number generator.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RosenbrockND {
     a: f64,
     b: f64,
     lower_bound: Array1<f64>,
     upper_bound: Array1<f64>,
+    /// Largest `anneal` move as a fraction of the bound width, see
+    /// [`DEFAULT_ANNEAL_STEP`].
+    #[serde(default = "default_anneal_step")]
+    anneal_step: f64,
     /// Random number generator. We use a `Arc<Mutex<_>>` here because `ArgminOperator` requires
     /// `self` to be passed as an immutable reference. This gives us thread safe interior
     /// mutability.
+    #[serde(skip, default = "entropy_rng")]
     rng: Arc<Mutex<Xoshiro256PlusPlus>>,
 }
 
+/// Leaves out the state of the random number generator, so equal problems print the same.
+impl fmt::Debug for RosenbrockND {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RosenbrockND")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .field("lower_bound", &self.lower_bound)
+            .field("upper_bound", &self.upper_bound)
+            .field("anneal_step", &self.anneal_step)
+            .finish_non_exhaustive()
+    }
+}
+
+fn default_anneal_step() -> f64 {
+    DEFAULT_ANNEAL_STEP
+}
+
+fn entropy_rng() -> Arc<Mutex<Xoshiro256PlusPlus>> {
+    Arc::new(Mutex::new(Xoshiro256PlusPlus::from_entropy()))
+}
+
 impl RosenbrockND {
     pub fn new(a: f64, b: f64, lower_bound: Array1<f64>, upper_bound: Array1<f64>) -> Self {
         Self {
@@ -30,9 +70,213 @@ impl RosenbrockND {
             b,
             lower_bound,
             upper_bound,
-            rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::from_entropy())),
+            anneal_step: DEFAULT_ANNEAL_STEP,
+            rng: entropy_rng(),
         }
     }
+
+    /// Starts from the default rosenbrock, see [`RosenbrockNDBuilder`].
+    pub fn builder() -> RosenbrockNDBuilder {
+        RosenbrockNDBuilder::default()
+    }
+
+    /// Default rosenbrock (`a = 1`, `b = 100`) in `n` dimensions, bounded by `[-5, 5]` on every
+    /// axis.
+    pub fn with_dim(n: usize) -> Self {
+        Self::new(
+            1.0,
+            100.0,
+            Array1::from_elem(n, -5.0),
+            Array1::from_elem(n, 5.0),
+        )
+    }
+
+    /// Reseeds the random number generator used by `anneal`, making runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(Xoshiro256PlusPlus::seed_from_u64(seed)));
+        self
+    }
+
+    /// Scales the `anneal` moves along each axis to `step` times the width of its bounds.
+    pub fn with_anneal_step(mut self, step: f64) -> Self {
+        self.anneal_step = step;
+        self
+    }
+
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    pub fn dim(&self) -> usize {
+        self.lower_bound.len()
+    }
+
+    pub fn lower_bound(&self) -> &Array1<f64> {
+        &self.lower_bound
+    }
+
+    pub fn upper_bound(&self) -> &Array1<f64> {
+        &self.upper_bound
+    }
+
+    /// Cost and gradient at `param`, computed together with their shared terms instead of
+    /// calling [`CostFunction::cost`] and [`Gradient::gradient`] separately. Argmin (0.8) has no
+    /// fused trait, callers evaluating both at the same point use this directly.
+    pub fn cost_and_gradient(&self, param: &Array1<f64>) -> Result<(f64, Array1<f64>), Error> {
+        let (cost, gradient) = rosenbrock_cost_and_derivative(&param.to_vec(), self.a, self.b);
+        check_finite("cost", &[cost], param)?;
+        check_finite("gradient", &gradient, param)?;
+        Ok((cost, Array1::from_vec(gradient)))
+    }
+
+    /// Cost of every row of `params`, computed with whole-array operations instead of one `cost`
+    /// call per point.
+    pub fn cost_batch(&self, params: &Array2<f64>) -> Array1<f64> {
+        let n = params.ncols();
+        if n < 2 {
+            return Array1::zeros(params.nrows());
+        }
+        let x = params.slice(s![.., ..n - 1]);
+        let y = params.slice(s![.., 1..]);
+        let terms = (self.a - &x).mapv(|v| v.powi(2))
+            + (&y - &x.mapv(|v| v.powi(2))).mapv(|v| self.b * v.powi(2));
+        terms.sum_axis(Axis(1))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Bounds {
+    /// The same interval on every axis.
+    Uniform(f64, f64),
+    PerAxis(Array1<f64>, Array1<f64>),
+}
+
+/// Builder of [`RosenbrockND`], every setting defaulting to the ones of
+/// `RosenbrockND::default()`:
+///
+/// ```
+/// use argmin_exploring::RosenbrockND;
+///
+/// let f = RosenbrockND::builder().a(2.0).dim(5).bounds(-3.0, 3.0).seed(7).build().unwrap();
+/// assert_eq!((f.a(), f.b(), f.dim()), (2.0, 100.0, 5));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RosenbrockNDBuilder {
+    a: f64,
+    b: f64,
+    dim: Option<usize>,
+    bounds: Bounds,
+    seed: Option<u64>,
+}
+
+impl Default for RosenbrockNDBuilder {
+    fn default() -> Self {
+        Self {
+            a: 1.0,
+            b: 100.0,
+            dim: None,
+            bounds: Bounds::Uniform(-5.0, 5.0),
+            seed: None,
+        }
+    }
+}
+
+impl RosenbrockNDBuilder {
+    pub fn a(mut self, a: f64) -> Self {
+        self.a = a;
+        self
+    }
+
+    pub fn b(mut self, b: f64) -> Self {
+        self.b = b;
+        self
+    }
+
+    /// Number of dimensions, 2 unless given or implied by [`Self::bound_arrays`].
+    pub fn dim(mut self, dim: usize) -> Self {
+        self.dim = Some(dim);
+        self
+    }
+
+    /// The same bounds on every axis.
+    pub fn bounds(mut self, lower: f64, upper: f64) -> Self {
+        self.bounds = Bounds::Uniform(lower, upper);
+        self
+    }
+
+    /// Bounds of every axis, their lengths must match each other and the dimension if given.
+    pub fn bound_arrays(mut self, lower: Array1<f64>, upper: Array1<f64>) -> Self {
+        self.bounds = Bounds::PerAxis(lower, upper);
+        self
+    }
+
+    /// Seeds the random number generator used by `anneal`, see [`RosenbrockND::with_seed`].
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<RosenbrockND, Error> {
+        let invalid = |text: String| Err(ArgminError::InvalidParameter { text }.into());
+        let (lower_bound, upper_bound) = match self.bounds {
+            Bounds::Uniform(lower, upper) => {
+                let dim = self.dim.unwrap_or(2);
+                (Array1::from_elem(dim, lower), Array1::from_elem(dim, upper))
+            }
+            Bounds::PerAxis(lower, upper) => (lower, upper),
+        };
+        if lower_bound.len() != upper_bound.len() {
+            return invalid(format!(
+                "the lower bound has {} dimensions but the upper bound {}",
+                lower_bound.len(),
+                upper_bound.len()
+            ));
+        }
+        if let Some(dim) = self.dim.filter(|&x| x != lower_bound.len()) {
+            return invalid(format!(
+                "the bounds have {} dimensions but `dim` is {dim}",
+                lower_bound.len()
+            ));
+        }
+        if lower_bound
+            .iter()
+            .zip(&upper_bound)
+            .any(|(l, u)| l > u || l.is_nan() || u.is_nan())
+        {
+            return invalid(format!(
+                "the lower bound {lower_bound} must not exceed the upper bound {upper_bound}"
+            ));
+        }
+        let f = RosenbrockND::new(self.a, self.b, lower_bound, upper_bound);
+        Ok(match self.seed {
+            Some(seed) => f.with_seed(seed),
+            None => f,
+        })
+    }
+}
+
+/// Fails on a cost or gradient that isn't finite, so a run blowing up ends with an error saying
+/// where instead of handing `inf` or `NaN` to the solver.
+fn check_finite(
+    what: &str,
+    values: &[f64],
+    param: &Array1<f64>,
+) -> Result<(), argmin::core::Error> {
+    let problem = if values.iter().any(|x| x.is_nan()) {
+        "is NaN"
+    } else if values.iter().any(|x| x.is_infinite()) {
+        "overflowed"
+    } else {
+        return Ok(());
+    };
+    Err(ArgminError::ConditionViolated {
+        text: format!("{what} {problem} at param {param:e}"),
+    }
+    .into())
 }
 
 impl Default for RosenbrockND {
@@ -46,7 +290,9 @@ impl CostFunction for RosenbrockND {
     type Output = f64;
 
     fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
-        Ok(rosenbrock_2d(&param.to_vec(), self.a, self.b))
+        let cost = rosenbrock(&param.to_vec(), self.a, self.b);
+        check_finite("cost", &[cost], param)?;
+        Ok(cost)
     }
 }
 
@@ -55,7 +301,8 @@ impl Gradient for RosenbrockND {
     type Gradient = Array1<f64>;
 
     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
-        let gradient = rosenbrock_2d_derivative(&param.to_vec(), self.a, self.b);
+        let gradient = rosenbrock_derivative(&param.to_vec(), self.a, self.b);
+        check_finite("gradient", &gradient, param)?;
         Ok(Array1::from_vec(gradient))
     }
 }
@@ -65,8 +312,7 @@ impl Hessian for RosenbrockND {
     type Hessian = Array2<f64>;
 
     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, argmin::core::Error> {
-        let h = rosenbrock_2d_hessian(&param.to_vec(), self.a, self.b);
-        Ok(Array2::from_shape_vec((2, 2), h)?)
+        Ok(rosenbrock_hessian_dense(&param.to_vec(), self.b))
     }
 }
 
@@ -80,31 +326,21 @@ impl Anneal for RosenbrockND {
         param: &Self::Param,
         temp: Self::Float,
     ) -> Result<Self::Output, argmin::core::Error> {
-        let mut param_n = param.clone();
         let mut rng = self.rng.lock().unwrap();
-        let distr = Uniform::from(0..param.len());
-        // Perform modifications to a degree proportional to the current temperature `temp`.
-        for _ in 0..(temp.floor() as u64 + 1) {
-            // Compute random index of the parameter vector using the supplied random number
-            // generator.
-            let idx = rng.sample(distr);
-
-            // Compute random number in [0.1, 0.1].
-            let val = rng.sample(Uniform::new_inclusive(-0.1, 0.1));
-
-            // modify previous parameter value at random position `idx` by `val`
-            param_n[idx] += val;
-
-            // check if bounds are violated. If yes, project onto bound.
-            param_n[idx] = param_n[idx].clamp(self.lower_bound[idx], self.upper_bound[idx]);
-        }
-        Ok(param_n)
+        Ok(bounded_anneal(
+            param,
+            temp,
+            &mut *rng,
+            (&self.lower_bound, &self.upper_bound),
+            self.anneal_step,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative, rosenbrock_2d_hessian};
     use ndarray::array;
 
     #[test]
@@ -127,4 +363,156 @@ mod tests {
             println!("\thessian: {hessian:?}");
         }
     }
+
+    #[test]
+    fn test_rosenbrock_2d_matches_testfunctions() {
+        let f = RosenbrockND::default();
+        let assert_close = |x: &[f64], y: &[f64]| {
+            assert_eq!(x.len(), y.len());
+            for (x, y) in x.iter().zip(y) {
+                assert!((x - y).abs() <= 1e-9 * y.abs().max(1.0), "{x} != {y}");
+            }
+        };
+        for param in [array![-1.2, 1.0], array![10.2, -20.0], array![0.5, 3.0]] {
+            let x = param.to_vec();
+            assert_close(&[f.cost(&param).unwrap()], &[rosenbrock_2d(&x, 1.0, 100.0)]);
+            assert_close(
+                &f.gradient(&param).unwrap().to_vec(),
+                &rosenbrock_2d_derivative(&x, 1.0, 100.0),
+            );
+            assert_close(
+                &f.hessian(&param).unwrap().into_raw_vec(),
+                &rosenbrock_2d_hessian(&x, 1.0, 100.0),
+            );
+        }
+    }
+
+    #[test]
+    fn test_cost_and_gradient() {
+        let f = RosenbrockND::with_dim(4);
+        for param in [
+            array![-1.2, 1.0, -1.2, 1.0],
+            array![10.2, -20.0, 0.5, 3.0],
+            array![1.0, 1.0, 1.0, 1.0],
+        ] {
+            let (cost, gradient) = f.cost_and_gradient(&param).unwrap();
+            let expected = f.cost(&param).unwrap();
+            assert!(
+                (cost - expected).abs() <= 1e-12 * expected.max(1.0),
+                "{cost}"
+            );
+            assert_eq!(gradient, f.gradient(&param).unwrap());
+        }
+        assert!(f.cost_and_gradient(&array![1e200, 0.0, 0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_rosenbrock_with_dim() {
+        let f = RosenbrockND::with_dim(5);
+        let optimum = Array1::ones(5);
+        assert_eq!(f.dim(), 5);
+        assert_eq!(f.cost(&optimum).unwrap(), 0.0);
+        assert!(f.gradient(&optimum).unwrap().iter().all(|&g| g == 0.0));
+        assert_eq!(f.hessian(&optimum).unwrap().dim(), (5, 5));
+    }
+
+    #[test]
+    fn test_builder() {
+        let f = RosenbrockND::builder().build().unwrap();
+        let g = RosenbrockND::default();
+        assert_eq!((f.a(), f.b()), (g.a(), g.b()));
+        assert_eq!(f.lower_bound(), g.lower_bound());
+        assert_eq!(f.upper_bound(), g.upper_bound());
+
+        let f = RosenbrockND::builder()
+            .b(10.0)
+            .dim(4)
+            .bounds(-3.0, 3.0)
+            .seed(7)
+            .build()
+            .unwrap();
+        assert_eq!((f.a(), f.b(), f.dim()), (1.0, 10.0, 4));
+        assert_eq!(f.upper_bound(), &Array1::from_elem(4, 3.0));
+        // Seeded like `with_seed`.
+        let param = array![0.0, 0.0, 0.0, 0.0];
+        let g =
+            RosenbrockND::new(1.0, 10.0, -f.upper_bound(), f.upper_bound().clone()).with_seed(7);
+        assert_eq!(
+            f.anneal(&param, 5.0).unwrap(),
+            g.anneal(&param, 5.0).unwrap()
+        );
+
+        let f = RosenbrockND::builder()
+            .bound_arrays(array![-1.0, -2.0, -3.0], array![1.0, 2.0, 3.0])
+            .build()
+            .unwrap();
+        assert_eq!(f.dim(), 3);
+    }
+
+    #[test]
+    fn test_builder_validates_bounds() {
+        let err = RosenbrockND::builder()
+            .bound_arrays(array![-1.0, -2.0], array![1.0, 2.0, 3.0])
+            .build()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("upper bound 3"), "{err}");
+        let err = RosenbrockND::builder()
+            .dim(3)
+            .bound_arrays(array![-1.0, -2.0], array![1.0, 2.0])
+            .build()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("`dim` is 3"), "{err}");
+        assert!(RosenbrockND::builder().bounds(1.0, -1.0).build().is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let f = RosenbrockND::new(2.0, 50.0, array![-1.0, -2.0], array![3.0, 4.0]).with_seed(42);
+        let json = serde_json::to_string(&f).unwrap();
+        let g: RosenbrockND = serde_json::from_str(&json).unwrap();
+        assert_eq!((g.a(), g.b()), (2.0, 50.0));
+        assert_eq!(g.lower_bound(), f.lower_bound());
+        assert_eq!(g.upper_bound(), f.upper_bound());
+        // The seeded generator isn't carried over.
+        let param = array![0.0, 0.0];
+        let anneal = |f: &RosenbrockND| {
+            (0..10)
+                .map(|_| f.anneal(&param, 1.0).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_ne!(anneal(&g), anneal(&f.with_seed(42)));
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let f = RosenbrockND::default();
+        let param = array![1e200, 0.0];
+        let err = f.cost(&param).unwrap_err().to_string();
+        assert!(
+            err.contains("cost overflowed at param [1e200, 0e0]"),
+            "{err}"
+        );
+        let err = f.gradient(&param).unwrap_err().to_string();
+        assert!(err.contains("gradient"), "{err}");
+        let err = f.cost(&array![f64::NAN, 1.0]).unwrap_err().to_string();
+        assert!(err.contains("cost is NaN"), "{err}");
+    }
+
+    #[test]
+    fn test_cost_batch() {
+        for n in [2, 3, 5] {
+            let f = RosenbrockND::new(2.0, 50.0, Array1::zeros(n), Array1::ones(n));
+            let params = Array2::from_shape_fn((7, n), |(i, j)| (i as f64 - 3.0) * 1.7 + j as f64);
+            let costs = f.cost_batch(&params);
+            for (row, cost) in params.rows().into_iter().zip(&costs) {
+                let expected = f.cost(&row.to_owned()).unwrap();
+                assert!(
+                    (cost - expected).abs() <= 1e-12 * expected.max(1.0),
+                    "{row}"
+                );
+            }
+        }
+    }
 }
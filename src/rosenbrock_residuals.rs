@@ -0,0 +1,112 @@
+use argmin::core::{Jacobian, Operator};
+use ndarray::{Array1, Array2};
+
+use crate::RosenbrockND;
+
+/// Rosenbrock written as a least-squares problem, for Gauss-Newton style solvers.
+///
+/// Every term of the chained form is split in two residuals:
+/// $ r_{2i} = a - x_i $ and $ r_{2i+1} = \sqrt{b}(x_{i+1} - x_i^2) $
+///
+/// so that $ \sum_j r_j^2 $ is exactly the cost of [`RosenbrockND`] with the same `a` and `b`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RosenbrockResiduals {
+    a: f64,
+    b: f64,
+}
+
+impl RosenbrockResiduals {
+    pub fn new(a: f64, b: f64) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Default for RosenbrockResiduals {
+    fn default() -> Self {
+        Self::new(1.0, 100.0)
+    }
+}
+
+impl From<&RosenbrockND> for RosenbrockResiduals {
+    fn from(f: &RosenbrockND) -> Self {
+        Self::new(f.a(), f.b())
+    }
+}
+
+impl Operator for RosenbrockResiduals {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let n = param.len().saturating_sub(1);
+        let sqrt_b = self.b.sqrt();
+        let mut residuals = Array1::zeros(2 * n);
+        for i in 0..n {
+            residuals[2 * i] = self.a - param[i];
+            residuals[2 * i + 1] = sqrt_b * (param[i + 1] - param[i].powi(2));
+        }
+        Ok(residuals)
+    }
+}
+
+impl Jacobian for RosenbrockResiduals {
+    type Param = Array1<f64>;
+    type Jacobian = Array2<f64>;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, argmin::core::Error> {
+        let n = param.len().saturating_sub(1);
+        let sqrt_b = self.b.sqrt();
+        let mut jacobian = Array2::zeros((2 * n, param.len()));
+        for i in 0..n {
+            jacobian[[2 * i, i]] = -1.0;
+            jacobian[[2 * i + 1, i]] = -2.0 * sqrt_b * param[i];
+            jacobian[[2 * i + 1, i + 1]] = sqrt_b;
+        }
+        Ok(jacobian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::CostFunction;
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_sum_of_squares_is_cost() {
+        let params = [
+            array![10.2, -20.0],
+            array![1.0, 1.0],
+            array![-1.2, 1.0],
+            array![0.5, -0.3, 2.0],
+            array![10.2, -20.0, 10.2, -20.0],
+        ];
+        for (a, b) in [(1.0, 100.0), (2.0, 50.0)] {
+            for param in &params {
+                let n = param.len();
+                let f = RosenbrockND::new(a, b, Array1::zeros(n), Array1::ones(n));
+                let residuals = RosenbrockResiduals::from(&f).apply(param).unwrap();
+                let cost = f.cost(param).unwrap();
+                let sum = residuals.mapv(|r| r.powi(2)).sum();
+                assert!((sum - cost).abs() <= 1e-12 * cost.max(1.0), "{param}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_jacobian_finite_differences() {
+        let f = RosenbrockResiduals::new(2.0, 50.0);
+        let param = array![0.5, -0.3, 2.0];
+        let jacobian = f.jacobian(&param).unwrap();
+        let h = 1e-6;
+        for j in 0..param.len() {
+            let mut step = param.clone();
+            step[j] += h;
+            let diff = (f.apply(&step).unwrap() - f.apply(&param).unwrap()) / h;
+            for (i, d) in diff.iter().enumerate() {
+                assert!((d - jacobian[[i, j]]).abs() < 1e-4, "({i}, {j})");
+            }
+        }
+    }
+}
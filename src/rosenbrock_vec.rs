@@ -1,9 +1,14 @@
 use argmin::core::{CostFunction, Gradient, Hessian};
-use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative, rosenbrock_2d_hessian};
+use argmin_testfunctions::rosenbrock;
+use serde::{Deserialize, Serialize};
+
+use crate::rosenbrock::{rosenbrock_derivative, rosenbrock_hessian_dense};
 
 /// The rosenbrock function is defined as:
 /// $ f(x,y) = (a-x)^2 + b(y-x^2)^2 $
-#[derive(Debug, Clone, Copy)]
+///
+/// For more than two dimensions the chained form is used, as in `RosenbrockND`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct RosenbrockVec {
     a: f64,
     b: f64,
@@ -26,7 +31,7 @@ impl CostFunction for RosenbrockVec {
     type Output = f64;
 
     fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
-        Ok(rosenbrock_2d(&param.to_vec(), self.a, self.b))
+        Ok(rosenbrock(param, self.a, self.b))
     }
 }
 
@@ -35,7 +40,7 @@ impl Gradient for RosenbrockVec {
     type Gradient = Vec<f64>;
 
     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
-        Ok(rosenbrock_2d_derivative(&param.to_vec(), self.a, self.b))
+        Ok(rosenbrock_derivative(param, self.a, self.b))
     }
 }
 
@@ -44,8 +49,8 @@ impl Hessian for RosenbrockVec {
     type Hessian = Vec<Vec<f64>>;
 
     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, argmin::core::Error> {
-        let t = rosenbrock_2d_hessian(&param.to_vec(), self.a, self.b);
-        Ok(vec![vec![t[0], t[1]], vec![t[2], t[3]]])
+        let hessian = rosenbrock_hessian_dense(param, self.b);
+        Ok(hessian.rows().into_iter().map(|x| x.to_vec()).collect())
     }
 }
 
@@ -73,4 +78,11 @@ mod tests {
             println!("\thessian: {hessian:?}");
         }
     }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let f = RosenbrockVec::new(2.0, 50.0);
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(serde_json::from_str::<RosenbrockVec>(&json).unwrap(), f);
+    }
 }
@@ -0,0 +1,323 @@
+use std::{collections::BTreeMap, fmt};
+
+use argmin::core::{ArgminError, Error};
+use ndarray::Array1;
+use serde::Serialize;
+
+use crate::{
+    adapters::{unnegate, Rescale, Transform},
+    annealing::AnnealingConfig,
+    bounds::BoundsMode,
+    comparison::{
+        iteration_cap, objective, solver_runs, CgRestart, DeSettings, MomentumSettings,
+        RestartSettings, LBFGS_M,
+    },
+    noisy::NoiseDist,
+    results::{format_param, Format, RunParams, Summary, DEFAULT_PRECISION},
+    Problem, RosenbrockND, RunOptions,
+};
+
+/// Every setting a comparison resolved from its flags and defaults, printed above its results
+/// and written to `run.json` by `--output`, as the one record of what ran.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunConfig {
+    pub problem: String,
+    /// `a` and `b` of rosenbrock, `None` for the other problems.
+    pub a: Option<f64>,
+    pub b: Option<f64>,
+    pub dim: usize,
+    /// Where the init comes from, e.g. `classic` or an init strategy.
+    pub init_source: String,
+    pub params: RunParams,
+    pub format: Format,
+    /// Labels of the methods to run, in order.
+    pub methods: Vec<String>,
+    /// Tolerances in effect, keyed by the flag setting them, e.g. `stall-tol`.
+    pub tolerances: BTreeMap<String, f64>,
+    /// Iterations each method gets out of `params.eval_budget`, in the order of `methods`,
+    /// empty without a budget.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub iteration_caps: Vec<u64>,
+    /// Settings beyond the fields above, one line each, e.g. `Parallel: one thread per method`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// `x` or `-` when unset.
+fn or_dash(x: Option<impl fmt::Display>) -> String {
+    x.map_or_else(|| "-".to_string(), |x| x.to_string())
+}
+
+impl fmt::Display for RunConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params = &self.params;
+        writeln!(f, "Run config:")?;
+        writeln!(f, "  problem:     {}", self.problem)?;
+        writeln!(f, "  a, b:        {}, {}", or_dash(self.a), or_dash(self.b))?;
+        writeln!(f, "  dim:         {}", self.dim)?;
+        let init = params
+            .init
+            .as_deref()
+            .map(|x| format_param(x, DEFAULT_PRECISION));
+        writeln!(f, "  init:        {} ({})", or_dash(init), self.init_source)?;
+        let drawn = match params.seed_drawn {
+            true => " (drawn at random)",
+            false => "",
+        };
+        writeln!(f, "  seed:        {}{drawn}", or_dash(params.seed))?;
+        writeln!(f, "  max iters:   {}", params.max_iters)?;
+        writeln!(f, "  eval budget: {}", or_dash(params.eval_budget))?;
+        writeln!(f, "  noise:       {}", or_dash(params.noise_stddev))?;
+        writeln!(f, "  maximize:    {}", params.maximize)?;
+        writeln!(f, "  format:      {}", self.format)?;
+        writeln!(
+            f,
+            "  methods:     {} ({})",
+            self.methods.join(", "),
+            self.methods.len()
+        )?;
+        let tolerances = self
+            .tolerances
+            .iter()
+            .map(|(name, tol)| format!("{name} {tol}"))
+            .collect::<Vec<_>>();
+        match tolerances.is_empty() {
+            true => write!(f, "  tolerances:  -"),
+            false => write!(f, "  tolerances:  {}", tolerances.join(", ")),
+        }
+    }
+}
+
+impl RunConfig {
+    /// What `--dry-run` prints: the config, its notes and the methods to run, one per line
+    /// along with their iteration cap under an evaluation budget.
+    pub fn dry_run(&self) -> String {
+        let mut text = format!("{self}\n");
+        for note in &self.notes {
+            text += &format!("{note}\n");
+        }
+        text += &format!("Methods ({}):\n", self.methods.len());
+        for (i, label) in self.methods.iter().enumerate() {
+            match self.iteration_caps.get(i) {
+                Some(cap) => text += &format!("  {label}, max iters {cap}\n"),
+                None => text += &format!("  {label}\n"),
+            }
+        }
+        text
+    }
+}
+
+/// Runs every method of `config` on its problem from its init, seeded, capped and wrapped like
+/// the comparison of `02-rosenbrock` with its default solver settings: the labels of `config`
+/// pick the methods of [`solver_runs`]. The noise is drawn from the default distribution,
+/// `options` sets the tolerances the runs stop on, `tolerances` is only recorded. Maximized rows
+/// report the largest values found.
+///
+/// Returns an error for an unknown problem or method, a least-squares method maximizing, and
+/// for a missing init or one of the wrong dimension.
+pub fn run_comparison(config: &RunConfig, options: &RunOptions) -> Result<Summary, Error> {
+    let invalid = |text: String| -> Error { ArgminError::InvalidParameter { text }.into() };
+    let params = &config.params;
+    let problem = match config.problem.parse::<Problem>().map_err(invalid)? {
+        Problem::Rosenbrock(_) => Problem::Rosenbrock(
+            RosenbrockND::builder()
+                .a(config.a.unwrap_or(1.0))
+                .b(config.b.unwrap_or(100.0))
+                .dim(config.dim)
+                .build()?,
+        ),
+        problem => problem.with_dim(config.dim).map_err(invalid)?,
+    };
+    let init = match &params.init {
+        Some(init) if init.len() == problem.dim() => Array1::from(init.clone()),
+        Some(init) => {
+            return Err(invalid(format!(
+                "the init has {} values but `{problem}` has dimension {}",
+                init.len(),
+                problem.dim()
+            )))
+        }
+        None => return Err(invalid("`run_comparison` needs an init".to_string())),
+    };
+    let problem = Rescale::identity(problem.clone(), problem.dim());
+    let de = DeSettings::default();
+    let max_iters =
+        |method| iteration_cap(method, params.max_iters, params.eval_budget, de.population);
+    let runs = solver_runs(
+        &problem,
+        &init,
+        max_iters,
+        &AnnealingConfig::default(),
+        &CgRestart::default(),
+        LBFGS_M,
+        None,
+        &de,
+        &MomentumSettings::default(),
+        &RestartSettings::default(),
+        false,
+        params.seed,
+    )
+    .map_err(invalid)?;
+    let rows = config
+        .methods
+        .iter()
+        .map(|label| {
+            // Seeded by its position among every method, like in the binary.
+            let Some((index, run)) = runs.iter().enumerate().find(|(_, x)| x.label == *label)
+            else {
+                return Err(invalid(format!("unknown method `{label}`")));
+            };
+            if params.maximize && run.method.needs_residuals() {
+                return Err(invalid(format!(
+                    "`{label}` is a least-squares method, it can't maximize"
+                )));
+            }
+            let objective = objective(
+                &problem,
+                params.maximize,
+                Transform::None,
+                None,
+                params.noise_stddev,
+                NoiseDist::default(),
+                BoundsMode::Unbounded,
+                params.seed,
+                index,
+                None,
+                None,
+            );
+            let mut result = run.run(objective, init.clone(), options);
+            if params.eval_budget.is_some() {
+                result.iteration_cap = Some(max_iters(run.method));
+            }
+            Ok(match params.maximize {
+                true => unnegate(result),
+                false => result,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(Summary {
+        rows,
+        config: config.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::RunStatus;
+
+    #[test]
+    fn test_display_every_field() {
+        let config = RunConfig {
+            problem: "rosenbrock".to_string(),
+            a: Some(1.5),
+            b: Some(100.0),
+            dim: 2,
+            init_source: "classic".to_string(),
+            params: RunParams {
+                init: Some(vec![-1.2, 1.0]),
+                seed: Some(42),
+                seed_drawn: true,
+                max_iters: 250,
+                noise_stddev: Some(0.01),
+                eval_budget: Some(1000),
+                maximize: true,
+            },
+            format: Format::Markdown,
+            methods: vec!["BFGS".to_string(), "L-BFGS".to_string()],
+            tolerances: BTreeMap::from([("stall-tol".to_string(), 1e-8)]),
+            iteration_caps: vec![500, 125],
+            notes: vec!["Parallel: one thread per method".to_string()],
+        };
+        let text = config.to_string();
+        for expected in [
+            "problem:     rosenbrock",
+            "a, b:        1.5, 100",
+            "dim:         2",
+            "init:        [-1.2, 1] (classic)",
+            "seed:        42 (drawn at random)",
+            "max iters:   250",
+            "eval budget: 1000",
+            "noise:       0.01",
+            "maximize:    true",
+            "format:      markdown",
+            "methods:     BFGS, L-BFGS (2)",
+            "tolerances:  stall-tol 0.00000001",
+        ] {
+            assert!(text.contains(expected), "{expected} not in:\n{text}");
+        }
+        // One line per field, `a` and `b` sharing one.
+        assert_eq!(text.lines().count(), 13);
+
+        let dry_run = config.dry_run();
+        let tail = dry_run.strip_prefix(&format!("{text}\n")).unwrap();
+        assert_eq!(
+            tail,
+            "Parallel: one thread per method\n\
+             Methods (2):\n  \
+             BFGS, max iters 500\n  \
+             L-BFGS, max iters 125\n"
+        );
+    }
+
+    #[test]
+    fn test_run_comparison_one_row_per_method() {
+        let config = RunConfig {
+            problem: "rosenbrock".to_string(),
+            a: Some(1.0),
+            b: Some(100.0),
+            dim: 2,
+            init_source: "classic".to_string(),
+            params: RunParams {
+                init: Some(vec![-1.2, 1.0]),
+                seed: Some(7),
+                max_iters: 100,
+                ..Default::default()
+            },
+            format: Format::Table,
+            methods: vec![
+                "BFGS".to_string(),
+                "Nelder-Mead".to_string(),
+                "Particle Swarm".to_string(),
+            ],
+            tolerances: BTreeMap::new(),
+            iteration_caps: Vec::new(),
+            notes: Vec::new(),
+        };
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let summary = run_comparison(&config, &options).unwrap();
+        let methods = summary.rows.iter().map(|x| x.method.as_str());
+        assert!(methods.eq(config.methods.iter().map(String::as_str)));
+        assert_eq!(summary.rows[0].status, RunStatus::Converged);
+        assert!(summary.rows[0].best_cost < 1e-12);
+        // Particle swarm gets the bounds of the problem, and the same rows from the same seed.
+        assert!(!summary.rows[2].is_failure());
+        let again = run_comparison(&config, &options).unwrap();
+        assert_eq!(again.rows[2].best_cost, summary.rows[2].best_cost);
+        assert_eq!(summary.report().results.len(), 3);
+
+        // Maximized within the bounds under a budget, with noise on the cost.
+        let config = RunConfig {
+            params: RunParams {
+                noise_stddev: Some(0.01),
+                eval_budget: Some(1000),
+                maximize: true,
+                ..config.params.clone()
+            },
+            methods: vec!["Particle Swarm".to_string()],
+            ..config
+        };
+        let summary = run_comparison(&config, &options).unwrap();
+        assert_eq!(summary.rows[0].iteration_cap, Some(2));
+        assert!(summary.rows[0].best_cost > 1.0);
+
+        let config = RunConfig {
+            methods: vec!["Simplex".to_string()],
+            ..config
+        };
+        assert!(run_comparison(&config, &options).is_err());
+    }
+}
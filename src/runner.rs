@@ -0,0 +1,1016 @@
+use std::{
+    fmt::Debug,
+    thread,
+    time::{Duration, Instant},
+};
+
+use argmin::{
+    core::{
+        observers::{ObserverMode, SlogLogger},
+        DeserializeOwnedAlias, Error, Executor, SerializeAlias, Solver, State,
+    },
+    solver::particleswarm::Particle,
+};
+use ndarray::Array1;
+
+#[cfg(feature = "log")]
+use crate::log_observer::LogObserver;
+use crate::{
+    methods::Method,
+    observers::{
+        AcceptanceObserver, CostHistoryObserver, CsvHistoryObserver, FallbackObserver,
+        GradientNorm, Logger, NdjsonObserver, RestartObserver, TimePerIterObserver,
+        TrajectoryObserver,
+    },
+    optimum::ParamTarget,
+    problems::Derivatives,
+    results,
+    step_length::LineSearchError,
+    termination::{Stall, Stopping},
+};
+
+/// Solver parameters that can be flattened into a plain vector to be stored in the results.
+pub trait ToParamVec {
+    fn to_param_vec(&self) -> Vec<f64>;
+}
+
+impl ToParamVec for Array1<f64> {
+    fn to_param_vec(&self) -> Vec<f64> {
+        self.to_vec()
+    }
+}
+
+impl ToParamVec for Vec<f64> {
+    fn to_param_vec(&self) -> Vec<f64> {
+        self.clone()
+    }
+}
+
+impl ToParamVec for Particle<Array1<f64>, f64> {
+    fn to_param_vec(&self) -> Vec<f64> {
+        self.position.to_vec()
+    }
+}
+
+/// Settings shared by every solver run of a comparison.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Log the state every given number of iterations.
+    pub log_every: u64,
+    /// Stop as soon as the best param is close enough to the optimum.
+    pub param_target: Option<ParamTarget>,
+    /// Stop once the best cost stopped improving.
+    pub stall: Option<Stall>,
+    /// Record the best cost after every iteration in the result.
+    pub cost_history: bool,
+    /// Record the param and cost after every iteration in the result.
+    pub trajectory: bool,
+    /// Append the cost of every iteration to a CSV shared by all the runs.
+    pub csv_history: Option<CsvHistoryObserver>,
+    /// Stream every iteration of every run as a JSON line.
+    pub ndjson: Option<NdjsonObserver>,
+    /// Skip the solvers started once the budget of the whole sweep is spent.
+    pub time_budget: Option<TimeBudget>,
+    /// Record the time taken by every iteration in the result.
+    pub time_per_iter: bool,
+    /// Neither log the state nor print the outcome.
+    pub quiet: bool,
+    /// Where the state is logged unless `quiet`.
+    pub logger: Logger,
+}
+
+impl RunOptions {
+    /// Options of the discarded runs of `--warmup`: quiet and recording nothing, so only the
+    /// timed runs show up in the logs and histories.
+    pub fn for_warmup(&self) -> Self {
+        Self {
+            cost_history: false,
+            trajectory: false,
+            csv_history: None,
+            ndjson: None,
+            time_per_iter: false,
+            quiet: true,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            log_every: 10,
+            param_target: None,
+            stall: None,
+            cost_history: false,
+            trajectory: false,
+            csv_history: None,
+            ndjson: None,
+            time_budget: None,
+            time_per_iter: false,
+            quiet: false,
+            logger: Logger::default(),
+        }
+    }
+}
+
+/// Time allowed for a whole sweep of solvers, counted from its creation.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeBudget {
+    start: Instant,
+    budget: Duration,
+}
+
+impl TimeBudget {
+    pub fn new(budget: Duration) -> Self {
+        Self::starting_at(Instant::now(), budget)
+    }
+
+    /// Budget counted from `start` instead of now.
+    pub fn starting_at(start: Instant, budget: Duration) -> Self {
+        Self { start, budget }
+    }
+
+    pub fn exhausted(&self) -> bool {
+        self.exhausted_at(Instant::now())
+    }
+
+    /// Whether the budget is spent at the time `now`.
+    pub fn exhausted_at(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) > self.budget
+    }
+}
+
+/// Runs `solver` on `problem` with the given `options` and summarizes the outcome. If the solver
+/// returns an error it is recorded in the row instead, if the time budget is already spent the
+/// solver is not run and the row is marked as skipped.
+///
+/// `configure` initializes the solver state (initial param, max iters, etc.), in the same way as
+/// `Executor::configure`.
+pub fn run_solver<O, S, I>(
+    family: &str,
+    method: &str,
+    problem: O,
+    solver: S,
+    configure: impl FnOnce(I) -> I,
+    options: &RunOptions,
+) -> results::Result
+where
+    S: Solver<O, I>,
+    I: State<Float = f64> + GradientNorm + SerializeAlias + DeserializeOwnedAlias,
+    I::Param: ToParamVec + Debug,
+{
+    if options.time_budget.is_some_and(|x| x.exhausted()) {
+        return results::Result::timed_out(family, method);
+    }
+    let solver = Stopping::new(solver)
+        .param_target(options.param_target.clone())
+        .stall(options.stall);
+    let history = CostHistoryObserver::new();
+    let acceptance = AcceptanceObserver::new();
+    let fallbacks = FallbackObserver::new();
+    let restarts = RestartObserver::new();
+    let times = TimePerIterObserver::new();
+    let trajectory = TrajectoryObserver::new();
+    let mut executor = Executor::new(problem, solver)
+        .add_observer(acceptance.clone(), ObserverMode::Always)
+        .add_observer(fallbacks.clone(), ObserverMode::Always)
+        .add_observer(restarts.clone(), ObserverMode::Always)
+        .configure(configure);
+    if !options.quiet {
+        let mode = ObserverMode::Every(options.log_every);
+        executor = match options.logger {
+            Logger::Term => executor.add_observer(SlogLogger::term(), mode),
+            #[cfg(feature = "log")]
+            Logger::Log => executor.add_observer(LogObserver::new(), mode),
+        };
+    }
+    if options.cost_history {
+        executor = executor.add_observer(history.clone(), ObserverMode::Always);
+    }
+    if options.time_per_iter {
+        executor = executor.add_observer(times.clone(), ObserverMode::Always);
+    }
+    if options.trajectory {
+        executor = executor.add_observer(trajectory.clone(), ObserverMode::Always);
+    }
+    if let Some(csv) = &options.csv_history {
+        executor = executor.add_observer(csv.for_method(method), ObserverMode::Always);
+    }
+    if let Some(ndjson) = &options.ndjson {
+        executor = executor.add_observer(ndjson.for_method(method), ObserverMode::Always);
+    }
+    let res = executor.run();
+    match res {
+        Ok(res) => {
+            if !options.quiet {
+                println!("{method}: {res}");
+            }
+            results::Result::new(
+                family,
+                method,
+                res.state.get_best_cost(),
+                res.state.get_time(),
+                res.state.get_iter(),
+                res.state.get_termination_reason(),
+            )
+            .with_best_param(res.state.get_best_param().map(ToParamVec::to_param_vec))
+            .with_evaluations(Some(res.state.get_func_counts().values().sum()))
+            .with_best_cost_history(options.cost_history.then(|| history.best_costs()))
+            .with_acceptance_rate(acceptance.counts().rate())
+            .with_fallback_steps(fallbacks.count())
+            .with_restarts(restarts.restarts(), restarts.best_attempt())
+            .with_time_per_iter(times.times())
+            .with_trajectory(options.trajectory.then(|| trajectory.trajectory()))
+        }
+        Err(e) => {
+            eprintln!("{method} failed: {e}");
+            results::Result::failed(family, method, &e)
+                .with_linesearch_error(is_linesearch_error(&e))
+        }
+    }
+}
+
+/// Calls `run` `repeat` times and returns the first row with the time averaged over all the
+/// runs, for more stable timings. Failed runs are not repeated, and repeats without a time (e.g.
+/// skipped once the time budget is spent) are left out of the average.
+pub fn run_repeated(repeat: usize, mut run: impl FnMut() -> results::Result) -> results::Result {
+    let first = run();
+    let Some(mut total) = first.duration else {
+        return first;
+    };
+    let mut count = 1;
+    for _ in 1..repeat {
+        if let Some(duration) = run().duration {
+            total += duration;
+            count += 1;
+        }
+    }
+    first.with_duration(total / count)
+}
+
+/// Calls `run` with attempt `0`, then with the next attempts while `success` rejects the result,
+/// at most `retries` more times. Returns the best row of all the attempts, the one with the
+/// lowest best cost that didn't fail, along with the number of retries used.
+///
+/// Attempts should draw their random numbers from [`attempt_seed`], so every retry is new.
+pub fn run_with_retries(
+    retries: usize,
+    success: impl Fn(&results::Result) -> bool,
+    mut run: impl FnMut(usize) -> results::Result,
+) -> results::Result {
+    let mut best = run(0);
+    let mut attempt = 0;
+    while attempt < retries && !best.skipped && !success(&best) {
+        attempt += 1;
+        let result = run(attempt);
+        if !result.is_failure() && (best.is_failure() || result.best_cost < best.best_cost) {
+            best = result;
+        }
+    }
+    best.with_retries(Some(attempt as u64))
+}
+
+/// [`run_with_retries`] of `first`, where every retry runs a new solver made by `rebuild` from
+/// the seed of the attempt (see [`attempt_seed`]). Stochastic solvers draw their random numbers
+/// themselves, rerunning `first` would only repeat its first attempt.
+pub fn run_with_fresh_retries<O>(
+    retries: usize,
+    success: impl Fn(&results::Result) -> bool,
+    first: &SolverRun<O>,
+    seed: Option<u64>,
+    rebuild: impl Fn(Option<u64>) -> SolverRun<O>,
+    mut run: impl FnMut(&SolverRun<O>, usize) -> results::Result,
+) -> results::Result {
+    run_with_retries(retries, success, |attempt| match attempt {
+        0 => run(first, attempt),
+        _ => run(&rebuild(attempt_seed(seed, attempt)), attempt),
+    })
+}
+
+/// Calls `run` once per seed of `seeds`, with the position of the seed, and returns the best
+/// row: the one with the lowest best cost that didn't fail, or the first one when they all
+/// failed. The row records the number of runs and the seed it came from.
+///
+/// Unlike [`run_with_retries`] every run happens, as stochastic solvers are used in practice.
+pub fn run_best_of(
+    seeds: &[u64],
+    mut run: impl FnMut(usize) -> results::Result,
+) -> results::Result {
+    let mut best = (run(0), seeds[0]);
+    for (i, &seed) in seeds.iter().enumerate().skip(1) {
+        let result = run(i);
+        if !result.is_failure() && (best.0.is_failure() || result.best_cost < best.0.best_cost) {
+            best = (result, seed);
+        }
+    }
+    let (best, seed) = best;
+    best.with_best_of(seeds.len() as u64, Some(seed))
+}
+
+/// Seed of the given attempt of [`run_with_retries`], the first attempt keeps `seed` so results
+/// don't change unless a retry happens.
+pub fn attempt_seed(seed: Option<u64>, attempt: usize) -> Option<u64> {
+    match attempt {
+        0 => seed,
+        attempt => seed.map(|x| child_seed(x, attempt as u64)),
+    }
+}
+
+/// Seed of a run: `seed` when given, otherwise one drawn from entropy and used the same way,
+/// along with whether it was drawn. Recording it lets any run be replayed with `--seed`.
+pub fn run_seed(seed: Option<u64>) -> (u64, bool) {
+    match seed {
+        Some(seed) => (seed, false),
+        None => (rand::random(), true),
+    }
+}
+
+/// Derives an independent seed for the `index`-th stream of `seed` (SplitMix64), so every
+/// stochastic run gets its own reproducible random numbers whatever the order or thread it runs
+/// on.
+pub fn child_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9e3779b97f4a7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Maps `f` over `items`, one thread per item when `parallel` is set. Results keep the order of
+/// `items` either way.
+pub fn map_runs<T: Sync, R: Send>(
+    items: &[T],
+    parallel: bool,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    if !parallel {
+        return items.iter().map(f).collect();
+    }
+    thread::scope(|s| {
+        let f = &f;
+        let handles = items
+            .iter()
+            .map(|x| s.spawn(move || f(x)))
+            .collect::<Vec<_>>();
+        handles.into_iter().map(|x| x.join().unwrap()).collect()
+    })
+}
+
+/// What to do when a solver returns an error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Record the error in the row and run the remaining solvers.
+    #[default]
+    KeepGoing,
+    /// Stop at the first error.
+    FailFast,
+}
+
+impl FailurePolicy {
+    /// Collects `results` according to the policy. Failing fast stops pulling from the iterator
+    /// at the first failed run and returns its error, so lazy iterators skip the remaining runs.
+    pub fn collect(
+        self,
+        results: impl IntoIterator<Item = results::Result>,
+    ) -> Result<Vec<results::Result>, String> {
+        let mut collected = Vec::new();
+        for result in results {
+            if let (FailurePolicy::FailFast, Some(error)) = (self, &result.error) {
+                return Err(format!("{} failed: {error}", result.method));
+            }
+            collected.push(result);
+        }
+        Ok(collected)
+    }
+}
+
+/// `error` was raised by a line search wrapped with
+/// [`StepLengths::record`](crate::step_length::StepLengths::record), e.g. a non-finite cost met
+/// by More-Thuente while bracketing the step, or no step found within
+/// [`MAX_LINESEARCH_ITERS`](crate::step_length::MAX_LINESEARCH_ITERS). Failed rows record it
+/// in [`results::Result::linesearch_error`].
+pub fn is_linesearch_error(error: &Error) -> bool {
+    error.downcast_ref::<LineSearchError>().is_some()
+}
+
+/// A solver ready to be run, boxed so runs with different solver and state types can be listed,
+/// filtered and validated before any of them starts.
+pub struct SolverRun<O> {
+    pub method: Method,
+    /// Label of the result rows, the method label unless the run sets its own.
+    pub label: String,
+    #[allow(clippy::type_complexity)]
+    run: Box<dyn Fn(O, &str, Array1<f64>, &RunOptions) -> results::Result + Send + Sync>,
+}
+
+impl<O> SolverRun<O> {
+    /// `configure` gets the initial param of the run along with the state, solvers that don't
+    /// start from a single point (e.g. particle swarm) can ignore it. The solver is cloned on
+    /// every run, so the same `SolverRun` can be run several times.
+    pub fn new<S, I>(
+        method: Method,
+        solver: S,
+        configure: impl Fn(I, Array1<f64>) -> I + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: Solver<O, I> + Clone + Send + Sync + 'static,
+        I: State<Float = f64> + GradientNorm + SerializeAlias + DeserializeOwnedAlias,
+        I::Param: ToParamVec + Debug,
+    {
+        let run = move |problem, label: &str, init, options: &RunOptions| {
+            let configure = |state| configure(state, init);
+            run_solver(
+                method.family(),
+                label,
+                problem,
+                solver.clone(),
+                configure,
+                options,
+            )
+        };
+        Self {
+            method,
+            label: method.to_string(),
+            run: Box::new(run),
+        }
+    }
+
+    /// Replaces the label, e.g. to include the settings of the solver.
+    pub fn with_label(mut self, label: impl ToString) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    /// Post-processes every result of the run, e.g. to bring a solver specific cost back to the
+    /// scale of the other methods.
+    pub fn map_result(
+        self,
+        f: impl Fn(results::Result) -> results::Result + Send + Sync + 'static,
+    ) -> Self
+    where
+        O: 'static,
+    {
+        let run = self.run;
+        Self {
+            run: Box::new(move |problem, label, init, options| {
+                f(run(problem, label, init, options))
+            }),
+            ..self
+        }
+    }
+
+    /// Reruns a row failing in its line search with `fallback` instead, once, on the same
+    /// problem and init, e.g. the same solver with a backtracking line search. The row keeps the
+    /// label of this run and records `name` in its line-search fallback column.
+    pub fn with_linesearch_fallback(self, fallback: SolverRun<O>, name: impl ToString) -> Self
+    where
+        O: Clone + 'static,
+    {
+        let run = self.run;
+        let name = name.to_string();
+        Self {
+            run: Box::new(move |problem, label, init, options| {
+                let result = run(problem.clone(), label, init.clone(), options);
+                match result.linesearch_error {
+                    true => (fallback.run)(problem, label, init, options)
+                        .with_linesearch_fallback(Some(name.clone())),
+                    false => result,
+                }
+            }),
+            ..self
+        }
+    }
+
+    /// Short lowercase name used to select the run from the command line, see [`Method::key`].
+    pub fn key(&self) -> String {
+        self.method.key()
+    }
+
+    /// Derivative the method needs and `problem` doesn't provide, if any.
+    fn missing_derivative(&self, problem: &O) -> Option<&'static str>
+    where
+        O: Derivatives,
+    {
+        if self.method.needs_hessian() && !problem.has_hessian() {
+            Some("hessian")
+        } else if self.method.needs_gradient() && !problem.has_gradient() {
+            Some("gradient")
+        } else if self.method.needs_anneal() && !problem.has_anneal() {
+            Some("anneal move")
+        } else {
+            None
+        }
+    }
+
+    /// Runs the solver, or records the run as skipped when `problem` lacks a derivative the
+    /// method needs.
+    pub fn run(&self, problem: O, init: Array1<f64>, options: &RunOptions) -> results::Result
+    where
+        O: Derivatives,
+    {
+        if let Some(derivative) = self.missing_derivative(&problem) {
+            let reason = format!("no {derivative}");
+            return results::Result::skipped(self.method.family(), &self.label, &reason);
+        }
+        (self.run)(problem, &self.label, init, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use std::cell::{Cell, RefCell};
+
+    use argmin::core::{IterState, LineSearch};
+    use argmin::solver::{
+        gradientdescent::SteepestDescent,
+        linesearch::{condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch},
+        neldermead::NelderMead,
+        newton::{Newton, NewtonCG},
+        quasinewton::LBFGS,
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::{
+        annealing::AnnealingConfig, particle_swarm::SeededParticleSwarm, step_length::StepLengths,
+        Problem, RosenbrockND, TestFnProblem,
+    };
+
+    #[test]
+    fn test_run_repeated() {
+        let mut calls = 0;
+        let res = run_repeated(3, || {
+            calls += 1;
+            let time = Duration::from_millis(calls);
+            results::Result::new("", "x", 1.0, Some(time), 10, None)
+        });
+        assert_eq!(calls, 3);
+        assert_eq!(res.duration, Some(Duration::from_millis(2)));
+        assert_eq!(res.time, "2ms");
+    }
+
+    #[test]
+    fn test_run_best_of() {
+        let run = |seed| {
+            let rastrigin = TestFnProblem::rastrigin();
+            let bounds = (array![-5.12, -5.12], array![5.12, 5.12]);
+            let solver = SeededParticleSwarm::new(bounds, 10).with_seed(seed);
+            SolverRun::new(Method::ParticleSwarm, solver, |state, _| {
+                state.max_iters(20)
+            })
+            .run(
+                rastrigin,
+                array![3.2, -2.7],
+                &RunOptions {
+                    quiet: true,
+                    ..Default::default()
+                },
+            )
+        };
+        let seeds = (0..5).map(|i| child_seed(42, i)).collect::<Vec<_>>();
+        let best = run_best_of(&seeds, |i| run(seeds[i]));
+        assert_eq!(best.best_of, Some(5));
+        for &seed in &seeds {
+            assert!(best.best_cost <= run(seed).best_cost);
+        }
+        // The recorded seed reproduces the row.
+        let winner = run(best.best_of_seed.unwrap());
+        assert_eq!(winner.best_cost, best.best_cost);
+    }
+
+    #[test]
+    fn test_solver_run() {
+        let simplex = vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+        let run = SolverRun::<RosenbrockND>::new(
+            Method::NelderMead,
+            NelderMead::new(simplex),
+            |state, init| state.param(init).max_iters(5),
+        )
+        .with_label("Nelder-Mead (custom simplex)");
+        assert_eq!(run.key(), "nelder-mead");
+        let options = RunOptions {
+            log_every: 100,
+            ..Default::default()
+        };
+        let res = run.run(RosenbrockND::default(), array![10.2, -20.0], &options);
+        assert_eq!(res.method, "Nelder-Mead (custom simplex)");
+        assert_eq!(res.iterations, 5);
+        assert_eq!(res.best_param.map(|x| x.len()), Some(2));
+    }
+
+    #[test]
+    fn test_map_runs_seeded() {
+        let annealing = (0..4).map(|i| {
+            let solver = AnnealingConfig::default()
+                .build(Some(child_seed(42, i)))
+                .unwrap();
+            let run = SolverRun::<RosenbrockND>::new(
+                Method::SimulatedAnnealing,
+                solver,
+                |state, init| state.param(init).max_iters(50),
+            );
+            (i, run)
+        });
+        let swarm = (4..8).map(|i| {
+            let problem = RosenbrockND::default();
+            let bounds = (problem.lower_bound().clone(), problem.upper_bound().clone());
+            let solver = SeededParticleSwarm::new(bounds, 10).with_seed(child_seed(42, i));
+            let run = SolverRun::<RosenbrockND>::new(Method::ParticleSwarm, solver, |state, _| {
+                state.max_iters(20)
+            });
+            (i, run)
+        });
+        let runs = annealing.chain(swarm).collect::<Vec<_>>();
+        let options = RunOptions {
+            log_every: 100,
+            ..Default::default()
+        };
+        let run_all = |parallel| {
+            map_runs(&runs, parallel, |(i, x)| {
+                let problem = RosenbrockND::default().with_seed(child_seed(7, *i));
+                let res = x.run(problem, array![10.2, -20.0], &options);
+                (res.best_cost, res.best_param)
+            })
+        };
+        let sequential = run_all(false);
+        assert_eq!(sequential, run_all(true));
+        // Every run gets its own stream.
+        assert!(sequential.windows(2).all(|x| x[0] != x[1]));
+    }
+
+    #[test]
+    fn test_retries_rebuild_solvers() {
+        let problem = RosenbrockND::default();
+        let bounds = (problem.lower_bound().clone(), problem.upper_bound().clone());
+        let swarm = move |seed: Option<u64>| {
+            let solver = SeededParticleSwarm::new(bounds.clone(), 10).with_seed(seed.unwrap());
+            SolverRun::<RosenbrockND>::new(Method::ParticleSwarm, solver, |state, _| {
+                state.max_iters(5)
+            })
+        };
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let mut params = Vec::new();
+        let first = swarm(Some(42));
+        let result = run_with_fresh_retries(
+            1,
+            |_| false,
+            &first,
+            Some(42),
+            swarm,
+            |x, _| {
+                let result = x.run(problem.clone(), array![-1.2, 1.0], &options);
+                params.push(result.best_param.clone());
+                result
+            },
+        );
+        assert_eq!(result.retries, Some(1));
+        assert_eq!(params.len(), 2);
+        assert_ne!(params[0], params[1]);
+    }
+
+    #[test]
+    fn test_replay_drawn_seed() {
+        let (seed, drawn) = run_seed(None);
+        assert!(drawn);
+        assert_eq!(run_seed(Some(seed)), (seed, false));
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        // Stochastic rows seeded from the seed of the run, as `02-rosenbrock` does.
+        let run = |seed| {
+            let annealing = AnnealingConfig::default()
+                .build(Some(child_seed(seed, 0)))
+                .unwrap();
+            let annealing = SolverRun::new(Method::SimulatedAnnealing, annealing, |state, init| {
+                state.param(init).max_iters(50)
+            });
+            let problem = RosenbrockND::default().with_seed(child_seed(seed, 1));
+            let bounds = (problem.lower_bound().clone(), problem.upper_bound().clone());
+            let swarm = SeededParticleSwarm::new(bounds, 10).with_seed(child_seed(seed, 2));
+            let swarm =
+                SolverRun::new(Method::ParticleSwarm, swarm, |state, _| state.max_iters(20));
+            [annealing, swarm]
+                .iter()
+                .map(|x| x.run(problem.clone(), array![-1.2, 1.0], &options))
+                .collect::<Vec<_>>()
+        };
+        let params = results::RunParams {
+            seed: Some(seed),
+            seed_drawn: drawn,
+            ..Default::default()
+        };
+        let rows = run(seed);
+        let json = results::Format::Json.render(&results::Report::new("", params, rows.clone()));
+        // Floats don't survive the JSON exactly, only the seed is read back.
+        let recorded: results::Report<results::Result> = serde_json::from_str(&json).unwrap();
+        let replayed = run(recorded.params.seed.unwrap());
+        for (recorded, replayed) in rows.iter().zip(&replayed) {
+            assert_eq!(
+                recorded.best_cost, replayed.best_cost,
+                "{}",
+                recorded.method
+            );
+            assert_eq!(
+                recorded.best_param, replayed.best_param,
+                "{}",
+                recorded.method
+            );
+        }
+    }
+
+    #[test]
+    fn test_seed_sweep_deterministic() {
+        let run = SolverRun::<RosenbrockND>::new(
+            Method::Lbfgs,
+            LBFGS::new(MoreThuenteLineSearch::new(), 7),
+            |state, init| state.param(init).max_iters(10),
+        );
+        let options = RunOptions {
+            log_every: 100,
+            ..Default::default()
+        };
+        let results = (0..5)
+            .map(|seed| {
+                let problem = RosenbrockND::default().with_seed(child_seed(seed, 0));
+                run.run(problem, array![-1.2, 1.0], &options)
+            })
+            .collect::<Vec<_>>();
+        let row = results::SweepRow::new(&results);
+        assert_eq!((row.runs, row.failed), (5, 0));
+        assert_eq!(row.std, Some(0.0));
+        assert_eq!(row.mean, row.min);
+        assert_eq!(row.median, row.max);
+    }
+
+    #[test]
+    fn test_failure_policy() {
+        let nelder_mead = || {
+            let simplex = vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+            SolverRun::<RosenbrockND>::new(
+                Method::NelderMead,
+                NelderMead::new(simplex),
+                |state, _| state.max_iters(5),
+            )
+        };
+        // Steepest descent without an initial param fails.
+        let failing = SolverRun::new(
+            Method::MoreThuente,
+            SteepestDescent::new(MoreThuenteLineSearch::new()),
+            |state, _| state.max_iters(5),
+        );
+        let runs = [nelder_mead(), failing, nelder_mead()];
+        let options = RunOptions {
+            log_every: 100,
+            ..Default::default()
+        };
+        let calls = Cell::new(0);
+        let results = || {
+            calls.set(0);
+            runs.iter().map(|x| {
+                calls.set(calls.get() + 1);
+                x.run(RosenbrockND::default(), array![10.2, -20.0], &options)
+            })
+        };
+
+        let res = FailurePolicy::KeepGoing.collect(results()).unwrap();
+        assert_eq!(calls.get(), 3);
+        assert_eq!(
+            res.iter().map(|x| x.error.is_some()).collect::<Vec<_>>(),
+            [false, true, false]
+        );
+
+        let err = FailurePolicy::FailFast.collect(results()).unwrap_err();
+        assert_eq!(calls.get(), 2);
+        assert!(err.starts_with("More-Thuente failed:"), "{err}");
+    }
+
+    #[test]
+    fn test_csv_history() {
+        let path = std::env::temp_dir().join("argmin-exploring-test-csv-history.csv");
+        let csv = CsvHistoryObserver::create(&path).unwrap();
+        let lbfgs = SolverRun::<RosenbrockND>::new(
+            Method::Lbfgs,
+            LBFGS::new(MoreThuenteLineSearch::new(), 7),
+            |state, init| state.param(init).max_iters(5),
+        );
+        let simplex = vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+        let nelder_mead = SolverRun::<RosenbrockND>::new(
+            Method::NelderMead,
+            NelderMead::new(simplex),
+            |state, _| state.max_iters(5),
+        )
+        .with_label("Nelder-Mead (custom, simplex)");
+        let options = RunOptions {
+            log_every: 100,
+            csv_history: Some(csv.clone()),
+            ..Default::default()
+        };
+        let runs = [lbfgs, nelder_mead];
+        map_runs(&runs, true, |x| {
+            x.run(RosenbrockND::default(), array![10.2, -20.0], &options)
+        });
+        // Warmup runs write nothing.
+        let res = runs[0].run(
+            RosenbrockND::default(),
+            array![10.2, -20.0],
+            &options.for_warmup(),
+        );
+        assert_eq!(res.iterations, 5);
+        csv.flush().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some(CsvHistoryObserver::HEADER));
+        let rows = lines.collect::<Vec<_>>();
+        assert_eq!(rows.len(), 10);
+        let lbfgs = rows
+            .iter()
+            .filter(|x| x.starts_with("L-BFGS,"))
+            .collect::<Vec<_>>();
+        let nelder_mead = rows
+            .iter()
+            .filter(|x| x.starts_with("\"Nelder-Mead (custom, simplex)\","))
+            .collect::<Vec<_>>();
+        assert_eq!(lbfgs.len(), 5);
+        assert_eq!(nelder_mead.len(), 5);
+        // Only gradient based methods have a gradient norm.
+        assert!(lbfgs.iter().all(|x| !x.ends_with(',')));
+        assert!(nelder_mead.iter().all(|x| x.ends_with(',')));
+        assert!(lbfgs[0].starts_with("L-BFGS,1,"));
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let nelder_mead = |method| {
+            let simplex = vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+            SolverRun::<RosenbrockND>::new(
+                Method::NelderMead,
+                NelderMead::new(simplex),
+                |state, _| state.max_iters(5),
+            )
+            .with_label(method)
+        };
+        let runs = [
+            nelder_mead("First"),
+            nelder_mead("Second"),
+            nelder_mead("Third"),
+        ];
+        let start = Instant::now();
+        let budget = TimeBudget::starting_at(start, Duration::from_millis(200));
+        assert!(!budget.exhausted_at(start + Duration::from_millis(150)));
+        assert!(budget.exhausted_at(start + Duration::from_millis(250)));
+
+        // The first run has the whole budget, the others start once it is spent.
+        let spent = TimeBudget::starting_at(start - Duration::from_secs(1), budget.budget);
+        let results = runs
+            .iter()
+            .zip([budget, spent, spent])
+            .map(|(x, budget)| {
+                let options = RunOptions {
+                    log_every: 100,
+                    time_budget: Some(budget),
+                    ..Default::default()
+                };
+                x.run(RosenbrockND::default(), array![10.2, -20.0], &options)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results.iter().map(|x| x.skipped).collect::<Vec<_>>(),
+            [false, true, true]
+        );
+        assert_eq!(results[0].iterations, 5);
+        assert_eq!(
+            results[1].termination_reason,
+            "Skipped: time budget exhausted"
+        );
+    }
+
+    #[test]
+    fn test_missing_derivatives() {
+        let problem = "perm".parse::<Problem>().unwrap();
+        assert!(problem.has_gradient() && !problem.has_hessian() && !problem.has_anneal());
+        let annealing = AnnealingConfig::default().build(Some(0)).unwrap();
+        let runs = [
+            SolverRun::<Problem>::new(Method::Newton, Newton::new(), |state, init| {
+                state.param(init).max_iters(5)
+            }),
+            SolverRun::new(
+                Method::NewtonCg,
+                NewtonCG::new(MoreThuenteLineSearch::new()),
+                |state, init| state.param(init).max_iters(5),
+            ),
+            SolverRun::new(
+                Method::Lbfgs,
+                LBFGS::new(MoreThuenteLineSearch::new(), 5),
+                |state, init| state.param(init).max_iters(5),
+            ),
+            SolverRun::new(Method::SimulatedAnnealing, annealing, |state, init| {
+                state.param(init).max_iters(5)
+            }),
+        ];
+        let options = RunOptions {
+            log_every: 100,
+            ..Default::default()
+        };
+        let results = runs
+            .iter()
+            .map(|x| x.run(problem.clone(), array![1.0, 1.0], &options))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results.iter().map(|x| x.skipped).collect::<Vec<_>>(),
+            [true, true, false, true]
+        );
+        assert_eq!(results[0].termination_reason, "Skipped: no hessian");
+        assert_eq!(results[3].termination_reason, "Skipped: no anneal move");
+        assert_eq!(results[1].method, "Newton-CG");
+        assert_eq!(results[2].iterations, 5);
+    }
+
+    #[test]
+    fn test_linesearch_fallback() {
+        // Steep enough that the first step of More-Thuente overflows the cost.
+        let steep = TestFnProblem::new("steep", |x| x.iter().map(|x| (x * x).exp()).sum())
+            .with_gradient(|x| x.iter().map(|x| 2.0 * x * (x * x).exp()).collect());
+        fn steepest_descent<L>(linesearch: L) -> SolverRun<TestFnProblem>
+        where
+            L: LineSearch<Array1<f64>, f64>
+                + Solver<TestFnProblem, IterState<Array1<f64>, Array1<f64>, (), (), f64>>
+                + Clone
+                + Send
+                + Sync
+                + 'static,
+        {
+            let solver = SteepestDescent::new(linesearch);
+            SolverRun::new(Method::MoreThuente, solver, |state, init| {
+                state.param(init).max_iters(100)
+            })
+        }
+        let steps = StepLengths::new();
+        let morethuente = || steepest_descent(steps.record(MoreThuenteLineSearch::new()));
+        let backtracking = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let init = array![3.0, -2.5];
+        let failed = morethuente().run(steep, init.clone(), &options);
+        assert!(failed.linesearch_error, "{:?}", failed.error);
+        // Only the line searches wrapped to record their steps are told apart, whatever the
+        // message says.
+        let unwrapped =
+            steepest_descent(MoreThuenteLineSearch::new()).run(steep, init.clone(), &options);
+        assert_eq!(unwrapped.error, failed.error);
+        assert!(!unwrapped.linesearch_error);
+
+        let res = morethuente()
+            .with_linesearch_fallback(steepest_descent(backtracking), "Backtracking")
+            .run(steep, init, &options);
+        assert_eq!(res.error, None);
+        assert_eq!(res.method, "More-Thuente");
+        assert_eq!(res.linesearch_fallback.as_deref(), Some("Backtracking"));
+        assert!(res.best_cost < 2.0 + 1e-6, "{}", res.best_cost);
+    }
+
+    #[test]
+    fn test_run_with_retries() {
+        let seeds = RefCell::new(Vec::new());
+        let run = |attempt| {
+            let seed = attempt_seed(Some(42), attempt).unwrap();
+            seeds.borrow_mut().push(seed);
+            results::Result::new("", "Random", 1.0 + (seed % 100) as f64, None, 1, None)
+        };
+
+        let res = run_with_retries(3, |x| x.best_cost < 1.0, run);
+        let seeds = seeds.take();
+        assert_eq!(seeds.len(), 4);
+        assert_eq!(seeds[0], 42);
+        assert!(seeds
+            .iter()
+            .enumerate()
+            .all(|(i, x)| !seeds[..i].contains(x)));
+        let best = seeds
+            .iter()
+            .map(|x| 1.0 + (x % 100) as f64)
+            .fold(f64::MAX, f64::min);
+        assert_eq!(res.best_cost, best);
+        assert_eq!(res.retries, Some(3));
+
+        let mut calls = 0;
+        let res = run_with_retries(
+            3,
+            |x| x.best_cost < 1.0,
+            |attempt| {
+                calls += 1;
+                let cost = if attempt == 1 { 0.5 } else { 2.0 };
+                results::Result::new("", "Random", cost, None, 1, None)
+            },
+        );
+        assert_eq!((calls, res.best_cost, res.retries), (2, 0.5, Some(1)));
+    }
+}
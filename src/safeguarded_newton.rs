@@ -0,0 +1,200 @@
+use argmin::{
+    core::{
+        ArgminError, CostFunction, Error, Gradient, Hessian, IterState, Problem, Solver, State,
+        TerminationReason, TerminationStatus, KV,
+    },
+    kv,
+};
+use ndarray::{Array1, Array2};
+
+/// Sufficient decrease constant of the backtracking line search.
+const ARMIJO: f64 = 1e-4;
+
+/// Trial steps of the line search, halving the step every time, before giving up.
+const MAX_TRIALS: usize = 50;
+
+type NewtonState = IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, f64>;
+
+/// Newton's method falling back to steepest descent wherever the hessian isn't positive
+/// definite, where the Newton step may point uphill or be undefined.
+///
+/// Every iteration tries a Cholesky factorization of the hessian. When it succeeds the step is
+/// the Newton step, otherwise the gradient scaled by the inverse of the hessian norm. Both are
+/// then shortened by a backtracking line search until the cost decreases enough, so unlike
+/// argmin's `Newton` the cost is evaluated and every step goes downhill.
+///
+/// The iteration log has a `fallback` entry telling whether the step was a fallback one.
+#[derive(Debug, Clone)]
+pub struct SafeguardedNewton {
+    tol: f64,
+    converged: bool,
+}
+
+impl SafeguardedNewton {
+    pub fn new() -> Self {
+        Self {
+            tol: f64::EPSILON.sqrt(),
+            converged: false,
+        }
+    }
+}
+
+impl Default for SafeguardedNewton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lower triangular $L$ with $L L^T = a$, `None` unless `a` is (numerically) positive definite.
+fn cholesky(a: &Array2<f64>) -> Option<Array2<f64>> {
+    let n = a.nrows();
+    let mut l = Array2::zeros((n, n));
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = (0..j).map(|k| l[[i, k]] * l[[j, k]]).sum::<f64>();
+            if i == j {
+                let diagonal = a[[i, i]] - sum;
+                if !(diagonal > 0.0 && diagonal.is_finite()) {
+                    return None;
+                }
+                l[[i, i]] = diagonal.sqrt();
+            } else {
+                l[[i, j]] = (a[[i, j]] - sum) / l[[j, j]];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solves $L L^T x = b$ by forward then back substitution.
+fn cholesky_solve(l: &Array2<f64>, b: &Array1<f64>) -> Array1<f64> {
+    let n = b.len();
+    let mut y = Array1::zeros(n);
+    for i in 0..n {
+        let sum = (0..i).map(|k| l[[i, k]] * y[k]).sum::<f64>();
+        y[i] = (b[i] - sum) / l[[i, i]];
+    }
+    let mut x = Array1::zeros(n);
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).map(|k| l[[k, i]] * x[k]).sum::<f64>();
+        x[i] = (y[i] - sum) / l[[i, i]];
+    }
+    x
+}
+
+impl<O> Solver<O, NewtonState> for SafeguardedNewton
+where
+    O: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>
+        + Hessian<Param = Array1<f64>, Hessian = Array2<f64>>,
+{
+    const NAME: &'static str = "Safeguarded Newton";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: NewtonState,
+    ) -> Result<(NewtonState, Option<KV>), Error> {
+        let param = state
+            .get_param()
+            .ok_or_else(|| ArgminError::NotInitialized {
+                text: "`SafeguardedNewton` requires an initial parameter vector".to_string(),
+            })?;
+        let cost = problem.cost(param)?;
+        Ok((state.cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: NewtonState,
+    ) -> Result<(NewtonState, Option<KV>), Error> {
+        let param = state.get_param().unwrap().clone();
+        let cost = state.get_cost();
+        let gradient = problem.gradient(&param)?;
+        if gradient.dot(&gradient).sqrt() < self.tol {
+            self.converged = true;
+            return Ok((state.param(param).cost(cost).gradient(gradient), None));
+        }
+        let hessian = problem.hessian(&param)?;
+        let (direction, fallback) = match cholesky(&hessian) {
+            Some(l) => (-cholesky_solve(&l, &gradient), false),
+            None => {
+                let norm = hessian.iter().map(|x| x.powi(2)).sum::<f64>().sqrt();
+                (-&gradient / norm.max(1.0), true)
+            }
+        };
+        let slope = gradient.dot(&direction);
+        let mut step = 1.0;
+        for _ in 0..MAX_TRIALS {
+            let trial = &param + &(step * &direction);
+            let trial_cost = problem.cost(&trial)?;
+            if trial_cost <= cost + ARMIJO * step * slope {
+                let kv = kv!("fallback" => fallback; "step" => step;);
+                let state = state.param(trial).cost(trial_cost).gradient(gradient);
+                return Ok((state.hessian(hessian), Some(kv)));
+            }
+            step /= 2.0;
+        }
+        // No step lowers the cost any more, as happens within rounding of the minimum.
+        self.converged = true;
+        let kv = kv!("fallback" => fallback; "step" => 0.0;);
+        Ok((state.param(param).cost(cost).gradient(gradient), Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &NewtonState) -> TerminationStatus {
+        match self.converged {
+            true => TerminationStatus::Terminated(TerminationReason::SolverConverged),
+            false => TerminationStatus::NotTerminated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::Executor;
+    use ndarray::array;
+
+    use super::*;
+    use crate::{observers::FallbackObserver, RosenbrockND};
+
+    #[test]
+    fn test_indefinite_start() {
+        // Indefinite hessian wherever y > x^2 + 1/200.
+        let init = array![0.0, 1.0];
+        let problem = RosenbrockND::default();
+        assert!(cholesky(&problem.hessian(&init).unwrap()).is_none());
+
+        let fallbacks = FallbackObserver::new();
+        let res = Executor::new(problem, SafeguardedNewton::new())
+            .configure(|state| state.param(init).max_iters(100))
+            .add_observer(
+                fallbacks.clone(),
+                argmin::core::observers::ObserverMode::Always,
+            )
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert!(
+            (param - &array![1.0, 1.0]).iter().all(|x| x.abs() < 1e-6),
+            "{param}"
+        );
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert!(fallbacks.count().unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = array![[4.0, 2.0], [2.0, 3.0]];
+        let l = cholesky(&a).unwrap();
+        assert!((l.dot(&l.t()) - &a).iter().all(|x| x.abs() < 1e-12));
+        let x = cholesky_solve(&l, &array![2.0, 1.0]);
+        assert!((a.dot(&x) - array![2.0, 1.0])
+            .iter()
+            .all(|x| x.abs() < 1e-12));
+        assert!(cholesky(&array![[1.0, 2.0], [2.0, 1.0]]).is_none());
+    }
+}
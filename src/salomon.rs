@@ -0,0 +1,176 @@
+use std::{
+    f64::consts::PI,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use argmin::{
+    core::{CostFunction, Gradient},
+    solver::simulatedannealing::Anneal,
+};
+use ndarray::Array1;
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+use crate::annealing::{bounded_anneal, DEFAULT_ANNEAL_STEP};
+
+/// The Salomon function $ f(x) = 1 - \cos(2 \pi \|x\|) + 0.1 \|x\| $.
+///
+/// It is evaluated on $[-100, 100]^d$ with its global minimum $f = 0$ at the origin. Only the
+/// distance to the origin matters: the cosine puts a ring of local minima at about every integer
+/// radius, each only $0.1$ higher than the one inside it, so a local method stops on the ring
+/// it starts next to.
+#[derive(Clone)]
+pub struct Salomon {
+    lower_bound: Array1<f64>,
+    upper_bound: Array1<f64>,
+    /// Largest `anneal` move as a fraction of the bound width, see
+    /// [`DEFAULT_ANNEAL_STEP`].
+    anneal_step: f64,
+    /// Random number generator behind a `Arc<Mutex<_>>`, as `anneal` only gets `&self`.
+    rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+}
+
+/// Leaves out the state of the random number generator, so equal problems print the same.
+impl fmt::Debug for Salomon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Salomon")
+            .field("lower_bound", &self.lower_bound)
+            .field("upper_bound", &self.upper_bound)
+            .field("anneal_step", &self.anneal_step)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Salomon {
+    pub fn new(dim: usize) -> Self {
+        Self {
+            lower_bound: Array1::from_elem(dim, -100.0),
+            upper_bound: Array1::from_elem(dim, 100.0),
+            anneal_step: DEFAULT_ANNEAL_STEP,
+            rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::from_entropy())),
+        }
+    }
+
+    /// Reseeds the random number generator used by `anneal`, making runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(Xoshiro256PlusPlus::seed_from_u64(seed)));
+        self
+    }
+
+    /// Scales the `anneal` moves along each axis to `step` times the width of its bounds.
+    pub fn with_anneal_step(mut self, step: f64) -> Self {
+        self.anneal_step = step;
+        self
+    }
+
+    pub fn dim(&self) -> usize {
+        self.lower_bound.len()
+    }
+
+    pub fn lower_bound(&self) -> &Array1<f64> {
+        &self.lower_bound
+    }
+
+    pub fn upper_bound(&self) -> &Array1<f64> {
+        &self.upper_bound
+    }
+
+    /// The global minimizer, the origin.
+    pub fn optimum(&self) -> Array1<f64> {
+        Array1::zeros(self.dim())
+    }
+}
+
+impl Default for Salomon {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl CostFunction for Salomon {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let r = param.dot(param).sqrt();
+        Ok(1.0 - (2.0 * PI * r).cos() + 0.1 * r)
+    }
+}
+
+impl Gradient for Salomon {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    /// Radial derivative along $x / \|x\|$, taken as zero at the origin where the cone
+    /// $0.1 \|x\|$ has none.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        let r = param.dot(param).sqrt();
+        if r == 0.0 {
+            return Ok(Array1::zeros(param.len()));
+        }
+        let radial = 2.0 * PI * (2.0 * PI * r).sin() + 0.1;
+        Ok(param * (radial / r))
+    }
+}
+
+impl Anneal for Salomon {
+    type Param = Array1<f64>;
+    type Output = Array1<f64>;
+    type Float = f64;
+
+    fn anneal(
+        &self,
+        param: &Self::Param,
+        temp: Self::Float,
+    ) -> Result<Self::Output, argmin::core::Error> {
+        let mut rng = self.rng.lock().unwrap();
+        Ok(bounded_anneal(
+            param,
+            temp,
+            &mut *rng,
+            (&self.lower_bound, &self.upper_bound),
+            self.anneal_step,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_optimum() {
+        for dim in 1..=5 {
+            let f = Salomon::new(dim);
+            let origin = f.optimum();
+            assert!(f.cost(&origin).unwrap().abs() < 1e-12);
+            assert!(f.gradient(&origin).unwrap().iter().all(|&g| g == 0.0));
+        }
+        // The ring at radius about 1 is a local minimum, higher than the origin.
+        let f = Salomon::new(3);
+        let ring = f.cost(&array![0.6, 0.0, 0.8]).unwrap();
+        assert!(ring > 0.09 && ring < 0.11, "{ring}");
+        assert!(f.cost(&array![0.5, 0.0, 0.0]).unwrap() > ring);
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = Salomon::new(3);
+        let param = array![0.3, -1.4, 2.6];
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-7;
+        for k in 0..param.len() {
+            let (mut forward, mut backward) = (param.clone(), param.clone());
+            forward[k] += h;
+            backward[k] -= h;
+            let diff = (f.cost(&forward).unwrap() - f.cost(&backward).unwrap()) / (2.0 * h);
+            assert!(
+                (diff - gradient[k]).abs() < 1e-4 * gradient[k].abs().max(1.0),
+                "{k}: {diff} vs {}",
+                gradient[k]
+            );
+        }
+    }
+}
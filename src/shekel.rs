@@ -0,0 +1,153 @@
+use argmin::core::{ArgminError, CostFunction, Error, Gradient};
+use ndarray::Array1;
+
+/// Centers of the minima, the first `m` rows being used.
+const A: [[f64; 4]; 10] = [
+    [4.0, 4.0, 4.0, 4.0],
+    [1.0, 1.0, 1.0, 1.0],
+    [8.0, 8.0, 8.0, 8.0],
+    [6.0, 6.0, 6.0, 6.0],
+    [3.0, 7.0, 3.0, 7.0],
+    [2.0, 9.0, 2.0, 9.0],
+    [5.0, 5.0, 3.0, 3.0],
+    [8.0, 1.0, 8.0, 1.0],
+    [6.0, 2.0, 6.0, 2.0],
+    [7.0, 3.6, 7.0, 3.6],
+];
+
+/// Widths of the minima, the smaller the deeper.
+const C: [f64; 10] = [0.1, 0.2, 0.2, 0.4, 0.4, 0.6, 0.3, 0.7, 0.5, 0.5];
+
+/// The Shekel functions in 4 dimensions, with `m` local minima:
+/// $ f(x) = -\sum_{i=1}^{m} \left( \sum_{j=1}^{4} (x_j - A_{ij})^2 + c_i \right)^{-1} $
+///
+/// Evaluated on $[0, 10]^4$, the global minimum is a sharp well near $(4, 4, 4, 4)$, only known
+/// numerically: $-10.1532$ for `m = 5`, $-10.4029$ for `m = 7` and $-10.5364$ for `m = 10`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shekel {
+    m: usize,
+}
+
+impl Shekel {
+    /// `None` unless `m` is 5, 7 or 10.
+    pub fn new(m: usize) -> Option<Self> {
+        matches!(m, 5 | 7 | 10).then_some(Self { m })
+    }
+
+    pub fn m(&self) -> usize {
+        self.m
+    }
+
+    pub fn dim(&self) -> usize {
+        4
+    }
+
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (Array1::zeros(4), Array1::from_elem(4, 10.0))
+    }
+
+    /// $ \sum_j (x_j - A_{ij})^2 + c_i $ for every minimum $i$.
+    fn denominators(&self, param: &Array1<f64>) -> Result<Vec<f64>, Error> {
+        if param.len() != 4 {
+            return Err(ArgminError::InvalidParameter {
+                text: format!(
+                    "Shekel is only defined in 4 dimensions, got {}",
+                    param.len()
+                ),
+            }
+            .into());
+        }
+        let denominators = A[..self.m]
+            .iter()
+            .zip(&C)
+            .map(|(a, c)| {
+                param
+                    .iter()
+                    .zip(a)
+                    .map(|(x, a)| (x - a).powi(2))
+                    .sum::<f64>()
+                    + c
+            })
+            .collect();
+        Ok(denominators)
+    }
+}
+
+impl Default for Shekel {
+    fn default() -> Self {
+        Self { m: 10 }
+    }
+}
+
+impl CostFunction for Shekel {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(-self
+            .denominators(param)?
+            .iter()
+            .map(|d| d.recip())
+            .sum::<f64>())
+    }
+}
+
+impl Gradient for Shekel {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let denominators = self.denominators(param)?;
+        let gradient = param
+            .iter()
+            .enumerate()
+            .map(|(j, x)| {
+                denominators
+                    .iter()
+                    .zip(&A)
+                    .map(|(d, a)| 2.0 * (x - a[j]) / d.powi(2))
+                    .sum()
+            })
+            .collect();
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_minima() {
+        for (m, minimum) in [(5, -10.1532), (7, -10.4029), (10, -10.5364)] {
+            let f = Shekel::new(m).unwrap();
+            let x = Array1::from_elem(4, 4.0);
+            assert!((f.cost(&x).unwrap() - minimum).abs() < 1e-3, "{m}");
+            // Deepest well: the other centers are all higher.
+            for a in &A[1..m] {
+                assert!(
+                    f.cost(&Array1::from(a.to_vec())).unwrap() > minimum + 1.0,
+                    "{m}"
+                );
+            }
+        }
+        assert!(Shekel::new(6).is_none());
+        assert!(Shekel::default().cost(&array![4.0, 4.0]).is_err());
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = Shekel::new(7).unwrap();
+        let param = array![3.5, 4.2, 5.0, 2.8];
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-7;
+        for k in 0..param.len() {
+            let mut step = param.clone();
+            step[k] += h;
+            let diff = (f.cost(&step).unwrap() - f.cost(&param).unwrap()) / h;
+            assert!((diff - gradient[k]).abs() < 1e-5, "{k}");
+        }
+    }
+}
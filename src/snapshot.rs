@@ -0,0 +1,63 @@
+/// Columns of the `--snapshot` table: everything that doesn't change from one run to the next,
+/// leaving out the times and what only stochastic methods fill.
+pub const SNAPSHOT_COLUMNS: &str =
+    "family,method,best_cost,iters,evaluations,termination_reason,status,step_length,best_param";
+
+/// Line diff of `actual` against the golden `expected`, `None` when they match.
+///
+/// Every line is kept, prefixed by `- ` when only in `expected`, `+ ` when only in `actual` and
+/// two spaces otherwise, pairing the lines of a longest common subsequence.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    // `common[i][j]`: length of the longest common subsequence of `expected[i..]` and
+    // `actual[j..]`.
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = match expected[i] == actual[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push(format!("  {}", expected[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            lines.push(format!("- {}", expected[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", actual[j]));
+            j += 1;
+        }
+    }
+    // Only the trailing newline differs.
+    if lines.iter().all(|x| x.starts_with("  ")) {
+        lines.push("(trailing newline)".to_string());
+    }
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\nd\n").unwrap(),
+            "  a\n- b\n+ x\n  c\n+ d"
+        );
+        assert_eq!(diff("a\nb", "b").unwrap(), "- a\n  b");
+        assert!(diff("a\n", "a").is_some());
+    }
+}
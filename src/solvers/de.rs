@@ -0,0 +1,260 @@
+use argmin::core::{ArgminError, CostFunction, Error, IterState, Problem, Solver, KV};
+use ndarray::Array1;
+use rand::Rng;
+use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256PlusPlus};
+
+/// Differential evolution (DE/rand/1/bin), which argmin doesn't provide.
+///
+/// Every iteration each member of the population is challenged by a trial point: the
+/// difference of two random members, scaled by the mutation factor `F`, is added to a third one,
+/// then every coordinate is taken from that mutant with probability `CR` (at least one is). The
+/// trial replaces the member when its cost is at least as low. Mutants are clamped to the bounds.
+///
+/// The population is drawn uniformly within the bounds, plus the initial param when the state
+/// has one. The state holds the best member, the population itself is kept by the solver.
+#[derive(Debug, Clone)]
+pub struct DifferentialEvolution {
+    bounds: (Array1<f64>, Array1<f64>),
+    population_size: usize,
+    mutation: f64,
+    crossover: f64,
+    rng: Xoshiro256PlusPlus,
+    population: Vec<(Array1<f64>, f64)>,
+}
+
+impl DifferentialEvolution {
+    /// With the usual `F = 0.8` and `CR = 0.9`.
+    pub fn new(bounds: (Array1<f64>, Array1<f64>), population_size: usize) -> Self {
+        Self {
+            bounds,
+            population_size,
+            mutation: 0.8,
+            crossover: 0.9,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+            population: Vec::new(),
+        }
+    }
+
+    /// Scale `F` of the differences added to the mutants, in `(0, 2]`.
+    pub fn with_mutation(mut self, mutation: f64) -> Result<Self, Error> {
+        if !(mutation > 0.0 && mutation <= 2.0) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`mutation` must be in (0, 2], got {mutation}"),
+            }
+            .into());
+        }
+        self.mutation = mutation;
+        Ok(self)
+    }
+
+    /// Probability `CR` of taking each coordinate from the mutant, in `[0, 1]`.
+    pub fn with_crossover(mut self, crossover: f64) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&crossover) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`crossover` must be in [0, 1], got {crossover}"),
+            }
+            .into());
+        }
+        self.crossover = crossover;
+        Ok(self)
+    }
+
+    /// Reseeds the population and the trial points, making runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        self
+    }
+
+    pub fn population_size(&self) -> usize {
+        self.population_size
+    }
+
+    pub fn mutation(&self) -> f64 {
+        self.mutation
+    }
+
+    pub fn crossover(&self) -> f64 {
+        self.crossover
+    }
+
+    /// Three distinct members, all different from `i`.
+    fn pick(&mut self, i: usize) -> [usize; 3] {
+        let mut picked = [i; 3];
+        for k in 0..3 {
+            picked[k] = loop {
+                let j = self.rng.gen_range(0..self.population.len());
+                if j != i && !picked[..k].contains(&j) {
+                    break j;
+                }
+            };
+        }
+        picked
+    }
+
+    fn best(&self) -> (Array1<f64>, f64) {
+        self.population
+            .iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .cloned()
+            .unwrap()
+    }
+}
+
+type DeState = IterState<Array1<f64>, (), (), (), f64>;
+
+impl<O> Solver<O, DeState> for DifferentialEvolution
+where
+    O: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    const NAME: &'static str = "Differential Evolution";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: DeState,
+    ) -> Result<(DeState, Option<KV>), Error> {
+        // Mutants need three members besides the one they challenge.
+        if self.population_size < 4 {
+            return Err(ArgminError::InvalidParameter {
+                text: format!(
+                    "`DifferentialEvolution` requires at least 4 members, got {}",
+                    self.population_size
+                ),
+            }
+            .into());
+        }
+        let (lower, upper) = self.bounds.clone();
+        let mut members = state.take_param().into_iter().collect::<Vec<_>>();
+        while members.len() < self.population_size {
+            let member =
+                Array1::from_shape_fn(lower.len(), |j| self.rng.gen_range(lower[j]..=upper[j]));
+            members.push(member);
+        }
+        self.population = members
+            .into_iter()
+            .map(|x| Ok((x.clone(), problem.cost(&x)?)))
+            .collect::<Result<_, Error>>()?;
+        let (param, cost) = self.best();
+        Ok((state.param(param).cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: DeState,
+    ) -> Result<(DeState, Option<KV>), Error> {
+        let (lower, upper) = self.bounds.clone();
+        let dim = lower.len();
+        for i in 0..self.population.len() {
+            let [a, b, c] = self.pick(i);
+            let mut mutant = &self.population[a].0
+                + self.mutation * (&self.population[b].0 - &self.population[c].0);
+            mutant.zip_mut_with(&lower, |x, l| *x = x.max(*l));
+            mutant.zip_mut_with(&upper, |x, u| *x = x.min(*u));
+            let forced = self.rng.gen_range(0..dim);
+            let mut trial = self.population[i].0.clone();
+            for j in 0..dim {
+                if j == forced || self.rng.gen::<f64>() < self.crossover {
+                    trial[j] = mutant[j];
+                }
+            }
+            let cost = problem.cost(&trial)?;
+            if cost <= self.population[i].1 {
+                self.population[i] = (trial, cost);
+            }
+        }
+        let (param, cost) = self.best();
+        Ok((state.param(param).cost(cost), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use argmin::solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch};
+    use ndarray::array;
+
+    use super::*;
+    use crate::{
+        run_solver,
+        runner::{run_with_fresh_retries, SolverRun},
+        testfn::TestFnProblem,
+        Method, RosenbrockND, RunOptions,
+    };
+
+    #[test]
+    fn test_beats_steepest_descent_on_rastrigin() {
+        let rastrigin = TestFnProblem::rastrigin().with_gradient(|x| {
+            x.iter()
+                .map(|x| 2.0 * x + 20.0 * PI * (2.0 * PI * x).sin())
+                .collect()
+        });
+        let init = array![3.2, -2.7];
+        let bounds = (array![-5.12, -5.12], array![5.12, 5.12]);
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        // The same 2000 evaluations each.
+        let population = 20;
+        let de = run_solver(
+            "",
+            "Differential Evolution",
+            rastrigin,
+            DifferentialEvolution::new(bounds, population).with_seed(42),
+            |state| state.param(init.clone()).max_iters(100),
+            &options,
+        );
+        let iterations = Method::MoreThuente.iterations_for_budget(2_000, population);
+        let sd = run_solver(
+            "",
+            "More-Thuente",
+            rastrigin,
+            SteepestDescent::new(MoreThuenteLineSearch::new()),
+            |state| state.param(init.clone()).max_iters(iterations),
+            &options,
+        );
+        // Steepest descent stays in the local minimum next to the init.
+        assert!(sd.best_cost > 1.0, "{}", sd.best_cost);
+        assert!(de.best_cost < 1e-3, "{}", de.best_cost);
+
+        let solver = DifferentialEvolution::new((array![0.0], array![1.0]), 3);
+        let res = run_solver("", "", rastrigin, solver, |state| state, &options);
+        assert!(res.error.is_some());
+        assert!(DifferentialEvolution::new((array![0.0], array![1.0]), 4)
+            .with_crossover(1.5)
+            .is_err());
+    }
+
+    #[test]
+    fn test_retries_draw_new_populations() {
+        let problem = RosenbrockND::default();
+        let bounds = (problem.lower_bound().clone(), problem.upper_bound().clone());
+        let de = move |seed: Option<u64>| {
+            let solver = DifferentialEvolution::new(bounds.clone(), 10).with_seed(seed.unwrap());
+            SolverRun::<RosenbrockND>::new(Method::DifferentialEvolution, solver, |state, init| {
+                state.param(init).max_iters(5)
+            })
+        };
+        let options = RunOptions {
+            quiet: true,
+            ..Default::default()
+        };
+        let mut params = Vec::new();
+        let result = run_with_fresh_retries(
+            1,
+            |_| false,
+            &de(Some(42)),
+            Some(42),
+            de,
+            |x, _| {
+                let result = x.run(problem.clone(), array![-1.2, 1.0], &options);
+                params.push(result.best_param.clone());
+                result
+            },
+        );
+        assert_eq!(result.retries, Some(1));
+        assert_ne!(params[0], params[1]);
+    }
+}
@@ -0,0 +1,169 @@
+use argmin::{
+    core::{
+        ArgminError, CostFunction, Error, Gradient, IterState, Problem, Solver, State,
+        TerminationReason, TerminationStatus, KV,
+    },
+    kv,
+};
+use ndarray::Array1;
+
+type MomentumState = IterState<Array1<f64>, Array1<f64>, (), (), f64>;
+
+/// Gradient descent with heavy-ball momentum, which argmin doesn't provide.
+///
+/// Every iteration the velocity decays by `beta` and takes a fixed step `learning_rate` down
+/// the gradient, then the param moves by the velocity:
+/// $v_{k+1} = \beta v_k - \eta \nabla f(x_k)$, $x_{k+1} = x_k + v_{k+1}$. Along a narrow valley
+/// the gradients across it cancel out while those along it add up, so it keeps speeding
+/// towards the minimum where plain descent (`beta = 0`) crawls, but it can overshoot and
+/// oscillate around it.
+///
+/// The iteration log has a `speed` entry with the norm of the velocity.
+#[derive(Debug, Clone)]
+pub struct Momentum {
+    learning_rate: f64,
+    beta: f64,
+    tol: f64,
+    velocity: Option<Array1<f64>>,
+    converged: bool,
+}
+
+impl Momentum {
+    /// With `learning_rate = 0.001` and `beta = 0.9`.
+    pub fn new() -> Self {
+        Self {
+            learning_rate: 0.001,
+            beta: 0.9,
+            tol: f64::EPSILON.sqrt(),
+            velocity: None,
+            converged: false,
+        }
+    }
+
+    /// Step `eta` taken down the gradient every iteration, positive.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Result<Self, Error> {
+        if !(learning_rate > 0.0 && learning_rate.is_finite()) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`learning_rate` must be positive, got {learning_rate}"),
+            }
+            .into());
+        }
+        self.learning_rate = learning_rate;
+        Ok(self)
+    }
+
+    /// Fraction `beta` of the velocity kept every iteration, in `[0, 1)`.
+    pub fn with_beta(mut self, beta: f64) -> Result<Self, Error> {
+        if !(0.0..1.0).contains(&beta) {
+            return Err(ArgminError::InvalidParameter {
+                text: format!("`beta` must be in [0, 1), got {beta}"),
+            }
+            .into());
+        }
+        self.beta = beta;
+        Ok(self)
+    }
+
+    pub fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+}
+
+impl Default for Momentum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> Solver<O, MomentumState> for Momentum
+where
+    O: CostFunction<Param = Array1<f64>, Output = f64>
+        + Gradient<Param = Array1<f64>, Gradient = Array1<f64>>,
+{
+    const NAME: &'static str = "Momentum";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: MomentumState,
+    ) -> Result<(MomentumState, Option<KV>), Error> {
+        let param = state
+            .get_param()
+            .ok_or_else(|| ArgminError::NotInitialized {
+                text: "`Momentum` requires an initial parameter vector".to_string(),
+            })?;
+        self.velocity = Some(Array1::zeros(param.len()));
+        let cost = problem.cost(param)?;
+        Ok((state.cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: MomentumState,
+    ) -> Result<(MomentumState, Option<KV>), Error> {
+        let param = state.get_param().unwrap().clone();
+        let gradient = problem.gradient(&param)?;
+        if gradient.dot(&gradient).sqrt() < self.tol {
+            self.converged = true;
+            let cost = state.get_cost();
+            return Ok((state.param(param).cost(cost).gradient(gradient), None));
+        }
+        let velocity = self.velocity.take().unwrap() * self.beta - &gradient * self.learning_rate;
+        let param = param + &velocity;
+        let cost = problem.cost(&param)?;
+        let kv = kv!("speed" => velocity.dot(&velocity).sqrt(););
+        self.velocity = Some(velocity);
+        Ok((state.param(param).cost(cost).gradient(gradient), Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &MomentumState) -> TerminationStatus {
+        match self.converged {
+            true => TerminationStatus::Terminated(TerminationReason::SolverConverged),
+            false => TerminationStatus::NotTerminated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::core::Executor;
+    use ndarray::array;
+
+    use super::*;
+    use crate::{Ellipsoid, EllipsoidKind};
+
+    #[test]
+    fn test_beats_plain_descent() {
+        // Curvatures from 2 to 200, with the usual step of one over the largest.
+        let problem = Ellipsoid::new(EllipsoidKind::Ellipsoid, 2).with_condition(100.0);
+        let target = 1e-8;
+        let iterations = |beta: f64| {
+            let solver = Momentum::new()
+                .with_learning_rate(0.005)
+                .unwrap()
+                .with_beta(beta)
+                .unwrap();
+            let res = Executor::new(problem, solver)
+                .configure(|state| {
+                    state
+                        .param(array![1.0, 1.0])
+                        .max_iters(10_000)
+                        .target_cost(target)
+                })
+                .run()
+                .unwrap();
+            assert!(res.state.get_best_cost() <= target, "beta {beta}");
+            res.state.get_iter()
+        };
+        let (plain, momentum) = (iterations(0.0), iterations(0.9));
+        assert!(momentum * 3 < plain, "{momentum} vs {plain}");
+
+        assert!(Momentum::new().with_beta(1.0).is_err());
+        assert!(Momentum::new().with_learning_rate(0.0).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    error, fmt,
+    sync::{Arc, Mutex},
+};
+
+use argmin::{
+    core::{
+        ArgminError, Error, IterState, Problem, Solver, State, TerminationReason,
+        TerminationStatus, KV,
+    },
+    solver::linesearch::LineSearch,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    results::{format_float, DEFAULT_PRECISION},
+    runner::ToParamVec,
+};
+
+/// Smallest, mean and largest step length accepted by the line search of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StepStats {
+    pub min: f64,
+    pub mean: f64,
+    pub max: f64,
+    /// Line searches run, one per iteration of the solver.
+    pub count: usize,
+}
+
+impl StepStats {
+    /// `None` without any step.
+    pub fn new(steps: &[f64]) -> Option<Self> {
+        if steps.is_empty() {
+            return None;
+        }
+        Some(Self {
+            min: steps.iter().copied().fold(f64::INFINITY, f64::min),
+            mean: steps.iter().sum::<f64>() / steps.len() as f64,
+            max: steps.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            count: steps.len(),
+        })
+    }
+}
+
+/// The precision, e.g. `{:.3}`, gives the significant digits of the step lengths.
+impl fmt::Display for StepStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(DEFAULT_PRECISION);
+        write!(
+            f,
+            "{} ({}-{})",
+            format_float(self.mean, digits),
+            format_float(self.min, digits),
+            format_float(self.max, digits)
+        )
+    }
+}
+
+/// Step lengths accepted by the line searches wrapped with [`StepLengths::record`], shared
+/// between clones like the observers, as the solvers clone their line search for every
+/// iteration.
+#[derive(Debug, Clone, Default)]
+pub struct StepLengths {
+    steps: Arc<Mutex<Vec<f64>>>,
+}
+
+impl StepLengths {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record<L>(&self, linesearch: L) -> RecordSteps<L> {
+        RecordSteps {
+            linesearch,
+            direction_norm: f64::NAN,
+            start: Vec::new(),
+            steps: self.clone(),
+        }
+    }
+
+    /// Removes the steps recorded so far, so the next run of the same solver starts afresh.
+    pub fn take(&self) -> Vec<f64> {
+        std::mem::take(&mut *self.steps.lock().unwrap())
+    }
+
+    pub fn take_stats(&self) -> Option<StepStats> {
+        StepStats::new(&self.take())
+    }
+}
+
+/// Iterations after which a line search wrapped with [`StepLengths::record`] is stopped. Argmin
+/// runs line searches without an iteration cap, and More-Thuente can loop forever once the step
+/// stops changing the param (e.g. DFP on Whitley): the solver then fails instead of hanging.
+pub const MAX_LINESEARCH_ITERS: u64 = 1000;
+
+/// Error raised by a line search wrapped with [`StepLengths::record`], displayed as the error it
+/// wraps. It survives the solvers passing it on, so
+/// [`is_linesearch_error`](crate::runner::is_linesearch_error) can tell line-search failures
+/// apart by type.
+#[derive(Debug)]
+pub struct LineSearchError(Error);
+
+impl LineSearchError {
+    fn wrap(error: Error) -> Error {
+        Self(error).into()
+    }
+}
+
+impl fmt::Display for LineSearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl error::Error for LineSearchError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Line search recording the length of the step it accepts, as a multiple of the search
+/// direction: $ \alpha = \lVert x_{k+1} - x_k \rVert / \lVert d_k \rVert $. Argmin's line
+/// searches don't report it.
+#[derive(Debug, Clone)]
+pub struct RecordSteps<L> {
+    linesearch: L,
+    direction_norm: f64,
+    /// Param the line search started from.
+    start: Vec<f64>,
+    steps: StepLengths,
+}
+
+impl<L> RecordSteps<L> {
+    /// Replaces the step of the running line search with the one reaching `param`.
+    fn update(&self, param: Option<&impl ToParamVec>) {
+        let Some(param) = param else {
+            return;
+        };
+        let distance = param
+            .to_param_vec()
+            .iter()
+            .zip(&self.start)
+            .map(|(x, s)| (x - s).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        if let Some(step) = self.steps.steps.lock().unwrap().last_mut() {
+            *step = distance / self.direction_norm;
+        }
+    }
+}
+
+impl<L, P, F> LineSearch<P, F> for RecordSteps<L>
+where
+    L: LineSearch<P, F>,
+    P: ToParamVec,
+{
+    fn search_direction(&mut self, direction: P) {
+        self.direction_norm = direction
+            .to_param_vec()
+            .iter()
+            .map(|x| x.powi(2))
+            .sum::<f64>()
+            .sqrt();
+        self.linesearch.search_direction(direction);
+    }
+
+    fn initial_step_length(&mut self, step_length: F) -> Result<(), Error> {
+        self.linesearch.initial_step_length(step_length)
+    }
+}
+
+impl<O, L, P, G, F> Solver<O, IterState<P, G, (), (), F>> for RecordSteps<L>
+where
+    L: Solver<O, IterState<P, G, (), (), F>>,
+    IterState<P, G, (), (), F>: State<Param = P>,
+    P: ToParamVec,
+{
+    const NAME: &'static str = L::NAME;
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        self.start = state
+            .get_param()
+            .map(ToParamVec::to_param_vec)
+            .unwrap_or_default();
+        self.steps.steps.lock().unwrap().push(0.0);
+        let (state, kv) = self
+            .linesearch
+            .init(problem, state)
+            .map_err(LineSearchError::wrap)?;
+        self.update(state.get_param());
+        Ok((state, kv))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let (state, kv) = self
+            .linesearch
+            .next_iter(problem, state)
+            .map_err(LineSearchError::wrap)?;
+        // Stopped by the cap below without ever accepting a step, the solver would be left
+        // without a param.
+        if state.get_param().is_none() && state.get_iter() + 1 >= MAX_LINESEARCH_ITERS {
+            return Err(LineSearchError::wrap(
+                ArgminError::ConditionViolated {
+                    text: format!(
+                        "`{}`: no step found within {MAX_LINESEARCH_ITERS} iterations",
+                        L::NAME
+                    ),
+                }
+                .into(),
+            ));
+        }
+        self.update(state.get_param());
+        Ok((state, kv))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), (), F>) -> TerminationStatus {
+        match state.get_iter() >= MAX_LINESEARCH_ITERS {
+            true => TerminationStatus::Terminated(TerminationReason::MaxItersReached),
+            false => self.linesearch.terminate(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::Executor,
+        solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch},
+    };
+    use ndarray::array;
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    #[test]
+    fn test_one_step_per_iteration() {
+        let steps = StepLengths::new();
+        let solver = SteepestDescent::new(steps.record(MoreThuenteLineSearch::new()));
+        let res = Executor::new(RosenbrockND::default(), solver)
+            .configure(|state| state.param(array![-1.2, 1.0]).max_iters(25))
+            .run()
+            .unwrap();
+        let recorded = steps.take();
+        assert_eq!(recorded.len() as u64, res.state.get_iter());
+        assert!(
+            recorded.iter().all(|&x| x > 0.0 && x.is_finite()),
+            "{recorded:?}"
+        );
+        assert!(steps.take_stats().is_none());
+
+        let stats = StepStats::new(&[0.5, 1.0, 3.0]).unwrap();
+        assert_eq!((stats.min, stats.mean, stats.max), (0.5, 1.5, 3.0));
+        assert_eq!(stats.to_string(), "1.5 (0.5-3)");
+    }
+}
@@ -0,0 +1,130 @@
+use argmin::core::{CostFunction, Error};
+use ndarray::{array, Array1, Array2};
+
+/// Samples the cost of a 2-D problem on a regular `rows x cols` grid spanning `lower` to `upper`.
+///
+/// Row 0 is the lowest `y`, column 0 the lowest `x`, both ends of the range are included.
+pub fn cost_surface<P>(
+    problem: &P,
+    lower: &Array1<f64>,
+    upper: &Array1<f64>,
+    (rows, cols): (usize, usize),
+) -> Result<Array2<f64>, Error>
+where
+    P: CostFunction<Param = Array1<f64>, Output = f64>,
+{
+    let mut surface = Array2::zeros((rows, cols));
+    for ((i, j), cost) in surface.indexed_iter_mut() {
+        let param = array![
+            lerp(lower[0], upper[0], j, cols),
+            lerp(lower[1], upper[1], i, rows)
+        ];
+        *cost = problem.cost(&param)?;
+    }
+    Ok(surface)
+}
+
+/// `k`-th of `n` evenly spaced values from `a` to `b`.
+fn lerp(a: f64, b: f64, k: usize, n: usize) -> f64 {
+    if n < 2 {
+        return (a + b) / 2.0;
+    }
+    a + (b - a) * k as f64 / (n - 1) as f64
+}
+
+/// Shades from low to high cost, used when colors are disabled.
+const SHADES: &[u8] = b" .:-=+*#%@";
+
+/// Renders a surface from [`cost_surface`] as a block of text, highest `y` on top. Costs are
+/// shaded on a log scale so valleys stay visible next to huge values. `mark` is a grid cell
+/// `(row, col)` drawn as `X`, e.g. the known optimum.
+///
+/// With `color` every cell is an ANSI 256-color background block, otherwise an ASCII shade.
+pub fn render_heatmap(surface: &Array2<f64>, mark: Option<(usize, usize)>, color: bool) -> String {
+    let finite = surface.iter().copied().filter(|x| x.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+    let scale = (max - min).ln_1p();
+    let level = |x: f64| {
+        if !x.is_finite() || scale <= 0.0 {
+            return 0.0;
+        }
+        (x - min).ln_1p() / scale
+    };
+
+    let mut out = String::new();
+    for i in (0..surface.nrows()).rev() {
+        for j in 0..surface.ncols() {
+            let level = level(surface[[i, j]]);
+            let marked = mark == Some((i, j));
+            match (color, marked) {
+                (true, true) => out.push_str("\x1b[1;97;41mX"),
+                (true, false) => {
+                    // Grayscale ramp of the 256-color palette, 232 (dark) to 255 (light).
+                    let code = 232 + (level * 23.0).round() as u8;
+                    out.push_str(&format!("\x1b[48;5;{code}m "));
+                }
+                (false, true) => out.push('X'),
+                (false, false) => {
+                    let k = (level * (SHADES.len() - 1) as f64).round() as usize;
+                    out.push(SHADES[k] as char);
+                }
+            }
+        }
+        if color {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Grid cell of `point` on a surface sampled by [`cost_surface`], `None` if it's outside.
+pub fn grid_cell(
+    point: &[f64],
+    lower: &Array1<f64>,
+    upper: &Array1<f64>,
+    (rows, cols): (usize, usize),
+) -> Option<(usize, usize)> {
+    let index = |x: f64, a: f64, b: f64, n: usize| {
+        let t = (x - a) / (b - a);
+        (0.0..=1.0)
+            .contains(&t)
+            .then(|| (t * (n - 1) as f64).round() as usize)
+    };
+    Some((
+        index(point[1], lower[1], upper[1], rows)?,
+        index(point[0], lower[0], upper[0], cols)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RosenbrockND;
+
+    #[test]
+    fn test_cost_surface() {
+        let f = RosenbrockND::default();
+        let (lower, upper) = (array![-2.0, -1.0], array![2.0, 3.0]);
+        let surface = cost_surface(&f, &lower, &upper, (5, 9)).unwrap();
+        assert_eq!(surface.dim(), (5, 9));
+        assert_eq!(surface[[0, 0]], f.cost(&lower).unwrap());
+        assert_eq!(surface[[4, 8]], f.cost(&upper).unwrap());
+        // (1, 1) is on the grid: column 6, row 2.
+        assert_eq!(grid_cell(&[1.0, 1.0], &lower, &upper, (5, 9)), Some((2, 6)));
+        assert_eq!(surface[[2, 6]], 0.0);
+        assert_eq!(grid_cell(&[5.0, 1.0], &lower, &upper, (5, 9)), None);
+    }
+
+    #[test]
+    fn test_render_heatmap() {
+        let surface = array![[0.0, 1.0, 2.0], [3.0, 4.0, 100.0]];
+        let plain = render_heatmap(&surface, Some((0, 0)), false);
+        assert_eq!(plain.lines().collect::<Vec<_>>(), ["--@", "X.:"]);
+        let colored = render_heatmap(&surface, None, true);
+        assert_eq!(colored.lines().count(), 2);
+        assert!(colored.contains("\x1b[48;5;232m"));
+        assert!(colored.contains("\x1b[48;5;255m"));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+
+use argmin::core::{Error, Problem, Solver, State, TerminationReason, TerminationStatus, KV};
+
+use crate::{optimum::ParamTarget, runner::ToParamVec};
+
+/// Termination message used when the best param gets close enough to a known optimum.
+pub const PARAM_TARGET_REACHED: &str = "Best param within tolerance of the optimum";
+
+/// Termination message used when the best cost stopped improving, see [`Stall`].
+pub const STALLED: &str = "Stalled";
+
+/// Stops a run once its best cost improved by at most `tol` over the last `iters` iterations,
+/// e.g. a simplex shrinking on a plateau. Runs without a finite best cost never stall.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stall {
+    pub iters: usize,
+    pub tol: f64,
+}
+
+/// Wraps a solver to add termination criteria on top of its own.
+///
+/// Observers only get a shared reference to the state, so they can't end a run. Overriding
+/// `Solver::terminate` is the only hook argmin checks after every iteration, hence the best
+/// costs watched for a stall are kept here rather than in an observer.
+#[derive(Debug, Clone)]
+pub struct Stopping<S> {
+    solver: S,
+    param_target: Option<ParamTarget>,
+    stall: Option<Stall>,
+    /// Best cost of the last `stall.iters + 1` iterations, oldest first, with the iteration of
+    /// the newest one.
+    best_costs: VecDeque<f64>,
+    last_iter: Option<u64>,
+}
+
+impl<S> Stopping<S> {
+    pub fn new(solver: S) -> Self {
+        Self {
+            solver,
+            param_target: None,
+            stall: None,
+            best_costs: VecDeque::new(),
+            last_iter: None,
+        }
+    }
+
+    pub fn param_target(mut self, param_target: Option<ParamTarget>) -> Self {
+        self.param_target = param_target;
+        self
+    }
+
+    pub fn stall(mut self, stall: Option<Stall>) -> Self {
+        self.stall = stall;
+        self
+    }
+
+    /// Records the best cost of the iteration, once per iteration, and tells whether the run
+    /// stalled.
+    fn stalled(&mut self, iter: u64, best_cost: f64) -> bool {
+        let Some(stall) = self.stall else {
+            return false;
+        };
+        if self.last_iter == Some(iter) {
+            return false;
+        }
+        self.last_iter = Some(iter);
+        self.best_costs.push_back(best_cost);
+        if self.best_costs.len() > stall.iters + 1 {
+            self.best_costs.pop_front();
+        }
+        match (self.best_costs.front(), self.best_costs.back()) {
+            (Some(&oldest), Some(&newest))
+                if self.best_costs.len() > stall.iters
+                    && oldest.is_finite()
+                    && newest.is_finite() =>
+            {
+                oldest - newest <= stall.tol
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<O, S, I> Solver<O, I> for Stopping<S>
+where
+    S: Solver<O, I>,
+    I: State<Float = f64>,
+    I::Param: ToParamVec,
+{
+    const NAME: &'static str = S::NAME;
+
+    fn init(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        self.solver.init(problem, state)
+    }
+
+    fn next_iter(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        self.solver.next_iter(problem, state)
+    }
+
+    fn terminate(&mut self, state: &I) -> TerminationStatus {
+        let status = self.solver.terminate(state);
+        if status.terminated() {
+            return status;
+        }
+        let param_reached = match (&self.param_target, state.get_best_param()) {
+            (Some(target), Some(param)) => target.reached(&param.to_param_vec()),
+            _ => false,
+        };
+        if param_reached {
+            return TerminationStatus::Terminated(TerminationReason::SolverExit(
+                PARAM_TARGET_REACHED.to_string(),
+            ));
+        }
+        if self.stalled(state.get_iter(), state.get_best_cost()) {
+            return TerminationStatus::Terminated(TerminationReason::SolverExit(
+                STALLED.to_string(),
+            ));
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::{
+        core::{CostFunction, Executor},
+        solver::neldermead::NelderMead,
+    };
+    use ndarray::{array, Array1};
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    /// Flat everywhere, no move ever improves the cost.
+    struct Plateau;
+
+    impl CostFunction for Plateau {
+        type Param = Array1<f64>;
+        type Output = f64;
+
+        fn cost(&self, _param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(1.0)
+        }
+    }
+
+    #[test]
+    fn test_param_target() {
+        let problem = RosenbrockND::default();
+        let target = ParamTarget::new(&problem, 1e-2);
+        let simplex = vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+        let res = Executor::new(
+            problem,
+            Stopping::new(NelderMead::new(simplex)).param_target(target),
+        )
+        .configure(|state| state.max_iters(1000))
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverExit(
+                PARAM_TARGET_REACHED.to_string()
+            ))
+        );
+        assert!(res.state.get_iter() < 1000);
+    }
+
+    #[test]
+    fn test_stall() {
+        let stall = Some(Stall {
+            iters: 5,
+            tol: 1e-12,
+        });
+        let simplex = vec![array![-1.0, 3.0], array![2.0, 1.5], array![2.0, -1.0]];
+        // Without a tolerance on the spread of the simplex, Nelder-Mead never converges.
+        let solver = NelderMead::new(simplex.clone())
+            .with_sd_tolerance(0.0)
+            .unwrap();
+        let res = Executor::new(Plateau, Stopping::new(solver).stall(stall))
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverExit(STALLED.to_string()))
+        );
+        assert_eq!(res.state.get_iter(), 5);
+
+        // Steady progress never stalls.
+        let res = Executor::new(
+            RosenbrockND::default(),
+            Stopping::new(NelderMead::new(simplex)).stall(stall),
+        )
+        .configure(|state| state.max_iters(20))
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::MaxItersReached)
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use argmin::core::{ArgminError, CostFunction, Error, Gradient, Hessian};
+use argmin_testfunctions as tf;
+use ndarray::{Array1, Array2};
+
+use crate::problems::Derivatives;
+
+type CostFn = fn(&[f64]) -> f64;
+
+/// Gradient, or hessian flattened row by row.
+type DerivativeFn = fn(&[f64]) -> Vec<f64>;
+
+/// Problem wrapping the plain functions of `argmin_testfunctions`, so the cost and its
+/// derivatives come straight from there. Derivatives the crate doesn't provide return an error,
+/// as for [`Problem`](crate::Problem).
+///
+/// Functions defined in a single dimension check the length of the param instead of panicking
+/// like the original ones.
+#[derive(Debug, Clone, Copy)]
+pub struct TestFnProblem {
+    name: &'static str,
+    dim: Option<usize>,
+    cost: CostFn,
+    gradient: Option<DerivativeFn>,
+    hessian: Option<DerivativeFn>,
+}
+
+impl TestFnProblem {
+    /// Problem in any dimension, without derivatives.
+    pub fn new(name: &'static str, cost: CostFn) -> Self {
+        Self {
+            name,
+            dim: None,
+            cost,
+            gradient: None,
+            hessian: None,
+        }
+    }
+
+    /// Restricts the problem to `dim` dimensions.
+    pub fn with_dim(mut self, dim: usize) -> Self {
+        self.dim = Some(dim);
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: DerivativeFn) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// `hessian` returns the matrix flattened row by row.
+    pub fn with_hessian(mut self, hessian: DerivativeFn) -> Self {
+        self.hessian = Some(hessian);
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// `None` when defined in any dimension.
+    pub fn dim(&self) -> Option<usize> {
+        self.dim
+    }
+
+    /// Rosenbrock in 2-D with `a = 1` and `b = 100`, with its gradient and hessian.
+    pub fn rosenbrock() -> Self {
+        Self::new("rosenbrock", |x| tf::rosenbrock_2d(x, 1.0, 100.0))
+            .with_dim(2)
+            .with_gradient(|x| tf::rosenbrock_2d_derivative(x, 1.0, 100.0))
+            .with_hessian(|x| tf::rosenbrock_2d_hessian(x, 1.0, 100.0))
+    }
+
+    /// Sphere, with its gradient.
+    pub fn sphere() -> Self {
+        Self::new("sphere", tf::sphere).with_gradient(tf::sphere_derivative)
+    }
+
+    pub fn ackley() -> Self {
+        Self::new("ackley", tf::ackley)
+    }
+
+    pub fn rastrigin() -> Self {
+        Self::new("rastrigin", tf::rastrigin)
+    }
+
+    pub fn levy() -> Self {
+        Self::new("levy", tf::levy)
+    }
+
+    pub fn styblinski_tang() -> Self {
+        Self::new("styblinski-tang", tf::styblinski_tang)
+    }
+
+    pub fn beale() -> Self {
+        Self::new("beale", tf::beale).with_dim(2)
+    }
+
+    pub fn booth() -> Self {
+        Self::new("booth", tf::booth).with_dim(2)
+    }
+
+    pub fn bukin_n6() -> Self {
+        Self::new("bukin-n6", tf::bukin_n6).with_dim(2)
+    }
+
+    pub fn cross_in_tray() -> Self {
+        Self::new("cross-in-tray", tf::cross_in_tray).with_dim(2)
+    }
+
+    pub fn easom() -> Self {
+        Self::new("easom", tf::easom).with_dim(2)
+    }
+
+    pub fn eggholder() -> Self {
+        Self::new("eggholder", tf::eggholder).with_dim(2)
+    }
+
+    pub fn goldstein_price() -> Self {
+        Self::new("goldstein-price", tf::goldsteinprice).with_dim(2)
+    }
+
+    pub fn himmelblau() -> Self {
+        Self::new("himmelblau", tf::himmelblau).with_dim(2)
+    }
+
+    pub fn holder_table() -> Self {
+        Self::new("holder-table", tf::holder_table).with_dim(2)
+    }
+
+    pub fn levy_n13() -> Self {
+        Self::new("levy-n13", tf::levy_n13).with_dim(2)
+    }
+
+    pub fn matyas() -> Self {
+        Self::new("matyas", tf::matyas).with_dim(2)
+    }
+
+    pub fn mccormick() -> Self {
+        Self::new("mccormick", tf::mccorminck).with_dim(2)
+    }
+
+    pub fn picheny() -> Self {
+        Self::new("picheny", tf::picheny).with_dim(2)
+    }
+
+    pub fn schaffer_n2() -> Self {
+        Self::new("schaffer-n2", tf::schaffer_n2).with_dim(2)
+    }
+
+    pub fn schaffer_n4() -> Self {
+        Self::new("schaffer-n4", tf::schaffer_n4).with_dim(2)
+    }
+
+    pub fn three_hump_camel() -> Self {
+        Self::new("three-hump-camel", tf::threehumpcamel).with_dim(2)
+    }
+
+    fn check_dim(&self, param: &Array1<f64>) -> Result<(), Error> {
+        match self.dim {
+            Some(dim) if dim != param.len() => Err(ArgminError::InvalidParameter {
+                text: format!(
+                    "`{}` is only defined in {dim} dimensions, got {}",
+                    self.name,
+                    param.len()
+                ),
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn not_implemented(&self, what: &str) -> Error {
+        ArgminError::NotImplemented {
+            text: format!("`{}` does not provide {what}", self.name),
+        }
+        .into()
+    }
+}
+
+impl CostFunction for TestFnProblem {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.check_dim(param)?;
+        Ok((self.cost)(&param.to_vec()))
+    }
+}
+
+impl Gradient for TestFnProblem {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.check_dim(param)?;
+        let gradient = self
+            .gradient
+            .ok_or_else(|| self.not_implemented("a gradient"))?;
+        Ok(Array1::from(gradient(&param.to_vec())))
+    }
+}
+
+impl Hessian for TestFnProblem {
+    type Param = Array1<f64>;
+    type Hessian = Array2<f64>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.check_dim(param)?;
+        let hessian = self
+            .hessian
+            .ok_or_else(|| self.not_implemented("a hessian"))?;
+        let n = param.len();
+        Ok(Array2::from_shape_vec((n, n), hessian(&param.to_vec()))?)
+    }
+}
+
+impl Derivatives for TestFnProblem {
+    fn has_gradient(&self) -> bool {
+        self.gradient.is_some()
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.hessian.is_some()
+    }
+
+    fn has_anneal(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::RosenbrockND;
+
+    #[test]
+    fn test_rosenbrock_matches() {
+        let f = TestFnProblem::rosenbrock();
+        let g = RosenbrockND::default();
+        for param in [array![-1.2, 1.0], array![0.5, -0.3], array![1.0, 1.0]] {
+            assert!((f.cost(&param).unwrap() - g.cost(&param).unwrap()).abs() < 1e-12);
+            let diff = f.gradient(&param).unwrap() - g.gradient(&param).unwrap();
+            assert!(diff.iter().all(|x| x.abs() < 1e-12));
+            let diff = f.hessian(&param).unwrap() - g.hessian(&param).unwrap();
+            assert!(diff.iter().all(|x| x.abs() < 1e-12));
+        }
+        assert!(f.cost(&array![1.0, 1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_missing_derivatives() {
+        let f = TestFnProblem::himmelblau();
+        assert_eq!(f.cost(&array![3.0, 2.0]).unwrap(), 0.0);
+        assert!(!f.has_gradient());
+        assert!(f.gradient(&array![3.0, 2.0]).is_err());
+
+        let f = TestFnProblem::sphere();
+        assert!(f.has_gradient() && !f.has_hessian());
+        assert_eq!(
+            f.gradient(&array![1.0, -2.0, 0.5]).unwrap(),
+            array![2.0, -4.0, 1.0]
+        );
+        assert!(f.hessian(&array![1.0, -2.0, 0.5]).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Param and cost after every iteration of a run, recorded by
+/// [`TrajectoryObserver`](crate::observers::TrajectoryObserver).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trajectory {
+    pub params: Vec<Vec<f64>>,
+    pub costs: Vec<f64>,
+}
+
+impl Trajectory {
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    fn dim(&self) -> usize {
+        self.params.first().map_or(0, Vec::len)
+    }
+
+    /// One row per iteration: `x0,x1,...,cost`.
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut header = (0..self.dim()).map(|i| format!("x{i}")).collect::<Vec<_>>();
+        header.push("cost".to_string());
+        writeln!(writer, "{}", header.join(","))?;
+        for (param, cost) in self.params.iter().zip(&self.costs) {
+            let mut row = param.iter().map(f64::to_string).collect::<Vec<_>>();
+            row.push(cost.to_string());
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// The same rows as [`Self::write_csv`] as a `(iterations, dim + 1)` array of `f64` in
+    /// NumPy's `.npy` format (version 1.0), readable with `numpy.load`.
+    pub fn write_npy(&self, mut writer: impl Write) -> io::Result<()> {
+        const MAGIC: &[u8] = b"\x93NUMPY\x01\x00";
+        let mut header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+            self.len(),
+            self.dim() + 1
+        );
+        // The magic, the header length and the header, ending with a newline, are padded to a
+        // multiple of 64 bytes so the data is aligned.
+        let unpadded = MAGIC.len() + 2 + header.len() + 1;
+        header.push_str(&" ".repeat(unpadded.next_multiple_of(64) - unpadded));
+        header.push('\n');
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        for (param, cost) in self.params.iter().zip(&self.costs) {
+            for x in param.iter().chain([cost]) {
+                writer.write_all(&x.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a `.npy` file when `path` has that extension, a CSV otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let file = io::BufWriter::new(fs::File::create(path)?);
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("npy") => self.write_npy(file),
+            _ => self.write_csv(file),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argmin::solver::{linesearch::MoreThuenteLineSearch, quasinewton::LBFGS};
+    use ndarray::array;
+
+    use crate::{run_solver, RosenbrockND, RunOptions};
+
+    #[test]
+    fn test_rows_match_iterations() {
+        let options = RunOptions {
+            trajectory: true,
+            quiet: true,
+            ..Default::default()
+        };
+        let result = run_solver(
+            "",
+            "L-BFGS",
+            RosenbrockND::default(),
+            LBFGS::new(MoreThuenteLineSearch::new(), 5),
+            |state| state.param(array![-1.2, 1.0]).max_iters(100),
+            &options,
+        );
+        let trajectory = result.trajectory.unwrap();
+        assert_eq!(trajectory.len() as u64, result.iterations);
+
+        let mut csv = Vec::new();
+        trajectory.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "x0,x1,cost");
+        assert_eq!(lines.len() as u64, result.iterations + 1);
+
+        let mut npy = Vec::new();
+        trajectory.write_npy(&mut npy).unwrap();
+        let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+        assert!(
+            header.contains(&format!("'shape': ({}, 3)", result.iterations)),
+            "{header}"
+        );
+        assert_eq!(npy.len(), 10 + header_len + trajectory.len() * 3 * 8);
+    }
+}
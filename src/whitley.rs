@@ -0,0 +1,121 @@
+use argmin::core::{CostFunction, Gradient};
+use ndarray::Array1;
+
+/// The Whitley function, a Griewank-style composite of every pairwise Rosenbrock term:
+/// $ f(x) = \sum_{i=1}^{d} \sum_{j=1}^{d} \left( \frac{y_{ij}^2}{4000} - \cos(y_{ij}) + 1 \right) $
+/// with $ y_{ij} = 100 (x_j - x_i^2)^2 + (1 - x_i)^2 $.
+///
+/// It is evaluated on $[-10.24, 10.24]^d$ with its global minimum $f = 0$ at $(1, \dots, 1)$.
+/// Non-separable and extremely rugged, the cosine of the Rosenbrock terms puts a local minimum in
+/// every ripple of the valley.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Whitley {
+    dim: usize,
+}
+
+impl Whitley {
+    pub fn new(dim: usize) -> Self {
+        Self { dim }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (
+            Array1::from_elem(self.dim, -10.24),
+            Array1::from_elem(self.dim, 10.24),
+        )
+    }
+
+    /// The global minimizer $(1, \dots, 1)$.
+    pub fn optimum(&self) -> Array1<f64> {
+        Array1::ones(self.dim)
+    }
+}
+
+impl Default for Whitley {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+/// The Rosenbrock term $y_{ij}$.
+fn rosenbrock_term(xi: f64, xj: f64) -> f64 {
+    100.0 * (xj - xi.powi(2)).powi(2) + (1.0 - xi).powi(2)
+}
+
+impl CostFunction for Whitley {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
+        let cost = param
+            .iter()
+            .flat_map(|xi| param.iter().map(move |xj| rosenbrock_term(*xi, *xj)))
+            .map(|y| y.powi(2) / 4000.0 - y.cos() + 1.0)
+            .sum();
+        Ok(cost)
+    }
+}
+
+impl Gradient for Whitley {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, argmin::core::Error> {
+        let mut gradient = Array1::zeros(param.len());
+        for (i, xi) in param.iter().enumerate() {
+            for (j, xj) in param.iter().enumerate() {
+                let y = rosenbrock_term(*xi, *xj);
+                let outer = y / 2000.0 + y.sin();
+                let inner = xj - xi.powi(2);
+                gradient[i] += outer * (-400.0 * xi * inner - 2.0 * (1.0 - xi));
+                gradient[j] += outer * 200.0 * inner;
+            }
+        }
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    #[test]
+    fn test_optimum() {
+        for dim in 1..=6 {
+            let f = Whitley::new(dim);
+            let optimum = f.optimum();
+            assert!(f.cost(&optimum).unwrap().abs() < 1e-15, "{dim}");
+            assert!(f
+                .gradient(&optimum)
+                .unwrap()
+                .iter()
+                .all(|g| g.abs() < 1e-12));
+        }
+        assert!(Whitley::new(3).cost(&array![0.5, -1.0, 2.0]).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_gradient_finite_differences() {
+        let f = Whitley::new(3);
+        let param = array![0.3, -0.4, 0.6];
+        let gradient = f.gradient(&param).unwrap();
+        let h = 1e-7;
+        for k in 0..param.len() {
+            let (mut forward, mut backward) = (param.clone(), param.clone());
+            forward[k] += h;
+            backward[k] -= h;
+            let diff = (f.cost(&forward).unwrap() - f.cost(&backward).unwrap()) / (2.0 * h);
+            assert!(
+                (diff - gradient[k]).abs() < 1e-4 * gradient[k].abs().max(1.0),
+                "{k}: {diff} vs {}",
+                gradient[k]
+            );
+        }
+    }
+}